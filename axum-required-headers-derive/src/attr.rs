@@ -0,0 +1,806 @@
+//! Parsing of the `#[header(...)]` and `#[headers(...)]` attributes.
+
+use quote::{ToTokens, quote};
+use syn::{
+    Attribute, Expr, ExprRange, Ident, LitInt, LitStr, Path, Token, Type, parenthesized,
+    parse::ParseStream,
+};
+
+pub(crate) const FIELD_ATTRIBUTE_IDENT: &str = "header";
+pub(crate) const CONTAINER_ATTRIBUTE_IDENT: &str = "headers";
+pub(crate) const VARIANT_VALUE_ATTRIBUTE_IDENT: &str = "header_value";
+
+/// Options parsed from a field-level `#[header(...)]` attribute.
+///
+/// ```ignore
+/// #[header("x-user-id")]
+/// #[header(rename = "ETag")]
+/// #[header("x-offset", range(0..=10000))]
+/// #[header("x-api-key", min_len = 32, max_len = 64)]
+/// #[header("x-trace-id", pattern = "^[0-9a-f-]{36}$")]
+/// #[header("x-api-key", validate_with = check_key)] // with `#[headers(state = "...")]`
+/// #[header("x-region", default = "us-east-1")]
+/// #[header("x-event-time", datetime_fmt = "%Y-%m-%dT%H:%M:%S%z")]
+/// #[header("x-request-id", alias = "x-correlation-id")]
+/// #[header("x-api-version", media_param("accept", "version"))]
+/// #[header("x-signature", required_if = "x-signature-alg")] // `Option` fields only
+/// #[header("x-count", trim)]
+/// #[header("x-capabilities", list, skip_unknown)]
+/// #[header("x-signature", base64 = "url_safe")]
+/// #[header("x-ctx", base64, gzip, json)]
+/// #[header("idempotency-key", uuid)]
+/// #[header("x-timeout", duration_suffix)]
+/// #[header("x-tenant-id", otel_attr = "tenant.id")]
+/// #[header("x-request-id", trace)]
+/// #[header("x-tenant-id", into_extension)]
+/// #[header("x-user-id", or_extension)]
+/// #[header("authorization", sensitive)]
+/// #[header("x-org-id", non_empty)]
+/// #[header("x-api-version", or_query = "api_version")]
+/// #[header("x-user-id", infallible, to_header_value)] // `Header` types only
+/// #[header("x-forwarded-proto", warn_on_normalize)] // `Header` enums only
+/// #[header(prefix = "x-meta-")] // `Headers` map fields only
+/// #[header("authorization", typed)] // `headers::Header` types
+/// #[header(problems)] // `#[headers(infallible)]` structs only
+/// #[header(skip)]
+/// ```
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    /// The positional header name literal, e.g. `#[header("x-user-id")]`
+    pub name: Option<LitStr>,
+    /// `name = PATH`, the header name as a path to a `&'static str` const
+    pub name_const: Option<Path>,
+    /// `from_type`, takes the header name from the `RequiredHeader` impl of the field type
+    pub from_type: bool,
+    /// `rename = "..."`, composes with the container `prefix`
+    pub rename: Option<LitStr>,
+    /// `range(min..max)` or `range(min..=max)`, bounds the parsed value
+    pub range: Option<ExprRange>,
+    /// `min_len = N`, the minimum length of the value in bytes
+    pub min_len: Option<usize>,
+    /// `max_len = N`, the maximum length of the value in bytes
+    pub max_len: Option<usize>,
+    /// `pattern = "..."`, a regex the value has to match
+    pub pattern: Option<LitStr>,
+    /// `validate_with = path::to::fn`, checks the value against the struct `state`
+    pub validate_with: Option<Path>,
+    /// `default = "..."` or `default = expr`, used when a required header is absent
+    pub default: Option<FieldDefault>,
+    /// `datetime_fmt = "..."`, parses the value with `chrono::DateTime::parse_from_str`
+    pub datetime_fmt: Option<LitStr>,
+    /// `alias = "..."`, fallback header names tried in order when the header is absent
+    pub aliases: Vec<LitStr>,
+    /// `media_param("header", "param")`, falls back to a parameter of the media types in another
+    /// header, e.g. `version` in `Accept: application/json; version=2`
+    pub media_param: Option<(LitStr, LitStr)>,
+    /// `nth = N`, reads the zero-based Nth occurrence of the header instead of the first
+    pub nth: Option<usize>,
+    /// `required_if = "..."` or `required_if_present = "..."`, makes an `Option` field required
+    /// when the named header is present
+    pub required_if: Option<LitStr>,
+    /// `trim`, strips surrounding whitespace from the value before parsing
+    pub trim: bool,
+    /// `no_trim`, keeps surrounding whitespace even with the `default-trim` feature
+    pub no_trim: bool,
+    /// `list` (or `csv`), splits comma-separated values into the items of a `Vec<T>` field
+    pub list: bool,
+    /// `skip_unknown`, skips `list` items that fail to parse instead of rejecting the header
+    pub skip_unknown: bool,
+    /// `base64` or `base64 = "..."`, decodes the value before parsing
+    pub base64: Option<Base64Alphabet>,
+    /// `gzip`, decompresses the `base64`-decoded bytes
+    pub gzip: bool,
+    /// `json`, deserializes the (decoded) value with `serde_json` instead of `FromStr`
+    pub json: bool,
+    /// `uuid`, requires the value to be a hyphenated UUID
+    pub uuid: bool,
+    /// `duration_suffix`, parses a `Duration` from a number with a `ms`/`s`/`m`/`h` unit
+    pub duration_suffix: bool,
+    /// `otel_attr = "..."`, records the extracted value as an attribute of the active
+    /// OpenTelemetry span
+    pub otel_attr: Option<LitStr>,
+    /// `trace`, records the extracted value on the current `tracing` span and logs errors
+    pub trace: bool,
+    /// `into_extension`, inserts a clone of the extracted value into the request extensions
+    pub into_extension: bool,
+    /// `or_extension`, falls back to a value of the field type in the request extensions when
+    /// the header is absent
+    pub or_extension: bool,
+    /// `sensitive`, marks `HeaderValue` fields sensitive and forbids recording the value
+    pub sensitive: bool,
+    /// `non_empty`, treats an empty value like an absent header
+    pub non_empty: bool,
+    /// `or_query = "..."`, falls back to a URL query parameter when the header is absent
+    pub or_query: Option<LitStr>,
+    /// `infallible`, generates a `FromStr` impl for a `Header` newtype
+    pub infallible: bool,
+    /// `to_header_value`, generates a `to_header_value` method for a `Header` newtype
+    pub to_header_value: bool,
+    /// `warn_on_normalize`, logs enum values only matching a variant case-insensitively
+    pub warn_on_normalize: bool,
+    /// `prefix = "..."`, collects every header starting with the prefix into a map field
+    pub prefix: Option<LitStr>,
+    /// `typed`, decodes the values with `headers::Header::decode` instead of `FromStr`
+    pub typed: bool,
+    /// `problems`, marks the `Vec<HeaderError>` field of an `infallible` struct, on its own
+    pub problems: bool,
+    /// `skip`, marks a field not read from the headers but set to `Default::default()`, on its
+    /// own
+    pub skip: bool,
+}
+
+/// The alphabets supported by the `base64` field option.
+#[derive(Clone, Copy)]
+pub(crate) enum Base64Alphabet {
+    /// `base64` or `base64 = "standard"`, RFC 4648 §4 with required padding
+    Standard,
+    /// `base64 = "url_safe"`, RFC 4648 §5 with optional padding
+    UrlSafe,
+}
+
+impl Base64Alphabet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Token![=]) {
+            return Ok(Base64Alphabet::Standard);
+        }
+        input.parse::<Token![=]>()?;
+        let lit: LitStr = input.parse()?;
+        match lit.value().as_str() {
+            "standard" => Ok(Base64Alphabet::Standard),
+            "url_safe" => Ok(Base64Alphabet::UrlSafe),
+            other => Err(syn::Error::new_spanned(
+                &lit,
+                format!(
+                    "unsupported base64 alphabet `{other}`, expected \"standard\" or \"url_safe\""
+                ),
+            )),
+        }
+    }
+}
+
+/// Checks a `pattern` regex at compile time, so invalid patterns don't fail at request time.
+#[cfg(feature = "regex")]
+fn parse_pattern(lit: LitStr) -> syn::Result<LitStr> {
+    match regex::Regex::new(&lit.value()) {
+        Ok(_) => Ok(lit),
+        Err(error) => Err(syn::Error::new_spanned(
+            &lit,
+            format!("invalid `pattern`: {error}"),
+        )),
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+fn parse_pattern(lit: LitStr) -> syn::Result<LitStr> {
+    Err(syn::Error::new_spanned(
+        lit,
+        "`pattern` requires the `regex` feature of `axum-required-headers`",
+    ))
+}
+
+/// Whether the attribute starts with a header name const given positionally, e.g.
+/// `#[header(HeaderNames::USER_ID)]` or `#[header(X_USER_ID, trim)]`.
+///
+/// To tell it apart from an option key, the path must have several segments or, as consts do,
+/// no lowercase letters.
+fn is_positional_name_path(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let Ok(path) = fork.parse::<Path>() else {
+        return false;
+    };
+    let is_const_like = path.leading_colon.is_some()
+        || path.segments.len() > 1
+        || path
+            .get_ident()
+            .is_some_and(|ident| !ident.to_string().chars().any(|c| c.is_ascii_lowercase()));
+    is_const_like && (fork.is_empty() || fork.peek(Token![,]))
+}
+
+/// The value of a `default = ...` field option.
+pub(crate) enum FieldDefault {
+    /// A string literal, parsed with `FromStr` like a header value
+    Literal(LitStr),
+    /// An expression evaluating to the field type, e.g. `default = my_fn()`
+    Expr(Expr),
+}
+
+impl FieldDefault {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(FieldDefault::Literal(input.parse()?))
+        } else {
+            Ok(FieldDefault::Expr(input.parse()?))
+        }
+    }
+}
+
+impl ToTokens for FieldDefault {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            FieldDefault::Literal(lit) => lit.to_tokens(tokens),
+            FieldDefault::Expr(expr) => expr.to_tokens(tokens),
+        }
+    }
+}
+
+impl FieldAttr {
+    /// Parses the `#[header(...)]` attribute from a list of attributes, if present
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Option<(&Attribute, Self)>> {
+        let Some(attr) = find_attr(attrs, FIELD_ATTRIBUTE_IDENT) else {
+            return Ok(None);
+        };
+
+        let mut field_attr = FieldAttr::default();
+        attr.parse_args_with(|input: ParseStream| {
+            // markers of fields not read from the headers stand alone
+            if input
+                .fork()
+                .parse::<Ident>()
+                .is_ok_and(|key| key == "problems" || key == "skip")
+            {
+                let key: Ident = input.parse()?;
+                if !input.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!("`{key}` marks a field not read from the headers and can't be \
+                                 combined with other options"),
+                    ));
+                }
+                if key == "skip" {
+                    field_attr.skip = true;
+                } else {
+                    field_attr.problems = true;
+                }
+                return Ok(());
+            }
+
+            if input.peek(LitStr) {
+                field_attr.name = Some(input.parse()?);
+                if input.is_empty() {
+                    return Ok(());
+                }
+                input.parse::<Token![,]>()?;
+            } else if is_positional_name_path(input) {
+                field_attr.name_const = Some(input.parse()?);
+                if input.is_empty() {
+                    return Ok(());
+                }
+                input.parse::<Token![,]>()?;
+            }
+
+            while !input.is_empty() {
+                let key: Ident = input.parse()?;
+                match key.to_string().as_str() {
+                    "name" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.name_const = Some(input.parse()?);
+                    }
+                    "rename" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.rename = Some(input.parse()?);
+                    }
+                    "range" => {
+                        let content;
+                        parenthesized!(content in input);
+                        field_attr.range = Some(content.parse()?);
+                    }
+                    "min_len" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.min_len = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    "max_len" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.max_len = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    "pattern" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.pattern = Some(parse_pattern(input.parse()?)?);
+                    }
+                    "validate_with" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.validate_with = Some(input.parse()?);
+                    }
+                    "default" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.default = Some(FieldDefault::parse(input)?);
+                    }
+                    "trim" => field_attr.trim = true,
+                    "from_type" => field_attr.from_type = true,
+                    "no_trim" => field_attr.no_trim = true,
+                    // `csv` names the common single-line case, occurrences are joined alike
+                    "list" | "csv" => field_attr.list = true,
+                    "skip_unknown" => field_attr.skip_unknown = true,
+                    "infallible" => field_attr.infallible = true,
+                    "uuid" => field_attr.uuid = true,
+                    "gzip" => field_attr.gzip = true,
+                    "json" => field_attr.json = true,
+                    "typed" => field_attr.typed = true,
+                    "duration_suffix" => field_attr.duration_suffix = true,
+                    "trace" => field_attr.trace = true,
+                    "into_extension" => field_attr.into_extension = true,
+                    "or_extension" => field_attr.or_extension = true,
+                    "sensitive" => field_attr.sensitive = true,
+                    "non_empty" => field_attr.non_empty = true,
+                    "to_header_value" => field_attr.to_header_value = true,
+                    "warn_on_normalize" => field_attr.warn_on_normalize = true,
+                    "base64" => field_attr.base64 = Some(Base64Alphabet::parse(input)?),
+                    "case_sensitive" => {
+                        return Err(syn::Error::new_spanned(
+                            &key,
+                            "`case_sensitive` is not supported: `http::HeaderMap` normalizes header \
+                             names to lowercase, so the received casing is not available during \
+                             extraction",
+                        ));
+                    }
+                    "alias" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.aliases.push(input.parse()?);
+                    }
+                    "media_param" => {
+                        let content;
+                        parenthesized!(content in input);
+                        let header: LitStr = content.parse()?;
+                        content.parse::<Token![,]>()?;
+                        let param: LitStr = content.parse()?;
+                        for lit in [&header, &param] {
+                            if lit.value().is_empty() {
+                                return Err(syn::Error::new_spanned(
+                                    lit,
+                                    "`media_param` names cannot be empty",
+                                ));
+                            }
+                        }
+                        field_attr.media_param = Some((header, param));
+                    }
+                    "or_query" => {
+                        input.parse::<Token![=]>()?;
+                        let param: LitStr = input.parse()?;
+                        if param.value().is_empty() {
+                            return Err(syn::Error::new_spanned(
+                                param,
+                                "`or_query` parameter names cannot be empty",
+                            ));
+                        }
+                        field_attr.or_query = Some(param);
+                    }
+                    "nth" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.nth = Some(input.parse::<LitInt>()?.base10_parse()?);
+                    }
+                    // `required_if_present` spells out that only presence is checked
+                    "required_if" | "required_if_present" => {
+                        input.parse::<Token![=]>()?;
+                        if field_attr.required_if.is_some() {
+                            return Err(syn::Error::new_spanned(
+                                &key,
+                                "`required_if` and `required_if_present` are the same option \
+                                 and can only be given once",
+                            ));
+                        }
+                        field_attr.required_if = Some(input.parse()?);
+                    }
+                    "datetime_fmt" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.datetime_fmt = Some(input.parse()?);
+                    }
+                    "otel_attr" => {
+                        input.parse::<Token![=]>()?;
+                        let key: LitStr = input.parse()?;
+                        if key.value().is_empty() {
+                            return Err(syn::Error::new_spanned(
+                                key,
+                                "span attribute key cannot be empty",
+                            ));
+                        }
+                        field_attr.otel_attr = Some(key);
+                    }
+                    "prefix" => {
+                        input.parse::<Token![=]>()?;
+                        field_attr.prefix = Some(input.parse()?);
+                    }
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &key,
+                            format!("unknown `header` attribute option `{key}`"),
+                        ));
+                    }
+                }
+
+                if input.is_empty() {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+            }
+            Ok(())
+        })?;
+
+        if let (Some(name), Some(rename)) = (&field_attr.name, &field_attr.rename) {
+            let mut err =
+                syn::Error::new_spanned(rename, "`rename` conflicts with the explicit header name");
+            err.combine(syn::Error::new_spanned(
+                name,
+                "explicit header name given here",
+            ));
+            return Err(err);
+        }
+
+        if let Some(name_const) = &field_attr.name_const
+            && (field_attr.name.is_some() || field_attr.rename.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                name_const,
+                "`name = ...` gives the header name and conflicts with the header name literal \
+                 and `rename`",
+            ));
+        }
+
+        if field_attr.from_type
+            && (field_attr.name.is_some()
+                || field_attr.name_const.is_some()
+                || field_attr.rename.is_some()
+                || field_attr.prefix.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`from_type` takes the header name from the field type and conflicts with other \
+                 header names, `rename` and `prefix`",
+            ));
+        }
+
+        if let Some(prefix) = &field_attr.prefix {
+            if let Some(name) = field_attr.name.as_ref().or(field_attr.rename.as_ref()) {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "`prefix` collects every header starting with it and can't be combined with a \
+                     header name",
+                ));
+            }
+            if let Some(name_const) = &field_attr.name_const {
+                return Err(syn::Error::new_spanned(
+                    name_const,
+                    "`prefix` collects every header starting with it and can't be combined with a \
+                     header name",
+                ));
+            }
+            if prefix.value().is_empty() {
+                return Err(syn::Error::new_spanned(
+                    prefix,
+                    "header prefix cannot be empty",
+                ));
+            }
+        }
+
+        if let (Some(min), Some(max)) = (field_attr.min_len, field_attr.max_len)
+            && min > max
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("`min_len = {min}` is greater than `max_len = {max}`"),
+            ));
+        }
+
+        if field_attr.trim && field_attr.no_trim {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`trim` conflicts with `no_trim`",
+            ));
+        }
+
+        if let (true, Some(format)) = (field_attr.duration_suffix, &field_attr.datetime_fmt) {
+            return Err(syn::Error::new_spanned(
+                format,
+                "`datetime_fmt` conflicts with `duration_suffix`",
+            ));
+        }
+
+        if field_attr.gzip && field_attr.base64.is_none() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`gzip` requires `base64`, header values can't carry the compressed bytes as is",
+            ));
+        }
+
+        if field_attr.skip_unknown && !field_attr.list {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`skip_unknown` requires `list`",
+            ));
+        }
+
+        let names = [&field_attr.name, &field_attr.rename].into_iter().flatten();
+        for lit in names
+            .chain(&field_attr.aliases)
+            .chain(&field_attr.required_if)
+        {
+            if lit.value().is_empty() {
+                return Err(syn::Error::new_spanned(attr, "header name cannot be empty"));
+            }
+        }
+
+        Ok(Some((attr, field_attr)))
+    }
+}
+
+/// Options parsed from the struct-level `#[headers(...)]` attribute.
+///
+/// ```ignore
+/// #[headers(rename_all = "kebab-case", prefix = "x-")]
+/// #[headers(infallible)]
+/// #[headers(state = "AppConfig")]
+/// #[headers(all_or_none("x-signature", "x-signature-timestamp"))]
+/// ```
+#[derive(Default)]
+pub(crate) struct ContainerAttr {
+    /// `rename_all = "..."`, derives header names from field identifiers
+    pub rename_all: Option<RenameRule>,
+    /// `prefix = "..."`, prepended to every derived or renamed header name
+    pub prefix: Option<LitStr>,
+    /// `lenient`, generates `from_request_parts_lenient`
+    pub lenient: bool,
+    /// `none_if_all_absent`, `Option<Self>` is only `None` when every required header is absent
+    pub none_if_all_absent: bool,
+    /// `all_optional`, non-`Option` fields fall back to `Default::default()` when absent
+    pub all_optional: bool,
+    /// `negotiate_error`, renders rejections according to the request's `Accept` header
+    pub negotiate_error: bool,
+    /// `advertise_required`, lists the required headers in the rejection of a missing header
+    pub advertise_required: bool,
+    /// `source_extension = "Type"`, reads headers from a request extension instead of
+    /// `parts.headers`
+    pub source_extension: Option<Type>,
+    /// `collect_errors`, reports every failing header at once as `HeaderError::Multiple`
+    pub collect_errors: bool,
+    /// `debug_on_error`, logs the request header names when extraction fails
+    pub debug_on_error: bool,
+    /// `status = 422`, the response status of rejections
+    pub status: Option<u16>,
+    /// `missing_status = 401`, the response status of rejections caused by a missing header.
+    /// `error_status = 200` sets both `status` and `missing_status`
+    pub missing_status: Option<u16>,
+    /// `on_missing_redirect = "/login"`, redirects with `303 See Other` when a header is missing
+    pub on_missing_redirect: Option<LitStr>,
+    /// `error_dto = "Type"`, the type JSON rejection bodies are rendered as
+    pub error_dto: Option<Type>,
+    /// `state = "Type"`, the state (via `FromRef`) passed to `validate_with` functions
+    pub state: Option<Type>,
+    /// `deserialize`, generates a `serde::Deserialize` impl reading a map keyed by header names
+    pub deserialize: bool,
+    /// `infallible`, extraction never fails and records problems in the `#[header(problems)]`
+    /// field
+    pub infallible: bool,
+    /// `all_or_none("...", ...)`, groups of headers that must be present together or not at all,
+    /// one entry per occurrence
+    pub all_or_none: Vec<Vec<LitStr>>,
+}
+
+impl ContainerAttr {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container_attr = ContainerAttr::default();
+        let Some(attr) = find_attr(attrs, CONTAINER_ATTRIBUTE_IDENT) else {
+            return Ok(container_attr);
+        };
+        let mut error_status = None;
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let lit: LitStr = meta.value()?.parse()?;
+                container_attr.rename_all = Some(RenameRule::from_lit(&lit)?);
+                Ok(())
+            } else if meta.path.is_ident("prefix") {
+                container_attr.prefix = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("lenient") {
+                container_attr.lenient = true;
+                Ok(())
+            } else if meta.path.is_ident("none_if_all_absent") {
+                container_attr.none_if_all_absent = true;
+                Ok(())
+            } else if meta.path.is_ident("all_optional") {
+                container_attr.all_optional = true;
+                Ok(())
+            } else if meta.path.is_ident("negotiate_error") {
+                container_attr.negotiate_error = true;
+                Ok(())
+            } else if meta.path.is_ident("advertise_required") {
+                container_attr.advertise_required = true;
+                Ok(())
+            } else if meta.path.is_ident("status") {
+                container_attr.status = Some(parse_status(&meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("missing_status") {
+                container_attr.missing_status = Some(parse_status(&meta.value()?.parse()?)?);
+                Ok(())
+            } else if meta.path.is_ident("on_missing_redirect") {
+                let lit: LitStr = meta.value()?.parse()?;
+                // `HeaderValue::from_static` accepts visible ASCII, spaces and tabs
+                let value = lit.value();
+                if value.is_empty()
+                    || !value
+                        .bytes()
+                        .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+                {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        "redirect location must be a non-empty ASCII header value",
+                    ));
+                }
+                container_attr.on_missing_redirect = Some(lit);
+                Ok(())
+            } else if meta.path.is_ident("error_status") {
+                let lit: LitInt = meta.value()?.parse()?;
+                error_status = Some((parse_status(&lit)?, lit));
+                Ok(())
+            } else if meta.path.is_ident("debug_on_error") {
+                container_attr.debug_on_error = true;
+                Ok(())
+            } else if meta.path.is_ident("deserialize") {
+                container_attr.deserialize = true;
+                Ok(())
+            } else if meta.path.is_ident("infallible") {
+                container_attr.infallible = true;
+                Ok(())
+            } else if meta.path.is_ident("collect_errors") {
+                container_attr.collect_errors = true;
+                Ok(())
+            } else if meta.path.is_ident("error_dto") {
+                let lit: LitStr = meta.value()?.parse()?;
+                container_attr.error_dto = Some(lit.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("state") {
+                let lit: LitStr = meta.value()?.parse()?;
+                container_attr.state = Some(lit.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("all_or_none") {
+                let content;
+                parenthesized!(content in meta.input);
+                let names: Vec<LitStr> = content
+                    .parse_terminated(<LitStr as syn::parse::Parse>::parse, Token![,])?
+                    .into_iter()
+                    .collect();
+                if names.len() < 2 {
+                    return Err(meta.error("`all_or_none` needs at least two header names"));
+                }
+                if let Some(empty) = names.iter().find(|name| name.value().is_empty()) {
+                    return Err(syn::Error::new_spanned(
+                        empty,
+                        "header name cannot be empty",
+                    ));
+                }
+                container_attr.all_or_none.push(names);
+                Ok(())
+            } else if meta.path.is_ident("source_extension") {
+                let lit: LitStr = meta.value()?.parse()?;
+                container_attr.source_extension = Some(lit.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown `headers` attribute option"))
+            }
+        })?;
+
+        // `error_status` is shorthand for the same `status` and `missing_status`
+        if let Some((status, lit)) = error_status {
+            if container_attr.status.is_some() || container_attr.missing_status.is_some() {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "`error_status` sets the status of every rejection and conflicts with \
+                     `status` and `missing_status`",
+                ));
+            }
+            container_attr.status = Some(status);
+            container_attr.missing_status = Some(status);
+        }
+        if let (Some(redirect), Some(_)) = (
+            &container_attr.on_missing_redirect,
+            container_attr.missing_status,
+        ) {
+            return Err(syn::Error::new_spanned(
+                redirect,
+                "`on_missing_redirect` answers missing headers with `303 See Other` and conflicts \
+                 with `missing_status` and `error_status`",
+            ));
+        }
+
+        if let Some(state) = &container_attr.state
+            && (container_attr.lenient || container_attr.deserialize)
+        {
+            return Err(syn::Error::new_spanned(
+                state,
+                "`state` conflicts with `lenient` and `deserialize`, which extract without the \
+                 application state",
+            ));
+        }
+        if container_attr.infallible
+            && (container_attr.lenient
+                || container_attr.collect_errors
+                || container_attr.none_if_all_absent
+                || container_attr.debug_on_error
+                || container_attr.shapes_rejection())
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`infallible` never rejects the request and conflicts with `lenient`, \
+                 `collect_errors`, `none_if_all_absent`, `debug_on_error` and the options shaping \
+                 the rejection",
+            ));
+        }
+
+        Ok(container_attr)
+    }
+
+    /// Whether the rejection has to be a `HeaderRejection` to honor the options
+    pub(crate) fn shapes_rejection(&self) -> bool {
+        self.negotiate_error
+            || self.advertise_required
+            || self.status.is_some()
+            || self.missing_status.is_some()
+            || self.on_missing_redirect.is_some()
+            || self.error_dto.is_some()
+    }
+}
+
+/// Parses an HTTP status code literal, e.g. `401`
+fn parse_status(lit: &LitInt) -> syn::Result<u16> {
+    let status: u16 = lit.base10_parse()?;
+    if !(100..=999).contains(&status) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            "status code must be within `100..=999`",
+        ));
+    }
+    Ok(status)
+}
+
+/// A header name as written in the generated code
+#[derive(Clone)]
+pub(crate) enum HeaderNameExpr {
+    /// A name known at expansion time, e.g. from `#[header("x-user-id")]` or `rename_all`
+    Literal(String),
+    /// `#[header(name = X_USER_ID)]`, a path to a `&'static str` const
+    Const(Path),
+    /// `#[header(from_type)]`, the `HEADER_NAME` of the field type
+    FromType(Type),
+}
+
+impl ToTokens for HeaderNameExpr {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            Self::Literal(name) => name.to_tokens(tokens),
+            Self::Const(path) => path.to_tokens(tokens),
+            Self::FromType(ty) => {
+                quote!(<#ty as ::axum_required_headers::RequiredHeader>::HEADER_NAME)
+                    .to_tokens(tokens);
+            }
+        }
+    }
+}
+
+/// The naming conventions supported by `#[headers(rename_all = "...")]`
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    /// `user_id` -> `user-id`
+    KebabCase,
+}
+
+impl RenameRule {
+    fn from_lit(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "kebab-case" => Ok(RenameRule::KebabCase),
+            other => Err(syn::Error::new_spanned(
+                lit,
+                format!("unsupported `rename_all` rule `{other}`, expected \"kebab-case\""),
+            )),
+        }
+    }
+
+    pub(crate) fn apply(self, field: &Ident) -> String {
+        let field = field.to_string();
+        let field = field.strip_prefix("r#").unwrap_or(&field);
+        match self {
+            RenameRule::KebabCase => field.replace('_', "-"),
+        }
+    }
+}
+
+fn find_attr<'a>(attrs: &'a [Attribute], ident: &str) -> Option<&'a Attribute> {
+    attrs.iter().find(|attr| attr.path().is_ident(ident))
+}
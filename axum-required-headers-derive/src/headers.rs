@@ -0,0 +1,1786 @@
+//! Implementation of the `Headers` derive macro.
+
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Type, spanned::Spanned};
+
+use crate::{
+    attr::{Base64Alphabet, ContainerAttr, FieldAttr, FieldDefault, HeaderNameExpr},
+    get_crate,
+};
+
+/// Element types that take the header value as is, skipping `to_str()` and `FromStr`.
+#[derive(Clone, Copy)]
+enum RawValue {
+    /// `HeaderValue`, cloned
+    HeaderValue,
+    /// `Vec<u8>`, the value bytes
+    Bytes,
+}
+
+impl RawValue {
+    fn of(ty: &Type) -> Option<Self> {
+        if generic_inner_type(ty, "Vec").is_some_and(is_u8) {
+            Some(RawValue::Bytes)
+        } else if last_segment_is(ty, "HeaderValue") {
+            Some(RawValue::HeaderValue)
+        } else {
+            None
+        }
+    }
+}
+
+/// A struct field annotated (or implicitly named) as a header.
+struct HeaderField<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    /// The `T` of an `Option<T>` field, `None` for required fields
+    optional_inner: Option<&'a Type>,
+    /// The `T` of a `Vec<T>` (or `Option<Vec<T>>`) field collecting every occurrence
+    vec_inner: Option<&'a Type>,
+    /// Whether values are stored without ASCII validation or parsing
+    raw: Option<RawValue>,
+    /// The lowercase `prefix` of a field collecting every matching header
+    prefix: Option<String>,
+    header_name: HeaderNameExpr,
+    attr: FieldAttr,
+}
+
+impl HeaderField<'_> {
+    /// The type the header value is parsed into
+    fn value_type(&self) -> &Type {
+        self.optional_inner.unwrap_or(self.ty)
+    }
+
+    /// The type each header value is parsed into, the `T` of `Vec<T>` fields
+    fn element_type(&self) -> &Type {
+        self.vec_inner.unwrap_or_else(|| self.value_type())
+    }
+
+    /// Whether the absence of the header rejects the request
+    fn is_required(&self) -> bool {
+        self.optional_inner.is_none() && self.attr.default.is_none() && self.prefix.is_none()
+    }
+
+    /// Whether extracting the field can't fail, so it is bound directly by every parser
+    fn never_fails(&self) -> bool {
+        (self.optional_inner.is_some() && self.attr.required_if.is_none()) || self.prefix.is_some()
+    }
+
+    /// Statement setting the `otel_attr` span attribute from the bound field, if any
+    fn span_attribute(&self) -> Option<proc_macro2::TokenStream> {
+        let key = self.attr.otel_attr.as_ref()?;
+        let ident = self.ident;
+        Some(if self.optional_inner.is_some() {
+            quote! {
+                if let ::std::option::Option::Some(value) = &#ident {
+                    ::axum_required_headers::__private::set_span_attribute(#key, value);
+                }
+            }
+        } else {
+            quote!(::axum_required_headers::__private::set_span_attribute(#key, &#ident);)
+        })
+    }
+
+    /// Statement recording the bound field on the current `tracing` span for `trace`, if any
+    fn span_record(&self) -> Option<proc_macro2::TokenStream> {
+        if !self.attr.trace {
+            return None;
+        }
+        let ident = self.ident;
+        let header_name = &self.header_name;
+        Some(if self.optional_inner.is_some() {
+            quote! {
+                if let ::std::option::Option::Some(value) = &#ident {
+                    ::axum_required_headers::__private::record_span_field(#header_name, value);
+                }
+            }
+        } else {
+            quote!(::axum_required_headers::__private::record_span_field(#header_name, &#ident);)
+        })
+    }
+
+    /// The type inserted into the request extensions for `into_extension`, if any
+    fn extension_type(&self) -> Option<&Type> {
+        self.attr.into_extension.then(|| self.value_type())
+    }
+
+    /// Statement inserting a clone of the field of the extracted `__value` into the request
+    /// extensions for `into_extension`, if any
+    fn extension_insert(&self) -> Option<proc_macro2::TokenStream> {
+        if !self.attr.into_extension {
+            return None;
+        }
+        let ident = self.ident;
+        Some(if self.optional_inner.is_some() {
+            quote! {
+                if let ::std::option::Option::Some(value) = &__value.#ident {
+                    parts.extensions.insert(::std::clone::Clone::clone(value));
+                }
+            }
+        } else {
+            quote!(parts.extensions.insert(::std::clone::Clone::clone(&__value.#ident));)
+        })
+    }
+
+    /// The type read from the request extensions for `or_extension`, if any
+    fn fallback_extension_type(&self) -> Option<&Type> {
+        self.attr.or_extension.then(|| self.value_type())
+    }
+
+    /// [`lookup`](Self::lookup), logging malformed values for `trace`
+    fn traced_lookup(&self) -> proc_macro2::TokenStream {
+        let lookup = self.lookup();
+        if !self.attr.trace {
+            return lookup;
+        }
+        quote! {
+            (#lookup).map(|result| {
+                result.inspect_err(::axum_required_headers::__private::warn_header_error)
+            })
+        }
+    }
+
+    /// An expression checking whether the `required_if` companion header is present
+    fn companion_present(&self) -> Option<proc_macro2::TokenStream> {
+        self.attr.required_if.as_ref().map(|companion| {
+            // `HeaderMap` names are lowercase
+            let companion = companion.value().to_ascii_lowercase();
+            quote!(__headers.contains_key(#companion))
+        })
+    }
+
+    /// An expression collecting the headers starting with `prefix` into the field type, keyed by
+    /// the rest of their name.
+    ///
+    /// Each name contributes its first value; values that aren't valid ASCII, and the bare
+    /// prefix, are skipped.
+    fn collect_prefixed(&self, prefix: &str) -> proc_macro2::TokenStream {
+        let ty = self.ty;
+        quote! {
+            __headers
+                .keys()
+                .filter_map(|name| {
+                    let key = name.as_str().strip_prefix(#prefix).filter(|key| !key.is_empty())?;
+                    let value = __headers.get(name)?.to_str().ok()?;
+                    ::std::option::Option::Some((
+                        ::std::borrow::ToOwned::to_owned(key),
+                        ::std::borrow::ToOwned::to_owned(value),
+                    ))
+                })
+                .collect::<#ty>()
+        }
+    }
+
+    /// An expression of type `Result<T, HeaderError>` producing the `default` of the field, if any
+    fn default_value(&self) -> Option<proc_macro2::TokenStream> {
+        let header_name = &self.header_name;
+        let value_type = self.value_type();
+
+        let default = match self.attr.default.as_ref()? {
+            FieldDefault::Literal(lit) => {
+                let parse = quote_spanned! {value_type.span()=>
+                    ::axum_required_headers::__private::parse_field::<#value_type>(#lit)
+                };
+                quote! {
+                    #parse.ok_or(::axum_required_headers::HeaderError::Parse(#header_name))
+                }
+            }
+            FieldDefault::Expr(expr) => quote! {
+                ::std::result::Result::<#value_type, ::axum_required_headers::HeaderError>::Ok(#expr)
+            },
+        };
+        Some(default)
+    }
+
+    /// An expression of type `Option<Result<T, HeaderError>>` extracting the field value from
+    /// `__headers`, or from the `__query` string for `or_query` and the `__extensions` for
+    /// `or_extension`, where `None` means the value is absent from all of them
+    fn lookup(&self) -> proc_macro2::TokenStream {
+        let mut lookup = self.header_lookup();
+        if let Some(param) = &self.attr.or_query {
+            let header_name = &self.header_name;
+            let parse_str = self.parse_str();
+            lookup = quote! {
+                (#lookup).or_else(|| {
+                    ::axum_required_headers::__private::query_param(__query, #param).map(|value| {
+                        let value = value
+                            .ok_or(::axum_required_headers::HeaderError::Parse(#header_name))?;
+                        (#parse_str)(&value)
+                    })
+                })
+            };
+        }
+        let Some(extension_type) = self.fallback_extension_type() else {
+            return lookup;
+        };
+        quote! {
+            (#lookup).or_else(|| {
+                __extensions
+                    .get::<#extension_type>()
+                    .map(|value| ::std::result::Result::Ok(::std::clone::Clone::clone(value)))
+            })
+        }
+    }
+
+    /// An expression of type `Option<Result<T, HeaderError>>` extracting the field value from
+    /// `headers`, where `None` means the header is absent.
+    ///
+    /// `Vec<T>` and `typed` fields parse every occurrence of the first present name.
+    fn header_lookup(&self) -> proc_macro2::TokenStream {
+        let header_name = &self.header_name;
+        let aliases = &self.attr.aliases;
+        let parse_value = self.parse_value();
+        let lookup_name = lookup_name(header_name);
+        // The occurrences of the first present name
+        let all_values = quote! {{
+            let __name = #lookup_name;
+            let __aliases: &[&str] = &[#(#aliases),*];
+            if __headers.contains_key(__name) {
+                ::std::option::Option::Some(__headers.get_all(__name))
+            } else {
+                __aliases
+                    .iter()
+                    .find(|alias| __headers.contains_key(**alias))
+                    .map(|alias| __headers.get_all(*alias))
+            }
+        }};
+
+        if self.attr.typed {
+            let value_type = self.value_type();
+            return quote! {
+                #all_values.map(|values| {
+                    ::axum_required_headers::__private::decode_typed::<#value_type>(values, #header_name)
+                })
+            };
+        }
+
+        if self.attr.list {
+            let value_type = self.value_type();
+            let element_type = self.element_type();
+            let parse_str = self.parse_str();
+            let on_error = if self.attr.skip_unknown {
+                quote! {
+                    ::std::result::Result::Err(::axum_required_headers::HeaderError::Parse(_)) => {}
+                    ::std::result::Result::Err(error) => return ::std::result::Result::Err(error),
+                }
+            } else {
+                quote!(::std::result::Result::Err(error) => return ::std::result::Result::Err(error),)
+            };
+            return quote! {
+                #all_values
+                    .map(|values| -> ::std::result::Result<#value_type, ::axum_required_headers::HeaderError> {
+                        let parse = #parse_str;
+                        let mut items: ::std::vec::Vec<#element_type> = ::std::vec::Vec::new();
+                        for value in values {
+                            let value = value
+                                .to_str()
+                                .map_err(|_| ::axum_required_headers::HeaderError::InvalidValue(#header_name))?;
+                            for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+                                match parse(item) {
+                                    ::std::result::Result::Ok(item) => items.push(item),
+                                    #on_error
+                                }
+                            }
+                        }
+                        ::std::result::Result::Ok(items)
+                    })
+            };
+        }
+
+        if self.vec_inner.is_some() {
+            let value_type = self.value_type();
+            return quote! {
+                #all_values
+                    .map(|values| values.iter().map(#parse_value)
+                        .collect::<::std::result::Result<#value_type, ::axum_required_headers::HeaderError>>())
+            };
+        }
+
+        let non_empty = self
+            .attr
+            .non_empty
+            .then(|| quote!(.filter(|value| !value.is_empty())));
+
+        if let Some(nth) = self.attr.nth {
+            return quote! {
+                __headers.get_all(#lookup_name).iter().nth(#nth)#non_empty.map(#parse_value)
+            };
+        }
+
+        let alias_lookups = aliases
+            .iter()
+            .map(|alias| quote!(__headers.get(#alias) #non_empty));
+        let media_param = self.attr.media_param.as_ref().map(|(header, param)| {
+            // `HeaderMap` names are lowercase
+            let header = header.value().to_ascii_lowercase();
+            let parse_str = self.parse_str();
+            quote! {
+                .or_else(|| {
+                    ::axum_required_headers::__private::media_type_param(__headers, #header, #param)
+                        .map(|value| (#parse_str)(&value))
+                })
+            }
+        });
+
+        quote! {
+            __headers.get(#lookup_name) #non_empty #(.or_else(|| #alias_lookups))*
+                .map(#parse_value)#media_param
+        }
+    }
+
+    /// Statement trimming `value` for `trim` fields
+    fn trim(&self) -> Option<proc_macro2::TokenStream> {
+        self.attr.trim.then(|| quote!(let value = value.trim();))
+    }
+
+    /// A closure parsing a single `&HeaderValue` into the element type
+    fn parse_value(&self) -> proc_macro2::TokenStream {
+        let header_name = &self.header_name;
+        let element_type = self.element_type();
+
+        if let Some(alphabet) = self.attr.base64 {
+            let url_safe = matches!(alphabet, Base64Alphabet::UrlSafe);
+            let trim = self.trim();
+            // stages of a `gzip` or `json` pipeline report which one failed
+            let pipeline = self.attr.gzip || self.attr.json;
+            let stage_error = |stage: &str| {
+                if pipeline {
+                    quote!(::axum_required_headers::HeaderError::Decode { name: #header_name, stage: #stage })
+                } else {
+                    quote!(::axum_required_headers::HeaderError::Parse(#header_name))
+                }
+            };
+            let base64_error = stage_error("base64");
+            let gunzip = self.attr.gzip.then(|| {
+                let gzip_error = stage_error("gzip");
+                quote! {
+                    let bytes = ::axum_required_headers::__private::gunzip(&bytes)
+                        .ok_or(#gzip_error)?;
+                }
+            });
+            let convert = match self.raw {
+                Some(_) => quote!(::std::result::Result::Ok(bytes)),
+                None => {
+                    let utf8_error = if self.attr.json {
+                        stage_error("json")
+                    } else {
+                        quote!(::axum_required_headers::HeaderError::Parse(#header_name))
+                    };
+                    // the raw value was trimmed before decoding, whitespace in the payload is data
+                    let parse_str = self.parse_str_with(None);
+                    quote! {
+                        let value = ::std::string::String::from_utf8(bytes)
+                            .map_err(|_| #utf8_error)?;
+                        (#parse_str)(&value)
+                    }
+                }
+            };
+            return quote! {
+                |value: &::axum_required_headers::http::HeaderValue| -> ::std::result::Result<#element_type, ::axum_required_headers::HeaderError> {
+                    let value = value
+                        .to_str()
+                        .map_err(|_| ::axum_required_headers::HeaderError::InvalidValue(#header_name))?;
+                    #trim
+                    let bytes = ::axum_required_headers::__private::decode_base64(value, #url_safe)
+                        .ok_or(#base64_error)?;
+                    #gunzip
+                    #convert
+                }
+            };
+        }
+
+        if let Some(raw) = self.raw {
+            let value = match raw {
+                RawValue::HeaderValue if self.attr.sensitive => quote!({
+                    let mut value = ::std::clone::Clone::clone(value);
+                    value.set_sensitive(true);
+                    value
+                }),
+                RawValue::HeaderValue => quote!(::std::clone::Clone::clone(value)),
+                RawValue::Bytes => quote!(value.as_bytes().to_vec()),
+            };
+            return quote! {
+                |value: &::axum_required_headers::http::HeaderValue| -> ::std::result::Result<#element_type, ::axum_required_headers::HeaderError> {
+                    ::std::result::Result::Ok(#value)
+                }
+            };
+        }
+
+        let parse_str = self.parse_str();
+
+        quote! {
+            |value: &::axum_required_headers::http::HeaderValue| -> ::std::result::Result<#element_type, ::axum_required_headers::HeaderError> {
+                let value = value
+                    .to_str()
+                    .map_err(|_| ::axum_required_headers::HeaderError::InvalidValue(#header_name))?;
+                (#parse_str)(value)
+            }
+        }
+    }
+
+    /// A closure parsing a single `&str` into the element type
+    fn parse_str(&self) -> proc_macro2::TokenStream {
+        self.parse_str_with(self.trim())
+    }
+
+    /// [`Self::parse_str`] with the given trimming statement
+    fn parse_str_with(&self, trim: Option<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+        let header_name = &self.header_name;
+        let element_type = self.element_type();
+        let validations = self.validations();
+        let length = self.length_check();
+        let pattern = self.attr.pattern.as_ref().map(|pattern| {
+            let reason = format!("value must match the pattern `{}`", pattern.value());
+            quote! {
+                static PATTERN: ::std::sync::OnceLock<::axum_required_headers::__private::regex::Regex> =
+                    ::std::sync::OnceLock::new();
+                let pattern = PATTERN.get_or_init(|| {
+                    ::axum_required_headers::__private::regex::Regex::new(#pattern)
+                        .expect("pattern validated by the derive")
+                });
+                if !pattern.is_match(value) {
+                    return ::std::result::Result::Err(::axum_required_headers::HeaderError::Validation {
+                        name: #header_name,
+                        reason: ::std::string::String::from(#reason),
+                    });
+                }
+            }
+        });
+        let uuid = self.attr.uuid.then(|| {
+            quote! {
+                if !::axum_required_headers::__private::is_uuid(value) {
+                    return ::std::result::Result::Err(::axum_required_headers::HeaderError::Validation {
+                        name: #header_name,
+                        reason: ::std::string::String::from("value must be a UUID"),
+                    });
+                }
+            }
+        });
+        let validate_with = self.attr.validate_with.as_ref().map(|validate| {
+            quote! {
+                if let ::std::result::Result::Err(error) = #validate(value, __state) {
+                    return ::std::result::Result::Err(::axum_required_headers::HeaderError::Validation {
+                        name: #header_name,
+                        reason: ::std::string::ToString::to_string(&error),
+                    });
+                }
+            }
+        });
+        let parse = match &self.attr.datetime_fmt {
+            Some(format) => quote! {
+                ::axum_required_headers::chrono::DateTime::parse_from_str(value, #format)
+                    .map(::std::convert::Into::<#element_type>::into)
+            },
+            None if self.attr.duration_suffix => quote! {
+                ::axum_required_headers::__private::parse_duration(value).ok_or(())
+            },
+            None if self.attr.json => quote! {
+                ::axum_required_headers::__private::decode_json::<#element_type>(value).ok_or(())
+            },
+            // `Box<str>` and `Cow<str>` don't implement `FromStr`
+            None if is_owned_str(element_type) => quote! {
+                ::std::result::Result::<_, ::std::convert::Infallible>::Ok(::std::convert::From::from(
+                    ::std::borrow::ToOwned::to_owned(value),
+                ))
+            },
+            // spanned at the field type, where a missing `FromStr` impl is reported
+            None => quote_spanned! {element_type.span()=>
+                ::axum_required_headers::__private::parse_field::<#element_type>(value).ok_or(())
+            },
+        };
+        let parse_error = if self.attr.json {
+            quote!(::axum_required_headers::HeaderError::Decode { name: #header_name, stage: "json" })
+        } else {
+            quote!(::axum_required_headers::HeaderError::Parse(#header_name))
+        };
+
+        quote! {
+            |value: &str| -> ::std::result::Result<#element_type, ::axum_required_headers::HeaderError> {
+                #trim
+                #length
+                #pattern
+                #uuid
+                #validate_with
+                let value: #element_type = #parse
+                    .map_err(|_| #parse_error)?;
+                #validations
+                ::std::result::Result::Ok(value)
+            }
+        }
+    }
+
+    /// Checks the length of the (trimmed) `value` for `min_len` and `max_len`, returning a
+    /// `HeaderError::Validation` on failure
+    fn length_check(&self) -> Option<proc_macro2::TokenStream> {
+        let header_name = &self.header_name;
+        let (condition, reason) = match (self.attr.min_len, self.attr.max_len) {
+            (None, None) => return None,
+            (Some(min), None) => (
+                quote!(value.len() < #min),
+                format!("value must be at least {min} bytes long"),
+            ),
+            (None, Some(max)) => (
+                quote!(value.len() > #max),
+                format!("value must be at most {max} bytes long"),
+            ),
+            (Some(min), Some(max)) => (
+                quote!(!(#min..=#max).contains(&value.len())),
+                format!("value must be between {min} and {max} bytes long"),
+            ),
+        };
+
+        Some(quote! {
+            if #condition {
+                return ::std::result::Result::Err(::axum_required_headers::HeaderError::Validation {
+                    name: #header_name,
+                    reason: ::std::string::String::from(#reason),
+                });
+            }
+        })
+    }
+
+    /// Checks run on the parsed `value`, returning a `HeaderError::Validation` on failure
+    fn validations(&self) -> proc_macro2::TokenStream {
+        let header_name = &self.header_name;
+        let mut validations = proc_macro2::TokenStream::new();
+
+        if let Some(range) = &self.attr.range {
+            let reason = format!("value must be within `{}`", compact_tokens(range));
+            validations.extend(quote! {
+                if !(#range).contains(&value) {
+                    return ::std::result::Result::Err(::axum_required_headers::HeaderError::Validation {
+                        name: #header_name,
+                        reason: ::std::string::String::from(#reason),
+                    });
+                }
+            });
+        }
+
+        validations
+    }
+
+    /// Statement binding the field, returning early on errors of required fields
+    fn strict_parser(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        let ty = self.ty;
+        let header_name = &self.header_name;
+
+        if let Some(prefix) = &self.prefix {
+            let collect = self.collect_prefixed(prefix);
+            return quote!(let #ident: #ty = #collect;);
+        }
+
+        let lookup = self.traced_lookup();
+        if let Some(companion_present) = self.companion_present() {
+            // Optional header, required when the companion header is present
+            quote! {
+                let #ident: #ty = match #lookup {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => ::std::option::Option::Some(value),
+                    ::std::option::Option::Some(::std::result::Result::Err(error)) if #companion_present => {
+                        return ::std::result::Result::Err(error);
+                    }
+                    ::std::option::Option::None if #companion_present => {
+                        return ::std::result::Result::Err(::axum_required_headers::HeaderError::Missing(#header_name));
+                    }
+                    _ => ::std::option::Option::None,
+                };
+            }
+        } else if self.optional_inner.is_some() {
+            // Optional header
+            quote! {
+                let #ident: #ty = match #lookup {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => ::std::option::Option::Some(value),
+                    _ => ::std::option::Option::None,
+                };
+            }
+        } else if let Some(default) = self.default_value() {
+            // Required header with a default for when it is absent
+            quote! {
+                let #ident: #ty = match #lookup {
+                    ::std::option::Option::Some(result) => result?,
+                    ::std::option::Option::None => (#default)?,
+                };
+            }
+        } else {
+            // Required header
+            quote! {
+                let #ident: #ty = match #lookup {
+                    ::std::option::Option::Some(result) => result?,
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Err(::axum_required_headers::HeaderError::Missing(#header_name));
+                    }
+                };
+            }
+        }
+    }
+
+    /// Statement binding the field, pushing errors of required fields into `__errors`.
+    ///
+    /// Required fields are bound as `Option<T>`, `None` when an error was recorded.
+    fn collecting_parser(&self) -> proc_macro2::TokenStream {
+        if self.never_fails() {
+            // Optional and prefixed headers never fail
+            return self.strict_parser();
+        }
+
+        let ident = self.ident;
+        let ty = self.ty;
+        let header_name = &self.header_name;
+        let lookup = self.traced_lookup();
+
+        if let Some(companion_present) = self.companion_present() {
+            // `Some(None)` when the header is absent or malformed without its companion
+            return quote! {
+                let #ident: ::std::option::Option<#ty> = match #lookup {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => {
+                        ::std::option::Option::Some(::std::option::Option::Some(value))
+                    }
+                    ::std::option::Option::Some(::std::result::Result::Err(error)) if #companion_present => {
+                        __errors.push(error);
+                        ::std::option::Option::None
+                    }
+                    ::std::option::Option::None if #companion_present => {
+                        __errors.push(::axum_required_headers::HeaderError::Missing(#header_name));
+                        ::std::option::Option::None
+                    }
+                    _ => ::std::option::Option::Some(::std::option::Option::None),
+                };
+            };
+        }
+
+        let absent = match self.default_value() {
+            Some(default) => quote! {
+                match #default {
+                    ::std::result::Result::Ok(value) => ::std::option::Option::Some(value),
+                    ::std::result::Result::Err(error) => {
+                        __errors.push(error);
+                        ::std::option::Option::None
+                    }
+                }
+            },
+            None => quote! {{
+                __errors.push(::axum_required_headers::HeaderError::Missing(#header_name));
+                ::std::option::Option::None
+            }},
+        };
+
+        quote! {
+            let #ident: ::std::option::Option<#ty> = match #lookup {
+                ::std::option::Option::Some(::std::result::Result::Ok(value)) => ::std::option::Option::Some(value),
+                ::std::option::Option::Some(::std::result::Result::Err(error)) => {
+                    __errors.push(error);
+                    ::std::option::Option::None
+                }
+                ::std::option::Option::None => #absent,
+            };
+        }
+    }
+
+    /// Statement binding the field, recording problems into `__warnings` instead of failing
+    fn lenient_parser(&self) -> proc_macro2::TokenStream {
+        self.tolerant_parser(
+            |error| quote!(__warnings.push(::axum_required_headers::HeaderWarning::new(#error));),
+        )
+    }
+
+    /// Statement binding the field, recording problems into `__problems` instead of failing
+    fn infallible_parser(&self) -> proc_macro2::TokenStream {
+        self.tolerant_parser(|error| quote!(__problems.push(#error);))
+    }
+
+    /// Statement binding the field, falling back to `Default` or `None` and passing each
+    /// `HeaderError` to `record` instead of failing
+    fn tolerant_parser(
+        &self,
+        record: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        if self.prefix.is_some() {
+            return self.strict_parser();
+        }
+
+        let ident = self.ident;
+        let ty = self.ty;
+        let header_name = &self.header_name;
+        let lookup = self.traced_lookup();
+        let record_error = record(quote!(error));
+
+        if self.optional_inner.is_some() {
+            // Optional header: absence is only a problem with a `required_if` companion
+            let absent = match self.companion_present() {
+                Some(companion_present) => {
+                    let record_missing =
+                        record(quote!(::axum_required_headers::HeaderError::Missing(#header_name)));
+                    quote! {
+                        if #companion_present {
+                            #record_missing
+                        }
+                        ::std::option::Option::None
+                    }
+                }
+                None => quote!(::std::option::Option::None),
+            };
+            quote! {
+                let #ident: #ty = match #lookup {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => ::std::option::Option::Some(value),
+                    ::std::option::Option::Some(::std::result::Result::Err(error)) => {
+                        #record_error
+                        ::std::option::Option::None
+                    }
+                    ::std::option::Option::None => { #absent }
+                };
+            }
+        } else if let Some(default) = self.default_value() {
+            // Required header with a default: absence is not a problem
+            quote! {
+                let #ident: #ty = match #lookup.unwrap_or_else(|| #default) {
+                    ::std::result::Result::Ok(value) => value,
+                    ::std::result::Result::Err(error) => {
+                        #record_error
+                        ::std::default::Default::default()
+                    }
+                };
+            }
+        } else {
+            // Required header: fall back to `Default` and record why
+            let record_missing =
+                record(quote!(::axum_required_headers::HeaderError::Missing(#header_name)));
+            quote! {
+                let #ident: #ty = match #lookup {
+                    ::std::option::Option::Some(::std::result::Result::Ok(value)) => value,
+                    ::std::option::Option::Some(::std::result::Result::Err(error)) => {
+                        #record_error
+                        ::std::default::Default::default()
+                    }
+                    ::std::option::Option::None => {
+                        #record_missing
+                        ::std::default::Default::default()
+                    }
+                };
+            }
+        }
+    }
+}
+
+pub(crate) fn derive_headers_impl(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // build `impl<S, ...>` generics
+    let s_ident = syn::Ident::new("S", name.span());
+    let mut impl_generics_with_s = input.generics.clone();
+    impl_generics_with_s.params.insert(
+        0,
+        syn::GenericParam::Type(syn::TypeParam::from(s_ident.clone())),
+    );
+    let (impl_generics_with_s, _, _) = impl_generics_with_s.split_for_impl();
+
+    // extend where-clause with `S: Send + Sync`
+    let mut where_clause_with_s = where_clause.cloned();
+    {
+        let wc = where_clause_with_s.get_or_insert_with(|| syn::WhereClause {
+            where_token: Default::default(),
+            predicates: Default::default(),
+        });
+        wc.predicates
+            .push(syn::parse_quote!(#s_ident: ::std::marker::Send + ::std::marker::Sync));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Headers can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "Headers only supports named fields",
+        ));
+    };
+
+    let container_attr = ContainerAttr::from_attrs(&input.attrs)?;
+
+    let mut header_fields = Vec::new();
+    let mut problems_field = None;
+    let mut skipped_fields = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+
+        let field_attr = FieldAttr::from_attrs(&field.attrs)?;
+        if let Some((attr, FieldAttr { problems: true, .. })) = &field_attr {
+            if !container_attr.infallible {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`problems` is only supported in `#[headers(infallible)]` structs",
+                ));
+            }
+            if problems_field.replace(ident).is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "only one field can be marked `problems`",
+                ));
+            }
+            continue;
+        }
+        if let Some((_, FieldAttr { skip: true, .. })) = &field_attr {
+            skipped_fields.push(ident);
+            continue;
+        }
+        let optional_inner = option_inner_type(&field.ty);
+        let value_type = optional_inner.unwrap_or(&field.ty);
+        // `Vec<u8>` holds the bytes of a single value rather than every occurrence
+        let vec_inner = generic_inner_type(value_type, "Vec").filter(|inner| !is_u8(inner));
+        let raw = RawValue::of(vec_inner.unwrap_or(value_type));
+        // `HeaderMap` names are lowercase
+        let prefix = field_attr
+            .as_ref()
+            .and_then(|(_, attr)| attr.prefix.as_ref())
+            .map(|prefix| prefix.value().to_ascii_lowercase());
+        let from_type = field_attr.as_ref().is_some_and(|(_, attr)| attr.from_type);
+        let header_name = match &prefix {
+            Some(prefix) => HeaderNameExpr::Literal(prefix.clone()),
+            // `Vec` fields read every occurrence of the header of their elements
+            None if from_type => HeaderNameExpr::FromType(vec_inner.unwrap_or(value_type).clone()),
+            None => resolve_header_name(&container_attr, field_attr.as_ref(), ident).ok_or_else(
+                || {
+                    syn::Error::new_spanned(
+                        field,
+                        "Missing #[header(\"header-name\")] attribute on field",
+                    )
+                },
+            )?,
+        };
+
+        let mut attr = field_attr.map(|(_, attr)| attr).unwrap_or_default();
+        if attr.infallible || attr.to_header_value || attr.warn_on_normalize {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`infallible`, `to_header_value` and `warn_on_normalize` are only supported on \
+                 `Header` types",
+            ));
+        }
+        if prefix.is_some()
+            && (optional_inner.is_some()
+                || !attr.aliases.is_empty()
+                || attr.media_param.is_some()
+                || attr.nth.is_some()
+                || attr.default.is_some()
+                || attr.range.is_some()
+                || attr.min_len.is_some()
+                || attr.max_len.is_some()
+                || attr.pattern.is_some()
+                || attr.validate_with.is_some()
+                || attr.datetime_fmt.is_some()
+                || attr.trim
+                || attr.list
+                || attr.base64.is_some()
+                || attr.json
+                || attr.uuid
+                || attr.duration_suffix
+                || attr.otel_attr.is_some()
+                || attr.trace
+                || attr.or_extension
+                || attr.sensitive
+                || attr.non_empty
+                || attr.or_query.is_some()
+                || attr.typed)
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`prefix` fields collect the matching headers into a map such as \
+                 `HashMap<String, String>` and don't support `Option` or other options",
+            ));
+        }
+        if attr.typed
+            && (attr.trim
+                || attr.list
+                || attr.base64.is_some()
+                || attr.json
+                || attr.uuid
+                || attr.duration_suffix
+                || attr.datetime_fmt.is_some()
+                || attr.range.is_some()
+                || attr.min_len.is_some()
+                || attr.max_len.is_some()
+                || attr.pattern.is_some()
+                || attr.validate_with.is_some()
+                || matches!(attr.default, Some(FieldDefault::Literal(_))))
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`typed` fields are decoded with `headers::Header::decode` and only support \
+                 `alias` and `default = expr`",
+            ));
+        }
+        if attr.json
+            && (raw.is_some()
+                || attr.list
+                || attr.uuid
+                || attr.duration_suffix
+                || attr.datetime_fmt.is_some()
+                || attr.range.is_some()
+                || attr.min_len.is_some()
+                || attr.max_len.is_some()
+                || attr.pattern.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`json` deserializes the value with `serde_json` and doesn't support raw fields, \
+                 `list`, `uuid`, `duration_suffix`, `datetime_fmt`, `range`, `min_len`, \
+                 `max_len` or `pattern`",
+            ));
+        }
+        if attr.list && vec_inner.is_none() {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`list` requires a `Vec<T>` or `Option<Vec<T>>` field",
+            ));
+        }
+        if attr.base64.is_some() && (attr.list || matches!(raw, Some(RawValue::HeaderValue))) {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`base64` is not supported on `HeaderValue` fields or together with `list`",
+            ));
+        }
+        if raw.is_some()
+            && attr.base64.is_none()
+            && (attr.trim
+                || attr.datetime_fmt.is_some()
+                || attr.range.is_some()
+                || attr.list
+                || attr.uuid
+                || attr.duration_suffix)
+        {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`HeaderValue` and `Vec<u8>` fields store the raw value and don't support `trim`, \
+                 `datetime_fmt`, `duration_suffix`, `range`, `list` or `uuid`",
+            ));
+        }
+        if raw.is_some()
+            && (attr.min_len.is_some()
+                || attr.max_len.is_some()
+                || attr.pattern.is_some()
+                || attr.validate_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`min_len`, `max_len`, `pattern` and `validate_with` are not supported on \
+                 `HeaderValue` and `Vec<u8>` fields",
+            ));
+        }
+        if let Some(key) = &attr.otel_attr
+            && (vec_inner.is_some() || raw.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                key,
+                "`otel_attr` is not supported on `Vec`, `HeaderValue` and `Vec<u8>` fields",
+            ));
+        }
+        if let Some((header, _)) = &attr.media_param
+            && (vec_inner.is_some() || raw.is_some() || attr.typed || attr.base64.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                header,
+                "`media_param` is not supported on `Vec`, `HeaderValue` and `Vec<u8>` fields or \
+                 together with `typed` and `base64`",
+            ));
+        }
+        if attr.nth.is_some()
+            && (vec_inner.is_some()
+                || attr.typed
+                || !attr.aliases.is_empty()
+                || attr.media_param.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`nth` reads a single occurrence of the header and is not supported on `Vec` \
+                 fields or together with `typed`, `alias` and `media_param`",
+            ));
+        }
+        if attr.non_empty && (vec_inner.is_some() || attr.typed) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`non_empty` is not supported on `Vec` fields or together with `typed`",
+            ));
+        }
+        if attr.sensitive && (attr.otel_attr.is_some() || attr.trace) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`sensitive` values must not be recorded and are not supported together with \
+                 `otel_attr` and `trace`",
+            ));
+        }
+        if attr.trace && (vec_inner.is_some() || raw.is_some()) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`trace` is not supported on `Vec`, `HeaderValue` and `Vec<u8>` fields",
+            ));
+        }
+        if attr.or_extension && container_attr.deserialize {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`or_extension` reads the request extensions and is not supported with \
+                 `#[headers(deserialize)]`",
+            ));
+        }
+        if let Some(param) = &attr.or_query {
+            if vec_inner.is_some() || raw.is_some() || attr.typed || attr.base64.is_some() {
+                return Err(syn::Error::new_spanned(
+                    param,
+                    "`or_query` is not supported on `Vec`, `HeaderValue` and `Vec<u8>` fields or \
+                     together with `typed` and `base64`",
+                ));
+            }
+            if container_attr.deserialize {
+                return Err(syn::Error::new_spanned(
+                    param,
+                    "`or_query` reads the request URI and is not supported with \
+                     `#[headers(deserialize)]`",
+                ));
+            }
+        }
+        if let (Some(validate), None) = (&attr.validate_with, &container_attr.state) {
+            return Err(syn::Error::new_spanned(
+                validate,
+                "`validate_with` requires the struct-level `#[headers(state = \"...\")]` option",
+            ));
+        }
+        if let (Some(companion), None) = (&attr.required_if, optional_inner) {
+            return Err(syn::Error::new_spanned(
+                companion,
+                "`required_if` is only supported on `Option` fields, which are required when the \
+                 named header is present and `None` otherwise",
+            ));
+        }
+        if let (Some(default), Some(_)) = (&attr.default, optional_inner) {
+            return Err(syn::Error::new_spanned(
+                default,
+                "`default` is only supported on required fields, `Option` fields are already \
+                 `None` when absent",
+            ));
+        }
+        if container_attr.all_optional && optional_inner.is_none() && attr.default.is_none() {
+            attr.default = Some(FieldDefault::Expr(syn::parse_quote!(
+                ::std::default::Default::default()
+            )));
+        }
+        // raw, `typed` and `prefix` fields never reach the trimming parsers
+        if cfg!(feature = "default-trim") && !attr.no_trim {
+            attr.trim = true;
+        }
+
+        header_fields.push(HeaderField {
+            ident,
+            ty: &field.ty,
+            optional_inner,
+            vec_inner,
+            raw,
+            prefix,
+            header_name,
+            attr,
+        });
+    }
+
+    if container_attr.infallible && problems_field.is_none() {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`infallible` requires a `Vec<HeaderError>` field marked `#[header(problems)]` to \
+             record the extraction problems",
+        ));
+    }
+
+    let mut field_constructions: Vec<_> = header_fields
+        .iter()
+        .map(|field| field.ident.to_token_stream())
+        .collect();
+    field_constructions.extend(
+        skipped_fields
+            .iter()
+            .map(|ident| quote!(#ident: ::std::default::Default::default())),
+    );
+    field_constructions.extend(problems_field.map(|ident| quote!(#ident: __problems)));
+    let field_parsers = if container_attr.infallible {
+        let parsers = header_fields.iter().map(HeaderField::infallible_parser);
+        let group_checks = group_checks(&container_attr, |error| quote!(__problems.push(#error);));
+        quote! {
+            let mut __problems: ::std::vec::Vec<::axum_required_headers::HeaderError> = ::std::vec::Vec::new();
+
+            #group_checks
+            #(#parsers)*
+        }
+    } else if container_attr.collect_errors {
+        let group_checks = group_checks(&container_attr, |error| quote!(__errors.push(#error);));
+        collecting_parsers(&header_fields, group_checks)
+    } else {
+        let parsers = header_fields.iter().map(HeaderField::strict_parser);
+        let group_checks = group_checks(
+            &container_attr,
+            |error| quote!(return ::std::result::Result::Err(#error);),
+        );
+        quote! {
+            #group_checks
+            #(#parsers)*
+        }
+    };
+    let axum_crate = get_crate("axum")?;
+    let http_crate = get_crate("http")?;
+
+    // `validate_with` functions get the `state` type, taken from the router state
+    if let (Some(state), Some(wc)) = (&container_attr.state, &mut where_clause_with_s) {
+        wc.predicates
+            .push(syn::parse_quote!(#state: ::#axum_crate::extract::FromRef<#s_ident>));
+    }
+    // `into_extension` values are cloned into the request extensions, and `or_extension` values
+    // out of them
+    let extension_bounds: Vec<syn::WherePredicate> = header_fields
+        .iter()
+        .flat_map(|field| {
+            field
+                .extension_type()
+                .into_iter()
+                .chain(field.fallback_extension_type())
+        })
+        .map(|ty| {
+            syn::parse_quote!(
+                #ty: ::std::clone::Clone + ::std::marker::Send + ::std::marker::Sync + 'static
+            )
+        })
+        .collect();
+    if let Some(wc) = &mut where_clause_with_s {
+        wc.predicates.extend(extension_bounds);
+    }
+    let state_binding = |state_ident: &str| {
+        let state_ident = Ident::new(state_ident, proc_macro2::Span::call_site());
+        container_attr.state.as_ref().map(|state| {
+            quote! {
+                let __state = &<#state as ::#axum_crate::extract::FromRef<#s_ident>>::from_ref(#state_ident);
+            }
+        })
+    };
+    let state_from_extractor = state_binding("_state");
+    let state_from_optional_extractor = state_binding("state");
+
+    // `Option<Self>` as an extractor: `None` when required headers are absent, but malformed
+    // values are still rejected
+    let required_fields: Vec<_> = header_fields
+        .iter()
+        .filter(|field| field.is_required())
+        .collect();
+    let required_bindings: Vec<_> = required_fields
+        .iter()
+        .map(|field| format_ident!("__{}", field.ident))
+        .collect();
+    let required_lookups = required_fields.iter().map(|field| field.lookup());
+    let none_condition = if container_attr.none_if_all_absent {
+        quote!(!__absent.is_empty() && __absent.iter().all(|absent| *absent))
+    } else {
+        quote!(__absent.iter().any(|absent| *absent))
+    };
+
+    let headers_source = headers_source(&container_attr, &http_crate);
+    let rejection = rejection_type(&container_attr);
+    let into_rejection = into_rejection(&container_attr, &http_crate);
+
+    let span_attributes: proc_macro2::TokenStream = header_fields
+        .iter()
+        .flat_map(|field| {
+            field
+                .span_attribute()
+                .into_iter()
+                .chain(field.span_record())
+        })
+        .collect();
+
+    let extension_inserts: Vec<_> = header_fields
+        .iter()
+        .filter_map(HeaderField::extension_insert)
+        .collect();
+
+    // `from_request_parts` delegates to `from_headers`, passing the request extensions along
+    // for `or_extension`
+    let state_param = container_attr
+        .state
+        .as_ref()
+        .map(|state| quote!(, __state: &#state));
+    let state_arg = container_attr.state.as_ref().map(|_| quote!(, __state));
+    let extract_output = if container_attr.infallible {
+        quote!(Self)
+    } else {
+        quote!(::std::result::Result<Self, ::axum_required_headers::HeaderError>)
+    };
+    let extract_value = if container_attr.infallible {
+        quote!(Self { #(#field_constructions),* })
+    } else {
+        quote!(::std::result::Result::Ok(Self { #(#field_constructions),* }))
+    };
+    let from_headers_doc = if container_attr.infallible {
+        "Extracts the headers from a `HeaderMap`, without a request.\n\n\
+         Every problem is recorded in the `problems` field, as by the extractor."
+    } else {
+        "Extracts the headers from a `HeaderMap`, without a request.\n\n\
+         Fails with the same `HeaderError` as the extractor, before it is turned into a \
+         rejection. `or_query` and `or_extension` fields only read the headers."
+    };
+    let (from_headers_state_param, from_headers_state_arg) = match &container_attr.state {
+        Some(state) => (Some(quote!(, state: &#state)), Some(quote!(, state))),
+        None => (None, None),
+    };
+    let extraction_where_clause = {
+        let mut where_clause = where_clause.cloned();
+        for ty in header_fields
+            .iter()
+            .filter_map(HeaderField::fallback_extension_type)
+        {
+            where_clause
+                .get_or_insert_with(|| syn::parse_quote!(where))
+                .predicates
+                .push(syn::parse_quote!(
+                    #ty: ::std::clone::Clone + ::std::marker::Send + ::std::marker::Sync + 'static
+                ));
+        }
+        where_clause
+    };
+
+    let extraction = if container_attr.infallible {
+        quote! {
+            let __value = {
+                #headers_source
+                #state_from_extractor
+                Self::__extract_headers(__headers, parts.uri.query(), &parts.extensions #state_arg)
+            };
+            #(#extension_inserts)*
+            ::std::result::Result::Ok(__value)
+        }
+    } else if !extension_inserts.is_empty() {
+        // the headers are no longer borrowed once the value is built
+        quote! {
+            let __result = {
+                #headers_source
+                #state_from_extractor
+                Self::__extract_headers(__headers, parts.uri.query(), &parts.extensions #state_arg)
+            };
+
+            let __value = __result.map_err(|error| #into_rejection)?;
+            #(#extension_inserts)*
+            ::std::result::Result::Ok(__value)
+        }
+    } else {
+        quote! {
+            let __result = {
+                #headers_source
+                #state_from_extractor
+                Self::__extract_headers(__headers, parts.uri.query(), &parts.extensions #state_arg)
+            };
+
+            __result.map_err(|error| #into_rejection)
+        }
+    };
+    // `None` never happens without possible rejections
+    let optional_extraction = if container_attr.infallible {
+        quote! {
+            <Self as ::#axum_crate::extract::FromRequestParts<#s_ident>>::from_request_parts(parts, state)
+                .await
+                .map(::std::option::Option::Some)
+        }
+    } else {
+        quote! {
+            {
+                #headers_source
+                let __query = parts.uri.query();
+                let __extensions = &parts.extensions;
+                #state_from_optional_extractor
+                #(let #required_bindings = #required_lookups;)*
+                let __absent: &[bool] = &[#(#required_bindings.is_none()),*];
+
+                if #none_condition {
+                    #(
+                        if let ::std::option::Option::Some(::std::result::Result::Err(error)) = #required_bindings {
+                            return ::std::result::Result::Err(#into_rejection);
+                        }
+                    )*
+                    return ::std::result::Result::Ok(::std::option::Option::None);
+                }
+            }
+
+            <Self as ::#axum_crate::extract::FromRequestParts<#s_ident>>::from_request_parts(parts, state)
+                .await
+                .map(::std::option::Option::Some)
+        }
+    };
+
+    let lenient = container_attr.lenient.then(|| {
+        let lenient_parsers = header_fields.iter().map(HeaderField::lenient_parser);
+        let group_checks = group_checks(
+            &container_attr,
+            |error| quote!(__warnings.push(::axum_required_headers::HeaderWarning::new(#error));),
+        );
+        quote! {
+            impl #impl_generics #name #ty_generics #extraction_where_clause {
+                /// Extracts the headers without ever failing.
+                ///
+                /// Missing or malformed required headers fall back to `Default::default()`,
+                /// malformed optional headers to `None`. Every tolerated problem is returned
+                /// as a warning alongside the value.
+                pub fn from_request_parts_lenient(
+                    parts: &::#http_crate::request::Parts,
+                ) -> (Self, ::std::vec::Vec<::axum_required_headers::HeaderWarning>) {
+                    #headers_source
+                    let __query = parts.uri.query();
+                    let __extensions = &parts.extensions;
+                    let mut __warnings = ::std::vec::Vec::new();
+
+                    #group_checks
+                    #(#lenient_parsers)*
+                    #span_attributes
+
+                    (Self { #(#field_constructions),* }, __warnings)
+                }
+            }
+        }
+    });
+
+    let deserialize = container_attr.deserialize.then(|| {
+        let mut generics_with_de = input.generics.clone();
+        generics_with_de
+            .params
+            .insert(0, syn::parse_quote!('__de));
+        let (impl_generics_with_de, _, _) = generics_with_de.split_for_impl();
+        quote! {
+            impl #impl_generics_with_de ::axum_required_headers::__private::serde::Deserialize<'__de>
+                for #name #ty_generics
+                #where_clause
+            {
+                fn deserialize<__D>(deserializer: __D) -> ::std::result::Result<Self, __D::Error>
+                where
+                    __D: ::axum_required_headers::__private::serde::Deserializer<'__de>,
+                {
+                    let __headers = &::axum_required_headers::__private::deserialize_header_map(deserializer)?;
+                    let __result = (|| -> ::std::result::Result<Self, ::axum_required_headers::HeaderError> {
+                        #field_parsers
+
+                        Ok(Self {
+                            #(#field_constructions),*
+                        })
+                    })();
+
+                    __result.map_err(<__D::Error as ::axum_required_headers::__private::serde::de::Error>::custom)
+                }
+            }
+        }
+    });
+
+    let into_params = into_params(&input, &header_fields);
+
+    let header_names = header_fields
+        .iter()
+        .filter(|field| field.prefix.is_none())
+        .map(|field| &field.header_name);
+    let required_header_names = required_fields.iter().map(|field| &field.header_name);
+
+    let expanded = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The names of all headers extracted into this struct.
+            pub const HEADER_NAMES: &'static [&'static str] = &[#(#header_names),*];
+
+            /// The names of the headers whose absence rejects the request.
+            pub const REQUIRED_HEADER_NAMES: &'static [&'static str] = &[#(#required_header_names),*];
+        }
+
+        impl #impl_generics #name #ty_generics #extraction_where_clause {
+            #[doc = #from_headers_doc]
+            pub fn from_headers(
+                headers: &::#http_crate::HeaderMap #from_headers_state_param
+            ) -> #extract_output {
+                Self::__extract_headers(
+                    headers,
+                    ::std::option::Option::None,
+                    &::#http_crate::Extensions::new()
+                    #from_headers_state_arg
+                )
+            }
+
+            fn __extract_headers(
+                __headers: &::#http_crate::HeaderMap,
+                __query: ::std::option::Option<&str>,
+                __extensions: &::#http_crate::Extensions
+                #state_param
+            ) -> #extract_output {
+                #field_parsers
+                #span_attributes
+
+                #extract_value
+            }
+        }
+
+        impl #impl_generics_with_s ::#axum_crate::extract::FromRequestParts<#s_ident>
+            for #name #ty_generics
+            #where_clause_with_s
+        {
+            type Rejection = #rejection;
+
+            async fn from_request_parts(
+                parts: &mut ::#http_crate::request::Parts,
+                _state: &#s_ident,
+            ) -> ::std::result::Result<Self, Self::Rejection> {
+                #extraction
+            }
+        }
+
+        impl #impl_generics_with_s ::#axum_crate::extract::OptionalFromRequestParts<#s_ident>
+            for #name #ty_generics
+            #where_clause_with_s
+        {
+            type Rejection = #rejection;
+
+            async fn from_request_parts(
+                parts: &mut ::#http_crate::request::Parts,
+                state: &#s_ident,
+            ) -> ::std::result::Result<::std::option::Option<Self>, Self::Rejection> {
+                #optional_extraction
+            }
+        }
+
+        #lenient
+
+        #deserialize
+
+        #into_params
+    };
+
+    Ok(expanded)
+}
+
+/// Implements `utoipa::IntoParams`, documenting every field as a `header` parameter.
+///
+/// Prefixed fields don't name a single header and are left out.
+#[cfg(feature = "utoipa")]
+fn into_params(input: &DeriveInput, header_fields: &[HeaderField]) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let parameters = header_fields
+        .iter()
+        .filter(|field| field.prefix.is_none())
+        .map(|field| {
+            let header_name = &field.header_name;
+            let required = field.is_required();
+            let value_type = field.value_type();
+            quote! {
+                ::axum_required_headers::__private::header_parameter(
+                    #header_name,
+                    #required,
+                    (&&::axum_required_headers::__private::FieldSchema::<#value_type>(
+                        ::std::marker::PhantomData,
+                    ))
+                        .field_schema(),
+                )
+            }
+        });
+
+    quote! {
+        impl #impl_generics ::axum_required_headers::utoipa::IntoParams for #name #ty_generics #where_clause {
+            fn into_params(
+                _parameter_in_provider: impl ::std::ops::Fn() -> ::std::option::Option<
+                    ::axum_required_headers::utoipa::openapi::path::ParameterIn,
+                >,
+            ) -> ::std::vec::Vec<::axum_required_headers::utoipa::openapi::path::Parameter> {
+                #[allow(unused_imports)]
+                use ::axum_required_headers::__private::{StringSchema as _, TypeSchema as _};
+
+                ::std::vec![#(#parameters),*]
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "utoipa"))]
+fn into_params(_input: &DeriveInput, _header_fields: &[HeaderField]) -> proc_macro2::TokenStream {
+    proc_macro2::TokenStream::new()
+}
+
+/// Statements binding every field, failing with all errors of the required fields at once.
+///
+/// A single error is returned as is, several as `HeaderError::Multiple`.
+fn collecting_parsers(
+    header_fields: &[HeaderField],
+    group_checks: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let parsers = header_fields.iter().map(HeaderField::collecting_parser);
+    let required: Vec<_> = header_fields
+        .iter()
+        .filter(|field| !field.never_fails())
+        .map(|field| field.ident)
+        .collect();
+
+    // every required binding is `Some` once no error was recorded
+    let unwrap_required = (!required.is_empty()).then(|| {
+        quote! {
+            let (#(::std::option::Option::Some(#required)),*) = (#(#required),*) else {
+                ::std::unreachable!("required headers without recorded errors are present");
+            };
+        }
+    });
+
+    quote! {
+        let mut __errors: ::std::vec::Vec<::axum_required_headers::HeaderError> = ::std::vec::Vec::new();
+
+        #group_checks
+        #(#parsers)*
+
+        match __errors.len() {
+            0 => {}
+            1 => return ::std::result::Result::Err(__errors.remove(0)),
+            _ => return ::std::result::Result::Err(::axum_required_headers::HeaderError::Multiple(__errors)),
+        }
+
+        #unwrap_required
+    }
+}
+
+/// Statements checking each `all_or_none` group of headers, passing the `HeaderError` of an
+/// incomplete group to `record`
+fn group_checks(
+    container_attr: &ContainerAttr,
+    record: impl Fn(proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let record_error = record(quote!(error));
+    container_attr
+        .all_or_none
+        .iter()
+        .map(|group| {
+            // `HeaderMap` names are lowercase
+            let names = group.iter().map(|name| name.value().to_ascii_lowercase());
+            quote! {
+                if let ::std::result::Result::Err(error) =
+                    ::axum_required_headers::__private::check_header_group(__headers, &[#(#names),*])
+                {
+                    #record_error
+                }
+            }
+        })
+        .collect()
+}
+
+/// Statements binding `__headers` to the `HeaderMap` the fields are read from, with the request
+/// `parts` in scope.
+///
+/// With `source_extension` the map comes from the request extension of that type, a missing
+/// extension behaving like an empty map.
+fn headers_source(
+    container_attr: &ContainerAttr,
+    http_crate: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Some(extension) = &container_attr.source_extension else {
+        return quote!(let __headers = &parts.headers;);
+    };
+
+    quote! {
+        let __empty = ::#http_crate::HeaderMap::new();
+        let __headers: &::#http_crate::HeaderMap = match parts.extensions.get::<#extension>() {
+            ::std::option::Option::Some(extension) => ::std::convert::AsRef::<::#http_crate::HeaderMap>::as_ref(extension),
+            ::std::option::Option::None => &__empty,
+        };
+    }
+}
+
+/// The `Rejection` type of the generated extractors.
+///
+/// Plain `HeaderError` unless a struct-level option needs to shape the response, `Infallible` for
+/// `infallible` structs.
+fn rejection_type(container_attr: &ContainerAttr) -> proc_macro2::TokenStream {
+    if container_attr.infallible {
+        quote!(::std::convert::Infallible)
+    } else if container_attr.shapes_rejection() {
+        quote!(::axum_required_headers::HeaderRejection)
+    } else {
+        quote!(::axum_required_headers::HeaderError)
+    }
+}
+
+/// An expression converting the `HeaderError` bound to `error` into the rejection type, with
+/// the request `parts` in scope.
+fn into_rejection(
+    container_attr: &ContainerAttr,
+    http_crate: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let rejection = if container_attr.shapes_rejection() {
+        header_rejection(container_attr, http_crate)
+    } else {
+        quote!(error)
+    };
+
+    if container_attr.debug_on_error {
+        // logs the map the fields were looked up in, which differs from `parts.headers` with
+        // `source_extension`
+        let headers_source = headers_source(container_attr, http_crate);
+        quote!({
+            {
+                #headers_source
+                ::axum_required_headers::__private::debug_header_error(__headers, &error);
+            }
+            #rejection
+        })
+    } else {
+        rejection
+    }
+}
+
+/// An expression building a `HeaderRejection` from the `HeaderError` bound to `error`
+fn header_rejection(
+    container_attr: &ContainerAttr,
+    http_crate: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let mut rejection = if container_attr.negotiate_error {
+        quote!(::axum_required_headers::HeaderRejection::negotiate(
+            error, parts
+        ))
+    } else {
+        quote!(::axum_required_headers::HeaderRejection::new(error))
+    };
+    if container_attr.advertise_required {
+        rejection.extend(quote!(.with_required_headers(Self::REQUIRED_HEADER_NAMES)));
+    }
+    let status_code = |status: u16| quote!(::#http_crate::StatusCode::from_u16(#status).expect("status validated by the derive"));
+    if let Some(status) = container_attr.status {
+        let status = status_code(status);
+        rejection.extend(quote!(.with_status(#status)));
+    }
+    if let Some(status) = container_attr.missing_status {
+        let status = status_code(status);
+        rejection.extend(quote!(.with_missing_status(#status)));
+    }
+    if let Some(location) = &container_attr.on_missing_redirect {
+        rejection.extend(quote! {
+            .with_missing_redirect(::#http_crate::HeaderValue::from_static(#location))
+        });
+    }
+    if let Some(dto) = &container_attr.error_dto {
+        rejection.extend(quote!(.with_error_dto::<#dto>()));
+    }
+    rejection
+}
+
+/// Resolves the header name of a field.
+///
+/// Precedence: explicit literal or const > `rename` > `rename_all` rule. The container `prefix`
+/// is applied to renamed and rule-derived names, but never to an explicit name.
+fn resolve_header_name(
+    container_attr: &ContainerAttr,
+    field_attr: Option<&(&syn::Attribute, FieldAttr)>,
+    field_name: &Ident,
+) -> Option<HeaderNameExpr> {
+    let field_attr = field_attr.map(|(_, field_attr)| field_attr);
+
+    if let Some(name) = field_attr.and_then(|attr| attr.name.as_ref()) {
+        return Some(HeaderNameExpr::Literal(name.value()));
+    }
+    if let Some(path) = field_attr.and_then(|attr| attr.name_const.as_ref()) {
+        return Some(HeaderNameExpr::Const(path.clone()));
+    }
+
+    let base = match field_attr.and_then(|attr| attr.rename.as_ref()) {
+        Some(rename) => rename.value(),
+        None => container_attr.rename_all?.apply(field_name),
+    };
+    let prefix = container_attr
+        .prefix
+        .as_ref()
+        .map(LitStr::value)
+        .unwrap_or_default();
+
+    Some(HeaderNameExpr::Literal(format!("{prefix}{base}")))
+}
+
+/// The primary header name for `HeaderMap` lookups.
+///
+/// Valid lowercase names become a `HeaderName` constant, sparing the parsing of the name on every
+/// lookup. Other names (e.g. `X-User-Id`) can't use `HeaderName::from_static` and stay strings,
+/// as do const names, whose value isn't known at expansion time.
+fn lookup_name(header_name: &HeaderNameExpr) -> proc_macro2::TokenStream {
+    let HeaderNameExpr::Literal(header_name) = header_name else {
+        return quote!(#header_name);
+    };
+    let is_static = !header_name.is_empty()
+        && header_name.bytes().all(|b| {
+            b.is_ascii_lowercase() || b.is_ascii_digit() || b"!#$%&'*+-.^_`|~".contains(&b)
+        });
+    if is_static {
+        quote! {
+            &const { ::axum_required_headers::http::HeaderName::from_static(#header_name) }
+        }
+    } else {
+        quote!(#header_name)
+    }
+}
+
+/// Renders tokens without the whitespace `proc_macro2` inserts, e.g. `0..=10` instead of `0 ..= 10`
+fn compact_tokens(tokens: impl ToTokens) -> String {
+    tokens
+        .to_token_stream()
+        .to_string()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+/// Helper function to extract `T` if a type is `Option<T>` or `std::option::Option<T>`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    generic_inner_type(ty, "Option")
+}
+
+/// Whether a type is a path ending in `ident`, e.g. `http::HeaderValue`
+fn last_segment_is(ty: &Type, ident: &str) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == ident && segment.arguments.is_none())
+}
+
+fn is_u8(ty: &Type) -> bool {
+    last_segment_is(ty, "u8")
+}
+
+/// Whether a type is `Box<str>` or `Cow<'_, str>` (or a path ending in them)
+fn is_owned_str(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(last_segment) = type_path.path.segments.last() else {
+        return false;
+    };
+    if last_segment.ident != "Box" && last_segment.ident != "Cow" {
+        return false;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+        return false;
+    };
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    });
+    matches!((types.next(), types.next()), (Some(inner), None) if last_segment_is(inner, "str"))
+}
+
+/// Extracts `T` if a type is `Wrapper<T>` (or a path ending in `Wrapper<T>`)
+fn generic_inner_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    // Check if the last segment is the wrapper
+    let last_segment = type_path.path.segments.last()?;
+    if last_segment.ident != wrapper {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
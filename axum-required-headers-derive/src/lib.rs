@@ -1,22 +1,49 @@
 use proc_macro::TokenStream;
 use proc_macro_crate::FoundCrate;
 use proc_macro2::Span;
-use quote::quote;
-use syn::{Data, DeriveInput, Fields, Ident, LitStr, parse_macro_input};
+use quote::{quote, quote_spanned};
+use syn::{Data, DataStruct, DeriveInput, Fields, Ident, LitStr, parse_macro_input};
 
-const ATTRIBUTE_IDENT: &str = "header";
+mod attr;
+mod headers;
+
+use attr::{FIELD_ATTRIBUTE_IDENT, FieldAttr, HeaderNameExpr, VARIANT_VALUE_ATTRIBUTE_IDENT};
+use headers::derive_headers_impl;
 
 /// Derive macro for individual header types.
 ///
 /// Automatically implements both `RequiredHeader` and `OptionalHeader`
 /// for a type, allowing it to be used with either `Required<T>` or `Optional<T>`.
 ///
+/// The type must implement `FromStr`; a missing implementation is reported at the derive.
+///
+/// For fieldless enums the `FromStr` impl is generated: each variant matches its name,
+/// case-insensitively, or the value of its `#[header_value("...")]` attribute instead. Values
+/// matching no variant fail with `ParseEnumError`, rejecting the request like any parse error.
+/// With `#[header("header-name", warn_on_normalize)]`, values only matching a variant in a
+/// different case are logged at warn level, to track non-conforming clients (requires the
+/// `tracing` feature of `axum-required-headers`, without it nothing is logged).
+///
 /// # Attributes
 /// - `#[header("header-name")]` - Specifies the header name to extract
+/// - `#[header("header-name", alias = "other-name")]` - Falls back to `other-name` when
+///   `header-name` is absent, `alias` may be repeated and aliases are tried in order
+/// - `#[header("header-name", non_empty)]` - Treats an empty value (e.g. `header-name: `) like an
+///   absent header: `Required<T>` rejects it as `HeaderError::Missing` and `Optional<T>` is `None`.
+///   Sets `EMPTY_IS_MISSING`
+/// - `#[header("header-name", infallible)]` - Generates the `FromStr` impl (with
+///   `Err = Infallible`) instead of requiring one. Only for newtype structs like
+///   `struct UserId(String)` whose field implements `From<String>`; don't combine it with a
+///   hand-written `FromStr` impl
+/// - `#[header("header-name", to_header_value)]` - Generates
+///   `fn to_header_value(&self) -> Result<HeaderValue, InvalidHeaderValue>`, formatting the field
+///   with `Display`, to send the header on outbound requests. Only for newtype structs whose field
+///   implements `Display`. The type also implements `IntoResponseParts`, writing the header into
+///   responses, e.g. `(UserId(id), body)`; an invalid value fails with a `500` `HeaderRejection`
 ///
 /// See `axum-required-headers` for examples
 ///
-#[proc_macro_derive(Header, attributes(header))]
+#[proc_macro_derive(Header, attributes(header, header_value))]
 pub fn derive_header(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -31,12 +58,220 @@ pub fn derive_header(input: TokenStream) -> TokenStream {
 /// # Attributes
 ///
 /// - `#[header("header-name")]` - Marks a field as a header
+/// - `#[header(name = X_HEADER_NAME)]` - Marks a field as a header named by a `&'static str`
+///   const, shared e.g. with clients or middleware. The name is used in const contexts
+///   (`HEADER_NAMES`), so it must be a `const`, not a `static`. Also accepted on `Header` types.
+///   The const can also be given in place of the literal, e.g. `#[header(HeaderNames::USER_ID)]`
+///   or `#[header(X_USER_ID, trim)]`, as long as it is a path with several segments or an
+///   uppercase name, which tells it apart from the options
+/// - `#[header(from_type)]` - Takes the header name from the `RequiredHeader` impl of the field
+///   type (the `T` of `Option<T>` and `Vec<T>`), e.g. a `#[derive(Header)]` newtype, instead of
+///   repeating it. The aliases of the type are not used, add `alias = "..."` for them
+/// - `#[header(rename = "Header-Name")]` - Overrides the `rename_all` rule for a field while still
+///   applying the struct `prefix`
+/// - `#[header("header-name", range(0..=100))]` - Rejects parsed values outside the range (`a..b` or
+///   `a..=b`) with `HeaderError::Validation`
+/// - `#[header("header-name", min_len = 32, max_len = 64)]` - Rejects values (after `trim`) whose
+///   byte length is outside the bounds with `HeaderError::Validation`, before parsing. Either
+///   bound can be given alone. Like any invalid value, an `Option` field becomes `None` instead
+/// - `#[header("header-name", pattern = "^[0-9a-f-]{36}$")]` - Rejects values (after `trim`) not
+///   matching the regex with `HeaderError::Validation`. The regex is checked at compile time and
+///   compiled once on first use. Requires the `regex` feature
+/// - `#[header("header-name", alias = "other-name")]` - Falls back to the aliases, in order, when
+///   the header is absent. `Missing` errors mention the primary name
+/// - `#[header("header-name", media_param("accept", "version"))]` - Falls back to the `version`
+///   parameter of the media types in the `accept` header (the first one carrying it, quotes
+///   removed) when neither the header nor its aliases are present, before `default`. The value is
+///   parsed like the header's. Not supported on `Vec`, raw, `typed` and `base64` fields
+/// - `#[header("header-name", nth = 1)]` - Reads the zero-based Nth occurrence of a repeated
+///   header instead of the first, so ordered occurrences fill distinct fields. Fewer occurrences
+///   count as an absent header: `Missing` for required fields, `None` for `Option` fields. Not
+///   supported on `Vec` fields or together with `typed`, `alias` and `media_param`
+/// - `#[header("header-name", trim)]` - Strips surrounding whitespace from the value before
+///   parsing, `String` fields store the trimmed value
+/// - `#[header("header-name", no_trim)]` - Keeps surrounding whitespace when the `default-trim`
+///   feature of `axum-required-headers` makes every field trim. A field's `no_trim` wins over its
+///   `trim`, which wins over the feature
+/// - `#[header("header-name", list)]` - Splits comma-separated values (of every occurrence) into
+///   the items of a `Vec<T>` field, parsing each item with `FromStr`. Add `skip_unknown` to skip
+///   items that fail to parse instead of rejecting the header. Items are trimmed and empty items
+///   skipped, so a present header holding only commas or whitespace is an empty `Vec`. `csv` is
+///   another spelling of `list`, e.g. `#[header("accept-language", csv)]`
+/// - `#[header("header-name", base64)]` - Decodes the value from base64 before parsing it with
+///   `FromStr` (the decoded bytes must be UTF-8), or stores the decoded bytes in `Vec<u8>` fields.
+///   `base64` uses the standard alphabet with required padding, `base64 = "url_safe"` the URL-safe
+///   alphabet with optional padding. Requires the `base64` feature of `axum-required-headers`
+/// - `#[header("header-name", duration_suffix)]` - Parses a `Duration` field from a whole number
+///   with a `ms`, `s`, `m` or `h` unit, e.g. `500ms` or `30s`. A bare number is in seconds, other
+///   units are rejected with `HeaderError::Parse`
+/// - `#[header("header-name", uuid)]` - Rejects values that aren't a hyphenated UUID (e.g. for
+///   `idempotency-key`) with `HeaderError::Validation`, before parsing them
+/// - `#[header("authorization", typed)]` - Decodes every occurrence of the header with
+///   `headers::Header::decode` instead of `FromStr`, for types of the `headers` crate such as
+///   `Authorization<Bearer>`. Decode failures are `HeaderError::Parse`. The header name is still
+///   taken from the attribute, not from `Header::name`. Requires the `headers` feature of
+///   `axum-required-headers`
+/// - `#[header(prefix = "x-meta-")]` - Collects every header whose name starts with the prefix
+///   into a map field such as `HashMap<String, String>` (any `FromIterator<(String, String)>`),
+///   keyed by the rest of the name. Each header contributes its first value; values that aren't
+///   valid ASCII are skipped rather than rejecting the request, so the field never fails
+/// - `#[header("header-name", default = "value")]` - Parses `"value"` with `FromStr` when a required
+///   header is absent instead of rejecting the request. `default = expr` uses an expression of the
+///   field type instead, e.g. `default = my_fn()`. A present header always wins, and a malformed one
+///   is still rejected
+/// - `#[header("header-name", datetime_fmt = "%Y-%m-%dT%H:%M:%S%z")]` - Parses the value with
+///   `chrono::DateTime::parse_from_str` into a `DateTime<FixedOffset>` (or any type convertible from
+///   it, e.g. `DateTime<Utc>`). Requires the `chrono` feature of `axum-required-headers`
+/// - `#[header("header-name", base64, gzip, json)]` - Decodes the value in stages: `base64`
+///   decodes it, `gzip` decompresses the result (at most 1 MiB) and `json` deserializes it into
+///   the field type with `serde_json`. `json` also works on its own, and `gzip` requires `base64`.
+///   A failing stage is reported as `HeaderError::Decode` naming it. Requires the `gzip` and
+///   `json` features of `axum-required-headers` respectively
+/// - `#[headers(rename_all = "kebab-case", prefix = "x-")]` - Derives header names from field
+///   identifiers for fields without an explicit header name
+/// - `#[headers(lenient)]` - Also generates `from_request_parts_lenient`, which never fails and
+///   returns the tolerated problems as `HeaderWarning`s (required field types must implement
+///   `Default`)
+/// - `#[headers(infallible)]` - Extraction never fails and the rejection is `Infallible`. Missing
+///   or malformed required headers fall back to `Default::default()` (their type must implement
+///   `Default`), malformed optional headers to `None`, and every problem is recorded in the
+///   `Vec<HeaderError>` field marked `#[header(problems)]`. Meant for request inspection and
+///   diagnostics endpoints
+/// - `#[headers(all_optional)]` - No header is required: absent headers leave `Option` fields
+///   `None` and set other fields to `Default::default()` (their type must implement `Default`),
+///   as if every field had `default = Default::default()`. Fields with their own `default` keep it,
+///   and malformed values are still rejected
+/// - `#[headers(none_if_all_absent)]` - Makes `Option<Self>` extract as `None` only when every
+///   required header is absent, instead of when any is
+/// - `#[headers(negotiate_error)]` - Rejects with a `HeaderRejection` rendered as JSON, HTML or
+///   plain text depending on the request's `Accept` header
+/// - `#[headers(advertise_required)]` - Rejects with a `HeaderRejection` listing the required
+///   headers in an `x-required-headers` response header when one is missing
+/// - `#[headers(status = 422, missing_status = 401)]` - Rejects with a `HeaderRejection` using the
+///   given response status, `missing_status` applying to missing headers only (both default to
+///   `400`)
+/// - `#[headers(error_status = 200)]` - Rejects with a `HeaderRejection` using the given status
+///   for every error, missing headers included. Meant for legacy clients that can't handle error
+///   statuses, which then have to inspect the body; responding `200` to a rejected request is
+///   non-standard, so prefer `status` and `missing_status` otherwise
+/// - `#[headers(on_missing_redirect = "/login")]` - Rejects with a `HeaderRejection` redirecting
+///   to the location with `303 See Other` when a header is missing. Other errors are rendered as
+///   usual
+/// - `#[headers(error_dto = "ErrorResponse")]` - Rejects with a `HeaderRejection` whose JSON body
+///   is `ErrorResponse`, built with `From<&HeaderError>` and serialized with `Serialize`. The status
+///   is chosen as usual, e.g. with `status`
+/// - `#[headers(deserialize)]` - Also implements `serde::Deserialize`, reading a map of header
+///   names (not field names) to values, e.g. a `HashMap<String, String>`, and parsing it like the
+///   request headers. Useful to build fixtures or read headers from configuration. Requires the
+///   `serde` feature of `axum-required-headers`
+/// - `#[headers(debug_on_error)]` - Logs the names of all request headers (or of the
+///   `source_extension` map) at debug level when extraction fails, to spot typos and casing
+///   issues. Requires the `tracing` feature of `axum-required-headers`, without it nothing is
+///   logged
+/// - `#[headers(collect_errors)]` - Reports every failing required header at once as
+///   `HeaderError::Multiple` instead of stopping at the first; a single failure is still reported
+///   on its own
+/// - `#[headers(state = "AppConfig")]` and `#[header("header-name", validate_with = f)]` - Calls
+///   `f(&str, &AppConfig) -> Result<(), E>` on the value (after `trim`) before parsing, rejecting
+///   with `HeaderError::Validation` (the reason being the `E: Display`) when it fails. The state
+///   is taken from the router state with `FromRef`
+/// - `#[headers(source_extension = "CanonicalHeaders")]` - Reads the headers from the
+///   `CanonicalHeaders` request extension instead of the request headers. The type must implement
+///   `AsRef<HeaderMap>`; when the extension is absent every header is treated as missing
+/// - `#[headers(all_or_none("x-signature", "x-signature-timestamp"))]` - Rejects with
+///   `HeaderError::IncompleteGroup` when some but not all headers of the group are present; none
+///   or all of them are fine. Only presence is checked, the headers don't need to be fields. Can
+///   be repeated for several groups
+/// - `#[header("header-name", required_if = "other-name")]` - On `Option` fields: when the
+///   `other-name` header is present, the field is required (missing or malformed values are
+///   rejected), otherwise it is optional as usual. Only the presence of `other-name` in the request
+///   is checked, not whether it is valid or extracted into a field, so field order doesn't matter.
+///   `required_if_present = "other-name"` is the same option under a more explicit name
+/// - `#[header("header-name", otel_attr = "tenant.id")]` - Sets the extracted value, formatted with
+///   `Display`, as the `tenant.id` attribute of the active OpenTelemetry span (`Option` fields only
+///   when present). Not supported on `Vec` and raw fields. Requires the `otel` feature of
+///   `axum-required-headers`
+/// - `#[header("header-name", trace)]` - Records the extracted value, formatted with `Display`, as
+///   the `header-name` field of the current `tracing` span (`Option` fields only when present), and
+///   logs a warning when the value is malformed. The span must declare the field, e.g. with
+///   `"header-name" = tracing::field::Empty`. Not supported on `Vec` and raw fields. Does nothing
+///   without the `tracing` feature of `axum-required-headers`
+/// - `#[header("header-name", into_extension)]` - Also inserts a clone of the extracted value
+///   into the request extensions once the whole struct was extracted, for later extractors,
+///   middleware or `Extension<T>` to read (`Option` fields insert the inner value, only when
+///   present). The type must be `Clone + Send + Sync + 'static`. `from_request_parts_lenient` and
+///   `Deserialize` don't touch extensions
+/// - `#[header("header-name", or_extension)]` - When the header (and its aliases) is absent, falls
+///   back to a clone of the value of the field type (the inner type of `Option` fields) in the
+///   request extensions, e.g. stored there by an upstream middleware, before the `default` or
+///   reporting the header as missing. Headers always win over extensions. The type must be
+///   `Clone + Send + Sync + 'static`. Not supported with `#[headers(deserialize)]`, which has no
+///   request extensions
+/// - `#[header("header-name", sensitive)]` - Marks the value of `HeaderValue` fields (and each
+///   value of `Vec<HeaderValue>` fields) sensitive with `HeaderValue::set_sensitive`, so it is
+///   redacted from its `Debug` output and HTTP/2 header compression. Rejected together with
+///   `otel_attr` and `trace`, which record the value. Errors never include header values. Wrap
+///   other field types in `Sensitive<T>` to redact them from `Debug`
+/// - `#[header("header-name", non_empty)]` - Treats an empty value like an absent header (and
+///   falls back to the aliases): a required field is `Missing`, an `Option` field `None` and a
+///   defaulted field gets its default. Not supported on `Vec` fields or together with `typed`
+/// - `#[header("header-name", or_query = "param")]` - When the header (and its aliases) is absent,
+///   falls back to the URL-decoded `param` query parameter (the first one), parsed and validated
+///   like the header. The header always wins, and an absent query string is like an absent
+///   parameter. Undecodable and unparsable values fail with `HeaderError::Parse`. Checked after
+///   `media_param` and before `or_extension`. Not supported on `Vec`, `HeaderValue` and `Vec<u8>`
+///   fields, together with `typed` and `base64` or with `#[headers(deserialize)]`. `from_headers`
+///   has no query string to read
+/// - `#[header(skip)]` - The field isn't read from the headers but set to `Default::default()`
+///   (its type must implement `Default`), e.g. to fill it in later in the handler
+/// - Fields with `HeaderValue` or `Vec<u8>` types store the raw value without ASCII validation or
+///   parsing, for decoding it yourself
+/// - Fields with `Box<str>` or `Cow<'static, str>` types are filled with an owned copy of the value,
+///   as neither implements `FromStr`
+/// - Fields with `Vec<T>` collect every occurrence of the header, parsing each value. A required
+///   `Vec` is missing when the header doesn't occur at all, `Option<Vec<T>>` is `None` instead
 /// - Fields with `Option<T>` are considered optional headers (will not error if not found in a
 ///   handler)
 ///
+/// # Field modes
+///
+/// Every field is in one of three modes, depending on its type and `default`:
+///
+/// - Required, e.g. `user_id: String`: an absent header rejects the request with
+///   `HeaderError::Missing`, a malformed one with its parse error
+/// - Optional, e.g. `tenant: Option<String>`: absent and malformed headers are `None`
+/// - Defaulted, e.g. `#[header("x-page-size", default = "20")] page_size: u32`: an absent header
+///   yields the default, a malformed one is rejected like a required field's
+///
+/// A defaulted field holds a plain `T`, sparing `.unwrap_or(..)` in handlers, but unlike an
+/// `Option` field a malformed value still rejects the request. Defaulted fields are not listed in
+/// `REQUIRED_HEADER_NAMES`.
+///
+/// Field types (the `T` of `Option<T>` and `Vec<T>`) are parsed with `FromStr`, except raw
+/// `HeaderValue` and `Vec<u8>` fields and decoding options such as `typed` and `json`. A type
+/// without a `FromStr` impl is reported at the field type.
+///
+/// The struct gets `HEADER_NAMES` and `REQUIRED_HEADER_NAMES` associated constants listing the
+/// extracted header names.
+///
+/// It also gets a `from_headers(&HeaderMap) -> Result<Self, HeaderError>` constructor, which the
+/// extractor delegates to, for unit tests and non-axum call sites. With `#[headers(state = "...")]`
+/// it takes a reference to the state as a second argument, with `#[headers(infallible)]` it returns
+/// `Self`. There is no query string or request extensions to read `or_query` and `or_extension`
+/// fields from.
+///
+/// The derived struct can also be extracted as `Option<Self>`: it is `None` when required headers
+/// are absent, while malformed values are still rejected.
+///
+/// With the `utoipa` feature of `axum-required-headers`, the struct also implements
+/// `utoipa::IntoParams`, documenting every field except `prefix` fields as a `header` parameter.
+///
+/// Header names are resolved with the following precedence: explicit literal or `name = CONST` >
+/// `rename` > `rename_all` rule.
+///
 /// See `axum-required-headers` for examples
 ///
-#[proc_macro_derive(Headers, attributes(header))]
+#[proc_macro_derive(Headers, attributes(header, headers))]
 pub fn derive_headers(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -54,7 +289,7 @@ fn derive_header_impl(input: DeriveInput) -> syn::Result<proc_macro2::TokenStrea
     let header_attr = input
         .attrs
         .iter()
-        .find(|attr| attr.path().is_ident(ATTRIBUTE_IDENT))
+        .find(|attr| attr.path().is_ident(FIELD_ATTRIBUTE_IDENT))
         .ok_or_else(|| {
             syn::Error::new_spanned(
                 &input,
@@ -62,166 +297,319 @@ fn derive_header_impl(input: DeriveInput) -> syn::Result<proc_macro2::TokenStrea
             )
         })?;
 
-    let header_name = parse_header_attr(header_attr)?;
+    let HeaderAttr {
+        header_name,
+        aliases,
+        non_empty,
+        infallible,
+        to_header_value,
+        warn_on_normalize,
+    } = parse_header_attr(header_attr)?;
+
+    let from_str_impl = if infallible {
+        ensure_newtype(&input, "infallible")?;
+        Some(infallible_from_str(&input))
+    } else {
+        enum_from_str(&input, &header_name, warn_on_normalize)?
+    };
+    if warn_on_normalize && from_str_impl.is_none() {
+        return Err(syn::Error::new_spanned(
+            header_attr,
+            "`warn_on_normalize` is only supported on fieldless enums",
+        ));
+    }
+    let to_header_value_impl = if to_header_value {
+        ensure_newtype(&input, "to_header_value")?;
+        Some(to_header_value_method(&input, &header_name))
+    } else {
+        None
+    };
+
+    // Report a missing `FromStr` impl at the derive with a dedicated message. Generic types are
+    // only checked once instantiated.
+    let from_str_assertion = input.generics.params.is_empty().then(|| {
+        quote_spanned! {name.span()=>
+            const _: fn() = || {
+                ::axum_required_headers::__private::assert_from_str::<#name>();
+            };
+        }
+    });
 
     let expanded = quote! {
+        #from_str_impl
+
+        #to_header_value_impl
+
+        #from_str_assertion
+
         // Implement RequiredHeader
         impl #impl_generics ::axum_required_headers::RequiredHeader for #name #ty_generics #where_clause {
             const HEADER_NAME: &'static str = #header_name;
+            const HEADER_ALIASES: &'static [&'static str] = &[#(#aliases),*];
+            const EMPTY_IS_MISSING: bool = #non_empty;
         }
 
         // Implement OptionalHeader
         impl #impl_generics ::axum_required_headers::OptionalHeader for #name #ty_generics #where_clause {
             const HEADER_NAME: &'static str = #header_name;
+            const HEADER_ALIASES: &'static [&'static str] = &[#(#aliases),*];
+            const EMPTY_IS_MISSING: bool = #non_empty;
         }
     };
 
     Ok(expanded)
 }
 
-fn derive_headers_impl(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
-    let name = &input.ident;
-    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
-
-    // build `impl<S, ...>` generics
-    let s_ident = syn::Ident::new("S", name.span());
-    let mut impl_generics_with_s = input.generics.clone();
-    impl_generics_with_s.params.insert(
-        0,
-        syn::GenericParam::Type(syn::TypeParam::from(s_ident.clone())),
-    );
-    let (impl_generics_with_s, _, _) = impl_generics_with_s.split_for_impl();
-
-    // extend where-clause with `S: Send + Sync`
-    let mut where_clause_with_s = where_clause.cloned();
-    {
-        let wc = where_clause_with_s.get_or_insert_with(|| syn::WhereClause {
-            where_token: Default::default(),
-            predicates: Default::default(),
-        });
-        wc.predicates
-            .push(syn::parse_quote!(#s_ident: ::std::marker::Send + ::std::marker::Sync));
-    }
+/// The options of the `#[header(...)]` attribute on a `Header` type
+struct HeaderAttr {
+    header_name: HeaderNameExpr,
+    aliases: Vec<LitStr>,
+    non_empty: bool,
+    infallible: bool,
+    to_header_value: bool,
+    warn_on_normalize: bool,
+}
 
-    let Data::Struct(data) = &input.data else {
+/// Parses `#[header("name")]` or `#[header(name = CONST)]` on a `Header` type, along with the
+/// `alias = "other"`, `non_empty`, `infallible`, `to_header_value` and `warn_on_normalize` options
+fn parse_header_attr(attr: &syn::Attribute) -> syn::Result<HeaderAttr> {
+    let Some((_, field_attr)) = FieldAttr::from_attrs(std::slice::from_ref(attr))? else {
+        unreachable!("the attribute is a `header` attribute");
+    };
+    let FieldAttr {
+        name,
+        name_const,
+        from_type: false,
+        rename: None,
+        range: None,
+        min_len: None,
+        max_len: None,
+        pattern: None,
+        validate_with: None,
+        default: None,
+        datetime_fmt: None,
+        aliases,
+        required_if: None,
+        trim: false,
+        no_trim: false,
+        list: false,
+        skip_unknown: false,
+        base64: None,
+        gzip: false,
+        json: false,
+        uuid: false,
+        duration_suffix: false,
+        otel_attr: None,
+        trace: false,
+        media_param: None,
+        nth: None,
+        into_extension: false,
+        or_extension: false,
+        sensitive: false,
+        non_empty,
+        or_query: None,
+        infallible,
+        to_header_value,
+        warn_on_normalize,
+        prefix: None,
+        typed: false,
+        problems: false,
+        skip: false,
+    } = field_attr
+    else {
         return Err(syn::Error::new_spanned(
-            name,
-            "Headers can only be derived for structs",
+            attr,
+            "`Header` types only support a header name, `alias = \"...\"`, `non_empty`, \
+             `infallible`, `to_header_value` and `warn_on_normalize`",
         ));
     };
 
-    let Fields::Named(fields) = &data.fields else {
+    let header_name = match (name, name_const) {
+        (Some(name), _) => HeaderNameExpr::Literal(name.value()),
+        (None, Some(path)) => HeaderNameExpr::Const(path),
+        (None, None) => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`Header` types need a header name, e.g. `#[header(\"x-user-id\")]`",
+            ));
+        }
+    };
+
+    Ok(HeaderAttr {
+        header_name,
+        aliases,
+        non_empty,
+        infallible,
+        to_header_value,
+        warn_on_normalize,
+    })
+}
+
+/// Fails unless `input` is a single-field tuple struct, which `option` requires
+fn ensure_newtype(input: &DeriveInput, option: &str) -> syn::Result<()> {
+    let is_newtype = matches!(
+        &input.data,
+        Data::Struct(DataStruct { fields: Fields::Unnamed(fields), .. }) if fields.unnamed.len() == 1
+    );
+    if !is_newtype {
         return Err(syn::Error::new_spanned(
-            name,
-            "Headers only supports named fields",
+            &input.ident,
+            format!(
+                "`{option}` is only supported on newtype structs, e.g. `struct UserId(String);`"
+            ),
         ));
-    };
+    }
+    Ok(())
+}
 
-    let mut field_parsers = Vec::new();
-    let mut field_names = Vec::new();
+/// Generates `FromStr` for a newtype struct by converting the value with `From<String>`
+fn infallible_from_str(input: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    for field in &fields.named {
-        let field_name = field.ident.as_ref().unwrap();
-        let field_type = &field.ty;
-        field_names.push(field_name);
+    quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::std::convert::Infallible;
 
-        // Find #[header(...)] attribute
-        let header_attr = field
-            .attrs
-            .iter()
-            .find(|attr| attr.path().is_ident(ATTRIBUTE_IDENT))
-            .ok_or_else(|| {
-                syn::Error::new_spanned(
-                    field,
-                    "Missing #[header(\"header-name\")] attribute on field",
-                )
-            })?;
-
-        // Parse the attribute
-        let header_name = parse_header_attr(header_attr)?;
-        let is_optional = is_option_type(field_type);
-
-        if is_optional {
-            // Optional header
-            field_parsers.push(quote! {
-                let #field_name: #field_type = {
-                    parts.headers
-                        .get(#header_name)
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse().ok())
-                };
-            });
-        } else {
-            // Required header
-            field_parsers.push(quote! {
-                let #field_name: #field_type = {
-                    parts.headers
-                        .get(#header_name)
-                        .ok_or_else(|| ::axum_required_headers::HeaderError::Missing(#header_name))?
-                        .to_str()
-                        .map_err(|_| ::axum_required_headers::HeaderError::InvalidValue(#header_name))?
-                        .parse()
-                        .map_err(|_| ::axum_required_headers::HeaderError::Parse(#header_name))?
-                };
-            });
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                ::std::result::Result::Ok(Self(::std::convert::From::from(s.to_owned())))
+            }
         }
     }
+}
 
-    let field_constructions = field_names.iter().map(|name| quote! { #name });
-    let axum_crate = get_crate("axum")?;
-    let http_crate = get_crate("http")?;
+/// Generates `FromStr` for a fieldless enum, matching each variant by name or by its
+/// `#[header_value("...")]`, case-insensitively. With `warn_on_normalize`, matches differing in
+/// case are logged.
+///
+/// Returns `None` for other types, which have to implement `FromStr` themselves.
+fn enum_from_str(
+    input: &DeriveInput,
+    header_name: &HeaderNameExpr,
+    warn_on_normalize: bool,
+) -> syn::Result<Option<proc_macro2::TokenStream>> {
+    let Data::Enum(data) = &input.data else {
+        return Ok(None);
+    };
+    if data.variants.is_empty() || !data.variants.iter().all(|v| v.fields.is_empty()) {
+        if let Some(attr) = data.variants.iter().find_map(variant_value_attr) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "`header_value` is only supported on enums whose variants all have no fields",
+            ));
+        }
+        return Ok(None);
+    }
 
-    let expanded = quote! {
-        impl #impl_generics_with_s ::#axum_crate::extract::FromRequestParts<#s_ident>
-            for #name #ty_generics
-            #where_clause_with_s
+    let mut idents = Vec::new();
+    let mut values = Vec::new();
+    for variant in &data.variants {
+        let value = match variant_value_attr(variant) {
+            Some(attr) => attr.parse_args::<LitStr>()?.value(),
+            None => variant.ident.to_string(),
+        };
+        if let Some(duplicate) = values
+            .iter()
+            .position(|other: &String| other.eq_ignore_ascii_case(&value))
         {
-            type Rejection = ::axum_required_headers::HeaderError;
-
-            async fn from_request_parts(
-                parts: &mut ::#http_crate::request::Parts,
-                _state: &#s_ident,
-            ) -> ::std::result::Result<Self, Self::Rejection> {
-                #(#field_parsers)*
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!(
+                    "`{value}` already matches the `{}` variant",
+                    idents[duplicate]
+                ),
+            ));
+        }
+        idents.push(&variant.ident);
+        values.push(value);
+    }
 
-                Ok(Self {
-                    #(#field_constructions),*
-                })
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let warn = values.iter().map(|value| {
+        warn_on_normalize.then(|| {
+            quote! {
+                if s != #value {
+                    ::axum_required_headers::__private::warn_normalized_value(#header_name, s, #value);
+                }
+            }
+        })
+    });
+
+    Ok(Some(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #ty_generics #where_clause {
+            type Err = ::axum_required_headers::ParseEnumError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                #(
+                    if s.eq_ignore_ascii_case(#values) {
+                        #warn
+                        return ::std::result::Result::Ok(Self::#idents);
+                    }
+                )*
+                ::std::result::Result::Err(::axum_required_headers::ParseEnumError::new(
+                    s,
+                    &[#(#values),*],
+                ))
             }
         }
-    };
-
-    Ok(expanded)
+    }))
 }
 
-fn parse_header_attr(attr: &syn::Attribute) -> syn::Result<String> {
-    let lit: LitStr = attr.parse_args()?;
-    let header_name = lit.value();
+fn variant_value_attr(variant: &syn::Variant) -> Option<&syn::Attribute> {
+    variant
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(VARIANT_VALUE_ATTRIBUTE_IDENT))
+}
 
-    if header_name.is_empty() {
-        return Err(syn::Error::new_spanned(attr, "header name cannot be empty"));
-    }
+/// Generates `to_header_value` for a newtype struct, formatting the field with `Display`, and an
+/// `IntoResponseParts` impl writing the header with it.
+fn to_header_value_method(
+    input: &DeriveInput,
+    header_name: &HeaderNameExpr,
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    Ok(header_name)
-}
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Formats the value for an outbound header, the inverse of its `FromStr` impl.
+            ///
+            /// Fails if the formatted value contains characters not allowed in a header value.
+            pub fn to_header_value(
+                &self,
+            ) -> ::std::result::Result<
+                ::axum_required_headers::http::HeaderValue,
+                ::axum_required_headers::http::header::InvalidHeaderValue,
+            > {
+                ::axum_required_headers::http::HeaderValue::try_from(
+                    ::std::string::ToString::to_string(&self.0),
+                )
+            }
+        }
 
-/// Helper function to detect if a type is `Option<T>` or `std::option::Option<T>`
-fn is_option_type(ty: &syn::Type) -> bool {
-    match ty {
-        syn::Type::Path(type_path) => {
-            // Check if the last segment is "Option"
-            if let Some(last_segment) = type_path.path.segments.last()
-                && last_segment.ident == "Option"
-            {
-                return true;
+        impl #impl_generics ::axum_required_headers::axum::response::IntoResponseParts
+            for #name #ty_generics
+            #where_clause
+        {
+            type Error = ::axum_required_headers::HeaderRejection;
+
+            fn into_response_parts(
+                self,
+                res: ::axum_required_headers::axum::response::ResponseParts,
+            ) -> ::std::result::Result<::axum_required_headers::axum::response::ResponseParts, Self::Error> {
+                let value = self
+                    .to_header_value()
+                    .map_err(|_| ::axum_required_headers::HeaderError::InvalidValue(#header_name));
+                ::axum_required_headers::__private::write_header(res, #header_name, value)
+                    .map_err(::axum_required_headers::__private::response_header_rejection)
             }
-            false
         }
-        _ => false,
     }
 }
 
-fn get_crate(crate_name: &str) -> syn::Result<proc_macro2::TokenStream> {
+pub(crate) fn get_crate(crate_name: &str) -> syn::Result<proc_macro2::TokenStream> {
     let Ok(found_crate) = proc_macro_crate::crate_name(crate_name) else {
         return Err(syn::Error::new(
             Span::call_site(),
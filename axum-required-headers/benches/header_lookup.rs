@@ -0,0 +1,33 @@
+//! Compares looking up a header by string with looking it up by a precomputed `HeaderName`,
+//! which is what the derive generates for valid lowercase header names.
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+const USER_ID: HeaderName = HeaderName::from_static("x-user-id");
+
+fn request_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("accept", HeaderValue::from_static("application/json"));
+    headers.insert("user-agent", HeaderValue::from_static("bench/1.0"));
+    headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+    headers.insert("x-user-id", HeaderValue::from_static("user123"));
+    headers
+}
+
+fn header_lookup(c: &mut Criterion) {
+    let headers = request_headers();
+    let mut group = c.benchmark_group("header_lookup");
+
+    group.bench_function("str", |b| {
+        b.iter(|| black_box(&headers).get(black_box("x-user-id")).is_some())
+    });
+    group.bench_function("header_name_constant", |b| {
+        b.iter(|| black_box(&headers).get(black_box(&USER_ID)).is_some())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, header_lookup);
+criterion_main!(benches);
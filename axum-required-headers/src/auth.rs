@@ -0,0 +1,170 @@
+//! Parsing of the `Authorization` header into the credential of one of several schemes.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{OptionalHeader, RequiredHeader};
+
+/// The `Authorization` header of a request accepting several authentication schemes.
+///
+/// The scheme token is matched case-insensitively and selects how the credentials are parsed:
+/// `Bearer <token>`, `Basic <base64 of user:password>` or `ApiKey <key>`. Other schemes, and
+/// credentials that don't fit their scheme, fail to parse and reject the request. Basic
+/// credentials must be canonical, padded base64 in the standard alphabet, and are decoded without
+/// the `base64` feature.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{AnyAuth, Credential, Required};
+///
+/// async fn handler(Required(AnyAuth(credential)): Required<AnyAuth>) -> String {
+///     match credential {
+///         Credential::Bearer(token) => format!("token of {} bytes", token.len()),
+///         Credential::Basic { username, .. } => format!("user {username}"),
+///         Credential::ApiKey(_) => "api key".to_owned(),
+///     }
+/// }
+///
+/// let AnyAuth(credential) = "Basic dXNlcjpwYXNz".parse().unwrap();
+/// assert_eq!(
+///     credential,
+///     Credential::Basic {
+///         username: "user".to_owned(),
+///         password: "pass".to_owned(),
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnyAuth(pub Credential);
+
+/// The credentials of an `Authorization` header, by scheme.
+///
+/// `Debug` redacts the secrets, so credentials can be logged safely.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// `Bearer <token>`
+    Bearer(String),
+    /// `Basic <credentials>`, the base64 of `username:password`
+    Basic {
+        /// The user name, everything before the first `:`
+        username: String,
+        /// The password, possibly empty
+        password: String,
+    },
+    /// `ApiKey <key>`
+    ApiKey(String),
+}
+
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bearer(_) => f.write_str("Bearer(<redacted>)"),
+            Self::Basic { username, .. } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &"<redacted>")
+                .finish(),
+            Self::ApiKey(_) => f.write_str("ApiKey(<redacted>)"),
+        }
+    }
+}
+
+/// Error returned when an `Authorization` header can't be parsed.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid authorization: {0}")]
+pub struct ParseAuthError(&'static str);
+
+impl FromStr for AnyAuth {
+    type Err = ParseAuthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (scheme, credentials) = s
+            .trim()
+            .split_once(' ')
+            .ok_or(ParseAuthError("expected a scheme followed by credentials"))?;
+        let credentials = credentials.trim_start();
+        if !is_token68(credentials) {
+            return Err(ParseAuthError("malformed credentials"));
+        }
+
+        let credential = if scheme.eq_ignore_ascii_case("bearer") {
+            Credential::Bearer(credentials.to_owned())
+        } else if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = decode_base64(credentials)
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .ok_or(ParseAuthError(
+                    "basic credentials must be base64 encoded UTF-8",
+                ))?;
+            let (username, password) = decoded.split_once(':').ok_or(ParseAuthError(
+                "basic credentials must be `username:password`",
+            ))?;
+            Credential::Basic {
+                username: username.to_owned(),
+                password: password.to_owned(),
+            }
+        } else if scheme.eq_ignore_ascii_case("apikey") {
+            Credential::ApiKey(credentials.to_owned())
+        } else {
+            return Err(ParseAuthError("unsupported scheme"));
+        };
+        Ok(Self(credential))
+    }
+}
+
+impl RequiredHeader for AnyAuth {
+    const HEADER_NAME: &'static str = "authorization";
+}
+
+impl OptionalHeader for AnyAuth {
+    const HEADER_NAME: &'static str = "authorization";
+}
+
+/// The `token68` grammar of RFC 7235: `1*( ALPHA / DIGIT / "-" / "." / "_" / "~" / "+" / "/" )
+/// *"="`
+fn is_token68(value: &str) -> bool {
+    let data = value.trim_end_matches('=');
+    !data.is_empty()
+        && data
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-._~+/".contains(&b))
+}
+
+/// Decodes standard, padded base64, rejecting `=` before the end and non-zero trailing bits like
+/// the `base64` crate does. Basic credentials are short, so they're decoded here rather than
+/// making `AnyAuth` depend on the `base64` feature
+fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    fn sextet(b: u8) -> Option<u32> {
+        Some(match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        } as u32)
+    }
+
+    if !value.len().is_multiple_of(4) {
+        return None;
+    }
+    let data = value.trim_end_matches('=');
+    if value.len() - data.len() > 2 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.as_bytes().chunks(4) {
+        let mut group = 0;
+        for (i, &b) in chunk.iter().enumerate() {
+            group |= sextet(b)? << (18 - 6 * i);
+        }
+        let decoded = group.to_be_bytes();
+        // 2, 3 and 4 sextets carry 1, 2 and 3 bytes, the bits left over must be zero
+        if decoded[chunk.len()..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        bytes.extend_from_slice(&decoded[1..chunk.len()]);
+    }
+    Some(bytes)
+}
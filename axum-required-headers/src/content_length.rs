@@ -0,0 +1,76 @@
+//! Verification of the `Content-Length` header against the received body.
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    response::{IntoResponse, Response},
+};
+use http::header::CONTENT_LENGTH;
+
+use crate::HeaderError;
+
+/// Extractor buffering the request body and checking that its length matches the
+/// `Content-Length` header.
+///
+/// A disagreement between the two is a request smuggling indicator (or a broken proxy), and is
+/// rejected with [`HeaderError::ContentLengthMismatch`]. A missing `Content-Length` header is
+/// rejected with [`HeaderError::Missing`], one that isn't a number with [`HeaderError::Parse`].
+/// Errors reading the body are rejected like axum's `Bytes` extractor does.
+///
+/// The whole body is buffered in memory before the handler runs, so this extractor consumes the
+/// body (it has to be the last handler argument) and is subject to axum's `DefaultBodyLimit`.
+/// Avoid it on routes streaming large uploads.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::VerifiedContentLength;
+///
+/// async fn upload(body: VerifiedContentLength) -> String {
+///     format!("received {} bytes", body.length)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VerifiedContentLength {
+    /// The length declared by `Content-Length`, equal to the length of `body`
+    pub length: u64,
+    /// The buffered request body
+    pub body: Bytes,
+}
+
+impl<S> FromRequest<S> for VerifiedContentLength
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let declared = req
+            .headers()
+            .get(CONTENT_LENGTH)
+            .ok_or(HeaderError::Missing("content-length"))
+            .and_then(|value| {
+                value
+                    .to_str()
+                    .map_err(|_| HeaderError::InvalidValue("content-length"))?
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| HeaderError::Parse("content-length"))
+            })
+            .map_err(IntoResponse::into_response)?;
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let actual = body.len() as u64;
+        if actual != declared {
+            return Err(HeaderError::ContentLengthMismatch { declared, actual }.into_response());
+        }
+
+        Ok(VerifiedContentLength {
+            length: declared,
+            body,
+        })
+    }
+}
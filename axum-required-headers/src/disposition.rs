@@ -0,0 +1,138 @@
+//! Parsing of the `Content-Disposition` header (RFC 6266) and its filename parameters.
+
+use std::str::FromStr;
+
+use crate::{
+    OptionalHeader, RequiredHeader,
+    syntax::{Cursor, percent_decode},
+};
+
+/// The `Content-Disposition` header of a request, e.g. of an upload.
+///
+/// The filename is taken from the RFC 5987 `filename*` parameter (percent-encoded UTF-8 or
+/// ISO-8859-1) when present and decodable, and from the plain `filename` parameter otherwise.
+/// Quoted filenames are unescaped; no path sanitization is done, so treat the filename as
+/// untrusted input.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{ContentDisposition, Optional};
+///
+/// async fn upload(Optional(disposition): Optional<ContentDisposition>) -> String {
+///     match disposition.and_then(|disposition| disposition.filename) {
+///         Some(filename) => format!("storing {filename}"),
+///         None => "storing unnamed upload".to_owned(),
+///     }
+/// }
+///
+/// let disposition: ContentDisposition = "attachment; filename*=UTF-8''%E2%82%AC%20rates.txt"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(disposition.disposition, "attachment");
+/// assert_eq!(disposition.filename.as_deref(), Some("€ rates.txt"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition {
+    /// The lowercase disposition type, e.g. `attachment`, `inline` or `form-data`
+    pub disposition: String,
+    /// The decoded filename, if given
+    pub filename: Option<String>,
+}
+
+/// Error returned when a `Content-Disposition` header can't be parsed.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid content disposition: {0}")]
+pub struct ParseContentDispositionError(String);
+
+impl FromStr for ContentDisposition {
+    type Err = ParseContentDispositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Cursor::new(s);
+        parser.skip_whitespace();
+        let disposition = parser.token().to_ascii_lowercase();
+        if disposition.is_empty() {
+            return Err(ParseContentDispositionError(
+                parser.error("expected a disposition type"),
+            ));
+        }
+
+        let mut filename = None;
+        let mut filename_ext = None;
+        loop {
+            parser.skip_whitespace();
+            if parser.is_done() {
+                break;
+            }
+            if !parser.eat(';') {
+                return Err(ParseContentDispositionError(
+                    parser.error("expected `;` between parameters"),
+                ));
+            }
+            parser.skip_whitespace();
+            // Trailing semicolons are tolerated
+            if parser.is_done() {
+                break;
+            }
+            let name = parser.token().to_ascii_lowercase();
+            if name.is_empty() {
+                return Err(ParseContentDispositionError(
+                    parser.error("expected a parameter name"),
+                ));
+            }
+            parser.skip_whitespace();
+            if !parser.eat('=') {
+                return Err(ParseContentDispositionError(
+                    parser.error("expected `=` after the parameter name"),
+                ));
+            }
+            parser.skip_whitespace();
+            let value = parser.param_value().map_err(ParseContentDispositionError)?;
+            // Only the first occurrence of each parameter counts
+            match name.as_str() {
+                "filename" => {
+                    filename.get_or_insert(value);
+                }
+                "filename*" => {
+                    filename_ext.get_or_insert(value);
+                }
+                _ => {}
+            }
+        }
+
+        // `filename*` can't be decoded: fall back to `filename`, as RFC 6266 recommends
+        let filename = filename_ext
+            .as_deref()
+            .and_then(decode_ext_value)
+            .or(filename);
+        Ok(Self {
+            disposition,
+            filename,
+        })
+    }
+}
+
+impl RequiredHeader for ContentDisposition {
+    const HEADER_NAME: &'static str = "content-disposition";
+}
+
+impl OptionalHeader for ContentDisposition {
+    const HEADER_NAME: &'static str = "content-disposition";
+}
+
+/// Decodes an RFC 5987 `ext-value`: `charset'[language]'percent-encoded`, in UTF-8 or
+/// ISO-8859-1
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let (charset, _language, encoded) = (parts.next()?, parts.next()?, parts.next()?);
+    let bytes = percent_decode(encoded)?;
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        // ISO-8859-1 bytes are the first 256 code points
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
@@ -1,11 +1,21 @@
+#[cfg(feature = "json")]
+use axum::{Json, http::header::CONTENT_TYPE};
 use axum::{
-    Json,
-    http::StatusCode,
+    http::{HeaderName, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
 };
+#[cfg(feature = "json")]
 use serde_json::json;
+use std::cmp::Ordering;
 
-#[derive(Debug, thiserror::Error)]
+use crate::HeaderRejection;
+
+/// An error extracting a header.
+///
+/// Errors are ordered by kind (in declaration order: missing, invalid value, parse, decode, too
+/// long, validation, incomplete group, content length mismatch, multiple), then by header name, so
+/// aggregated errors can be sorted deterministically.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
 pub enum HeaderError {
     #[error("Missing required header: `{0}`")]
     Missing(&'static str),
@@ -13,21 +23,340 @@ pub enum HeaderError {
     InvalidValue(&'static str),
     #[error("Failed to parse header value: `{0}`")]
     Parse(&'static str),
+    /// A stage of a `#[header(base64, gzip, json)]` decode pipeline failed, reported with the
+    /// `header_parse_error` code like `Parse`
+    #[error("Failed to parse header value (`{stage}` stage): `{name}`")]
+    Decode {
+        name: &'static str,
+        stage: &'static str,
+    },
+    #[error("Header value too long (maximum {max} bytes): `{name}`")]
+    TooLong { name: &'static str, max: usize },
+    #[error("Invalid header value for `{name}`: {reason}")]
+    Validation { name: &'static str, reason: String },
+    /// Some but not all headers of a `#[headers(all_or_none(...))]` group are present, `name`
+    /// being the first missing one
+    #[error("Incomplete header group ({}): missing `{name}`", .group.join(", "))]
+    IncompleteGroup {
+        name: &'static str,
+        group: &'static [&'static str],
+    },
+    /// The body length differs from the `Content-Length` header, see `VerifiedContentLength`
+    #[error("Body length ({actual} bytes) does not match `content-length` ({declared} bytes)")]
+    ContentLengthMismatch { declared: u64, actual: u64 },
+    /// Several problems, collected by `#[derive(Headers)]` with `#[headers(collect_errors)]`
+    #[error("Multiple header errors: {}", join_errors(.0))]
+    Multiple(Vec<HeaderError>),
 }
 
-impl IntoResponse for HeaderError {
-    fn into_response(self) -> Response {
+fn join_errors(errors: &[HeaderError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl HeaderError {
+    pub(crate) fn name(&self) -> &'static str {
+        use HeaderError::*;
+        match self {
+            Missing(name)
+            | InvalidValue(name)
+            | Parse(name)
+            | Decode { name, .. }
+            | TooLong { name, .. }
+            | Validation { name, .. }
+            | IncompleteGroup { name, .. } => name,
+            ContentLengthMismatch { .. } => "content-length",
+            Multiple(errors) => errors.first().map_or("", HeaderError::name),
+        }
+    }
+
+    /// The error for the missing headers `names`: `None` when there are none, [`Missing`] for a
+    /// single one and [`Multiple`] with one [`Missing`] per name otherwise, like
+    /// `#[headers(collect_errors)]` reports them.
+    ///
+    /// ```
+    /// use axum_required_headers::HeaderError;
+    /// use axum_required_headers::http::HeaderMap;
+    ///
+    /// let headers = HeaderMap::new();
+    /// let missing: Vec<&'static str> = ["x-user-id", "x-tenant-id"]
+    ///     .into_iter()
+    ///     .filter(|name| !headers.contains_key(*name))
+    ///     .collect();
+    ///
+    /// let error = HeaderError::missing_all(&missing).unwrap();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Multiple header errors: Missing required header: `x-user-id`; \
+    ///      Missing required header: `x-tenant-id`"
+    /// );
+    /// assert_eq!(HeaderError::missing_all(&[]), None);
+    /// ```
+    ///
+    /// [`Missing`]: HeaderError::Missing
+    /// [`Multiple`]: HeaderError::Multiple
+    pub fn missing_all(names: &[&'static str]) -> Option<Self> {
+        match names {
+            [] => None,
+            [name] => Some(HeaderError::Missing(name)),
+            names => Some(HeaderError::Multiple(
+                names.iter().copied().map(HeaderError::Missing).collect(),
+            )),
+        }
+    }
+
+    /// The name of the header that failed, `None` for [`HeaderError::Multiple`], which may
+    /// concern several headers.
+    ///
+    /// [`HeaderError::ContentLengthMismatch`] concerns `content-length`, and
+    /// [`HeaderError::IncompleteGroup`] reports its first missing header.
+    pub fn header_name(&self) -> Option<&'static str> {
+        match self {
+            HeaderError::Multiple(_) => None,
+            error => Some(error.name()),
+        }
+    }
+
+    /// Whether the error is (or contains) a missing header
+    pub(crate) fn is_missing(&self) -> bool {
+        match self {
+            HeaderError::Missing(_) => true,
+            HeaderError::Multiple(errors) => errors.iter().any(HeaderError::is_missing),
+            _ => false,
+        }
+    }
+
+    /// The machine readable error code used in response bodies, e.g. `missing_header`
+    #[cfg(feature = "json")]
+    pub(crate) fn code(&self) -> &'static str {
         use HeaderError::*;
-        let error = match self {
+        match self {
             Missing(_) => "missing_header",
             InvalidValue(_) => "invalid_header_value",
-            Parse(_) => "header_parse_error",
-        };
-        let body = Json(json!({
-            "error": error,
+            Parse(_) | Decode { .. } => "header_parse_error",
+            TooLong { .. } => "header_too_long",
+            Validation { .. } => "header_validation_error",
+            IncompleteGroup { .. } => "incomplete_header_group",
+            ContentLengthMismatch { .. } => "content_length_mismatch",
+            Multiple(_) => "multiple_header_errors",
+        }
+    }
+
+    /// Wraps the error in a [`HeaderRejection`] responding with `status` instead of
+    /// `400 Bad Request`.
+    ///
+    /// ```
+    /// use axum_required_headers::HeaderError;
+    /// use axum_required_headers::http::StatusCode;
+    ///
+    /// let rejection = HeaderError::Missing("authorization").with_status(StatusCode::UNAUTHORIZED);
+    /// assert_eq!(rejection.status(), StatusCode::UNAUTHORIZED);
+    /// ```
+    pub fn with_status(self, status: StatusCode) -> HeaderRejection {
+        HeaderRejection::new(self).with_status(status)
+    }
+
+    /// Wraps the error in a [`HeaderRejection`] adding a header to the response, see
+    /// [`HeaderRejection::with_header`].
+    pub fn with_header(self, name: HeaderName, value: HeaderValue) -> HeaderRejection {
+        HeaderRejection::new(self).with_header(name, value)
+    }
+
+    /// The position of the error kind in the sort order
+    fn kind_rank(&self) -> u8 {
+        use HeaderError::*;
+        match self {
+            Missing(_) => 0,
+            InvalidValue(_) => 1,
+            Parse(_) => 2,
+            Decode { .. } => 3,
+            TooLong { .. } => 4,
+            Validation { .. } => 5,
+            IncompleteGroup { .. } => 6,
+            ContentLengthMismatch { .. } => 7,
+            Multiple(_) => 8,
+        }
+    }
+
+    #[cfg(feature = "json")]
+    pub(crate) fn json_body(&self) -> serde_json::Value {
+        let mut body = json!({
+            "error": self.code(),
             "message": format!("{self}"),
-        }));
+        });
+        if let HeaderError::Multiple(errors) = self {
+            body["errors"] = errors.iter().map(HeaderError::json_body).collect();
+        }
+        body
+    }
+}
+
+impl PartialOrd for HeaderError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeaderError {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use HeaderError::*;
+        let details = match (self, other) {
+            (Decode { stage: a, .. }, Decode { stage: b, .. }) => a.cmp(b),
+            (TooLong { max: a, .. }, TooLong { max: b, .. }) => a.cmp(b),
+            (Validation { reason: a, .. }, Validation { reason: b, .. }) => a.cmp(b),
+            (IncompleteGroup { group: a, .. }, IncompleteGroup { group: b, .. }) => a.cmp(b),
+            (
+                ContentLengthMismatch {
+                    declared: a,
+                    actual: x,
+                },
+                ContentLengthMismatch {
+                    declared: b,
+                    actual: y,
+                },
+            ) => a.cmp(b).then(x.cmp(y)),
+            (Multiple(a), Multiple(b)) => a.cmp(b),
+            _ => Ordering::Equal,
+        };
+        self.kind_rank()
+            .cmp(&other.kind_rank())
+            .then_with(|| self.name().cmp(other.name()))
+            .then(details)
+    }
+}
+
+/// Error returned by the `FromStr` impl that `#[derive(Header)]` generates for fieldless enums
+/// when the value matches none of the variants.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown value `{value}`, expected one of: {}", .expected.join(", "))]
+pub struct ParseEnumError {
+    value: String,
+    expected: &'static [&'static str],
+}
+
+impl ParseEnumError {
+    /// Creates an error for `value`, which matched none of the `expected` values.
+    #[doc(hidden)]
+    pub fn new(value: &str, expected: &'static [&'static str]) -> Self {
+        Self {
+            value: value.to_owned(),
+            expected,
+        }
+    }
+
+    /// The value that matched no variant.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The values the variants are matched against.
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+}
+
+/// A header problem tolerated by lenient extraction.
+///
+/// Returned by the `from_request_parts_lenient` function generated by
+/// `#[derive(Headers)]` with `#[headers(lenient)]`.
+#[derive(Debug)]
+pub struct HeaderWarning {
+    /// The name of the offending header
+    pub name: &'static str,
+    /// The error that would have rejected the request
+    pub error: HeaderError,
+}
+
+impl HeaderWarning {
+    /// Creates a warning from the tolerated error.
+    pub fn new(error: HeaderError) -> Self {
+        Self {
+            name: error.name(),
+            error,
+        }
+    }
+}
+
+/// Responds with `400 Bad Request` and a `{"error": ..., "message": ...}` JSON body, or a
+/// plain text body with the error message without the `json` feature.
+impl IntoResponse for HeaderError {
+    fn into_response(self) -> Response {
+        #[cfg(feature = "json")]
+        let body = Json(self.json_body()).into_response();
+        #[cfg(not(feature = "json"))]
+        let body = self.to_string().into_response();
 
         (StatusCode::BAD_REQUEST, body).into_response()
     }
 }
+
+/// Renders a [`HeaderError`] as an RFC 7807 `application/problem+json` response.
+///
+/// The body has the standard `type` (`about:blank`), `title`, `status` and `detail` members, plus
+/// a `code` extension member with the machine readable error code (and `errors` with the nested
+/// problems of [`HeaderError::Multiple`]). Plain `HeaderError` responses are unaffected.
+///
+/// Requires the `json` feature.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{HeaderError, ProblemJson, Required, RequiredHeader};
+///
+/// # struct UserId(String);
+/// # impl std::str::FromStr for UserId {
+/// #     type Err = std::convert::Infallible;
+/// #     fn from_str(s: &str) -> Result<Self, Self::Err> { Ok(UserId(s.to_owned())) }
+/// # }
+/// # impl RequiredHeader for UserId { const HEADER_NAME: &'static str = "x-user-id"; }
+/// async fn handler(
+///     user_id: Result<Required<UserId>, HeaderError>,
+/// ) -> Result<String, ProblemJson> {
+///     let Required(user_id) = user_id?;
+///     Ok(user_id.0)
+/// }
+/// ```
+#[cfg(feature = "json")]
+#[derive(Debug, PartialEq, Eq)]
+pub struct ProblemJson(pub HeaderError);
+
+#[cfg(feature = "json")]
+impl From<HeaderError> for ProblemJson {
+    fn from(error: HeaderError) -> Self {
+        ProblemJson(error)
+    }
+}
+
+#[cfg(feature = "json")]
+impl HeaderError {
+    fn problem_body(&self, status: StatusCode) -> serde_json::Value {
+        let mut body = json!({
+            "type": "about:blank",
+            "title": status.canonical_reason().unwrap_or_default(),
+            "status": status.as_u16(),
+            "detail": self.to_string(),
+            "code": self.code(),
+        });
+        if let HeaderError::Multiple(errors) = self {
+            body["errors"] = errors
+                .iter()
+                .map(|error| json!({ "code": error.code(), "detail": error.to_string() }))
+                .collect();
+        }
+        body
+    }
+}
+
+#[cfg(feature = "json")]
+impl IntoResponse for ProblemJson {
+    fn into_response(self) -> Response {
+        let status = StatusCode::BAD_REQUEST;
+        let body = serde_json::to_vec(&self.0.problem_body(status))
+            .expect("serializing a `serde_json::Value` can't fail");
+
+        (status, [(CONTENT_TYPE, "application/problem+json")], body).into_response()
+    }
+}
@@ -4,11 +4,14 @@
 //! what the derive macro provides, you can use these wrapper types
 //! and traits to avoid orphan rule violations.
 
-use axum::extract::FromRequestParts;
-use http::request::Parts;
+use axum::extract::{FromRequestParts, OptionalFromRequestParts};
+use axum::response::{IntoResponseParts, ResponseParts};
+use http::{HeaderMap, HeaderName, HeaderValue, request::Parts};
+use std::convert::Infallible;
+use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 
-use crate::HeaderError;
+use crate::{HeaderError, HeaderRejection};
 
 /// Trait for headers that can be parsed from a string using `FromStr`.
 ///
@@ -16,6 +19,22 @@ use crate::HeaderError;
 /// `FromRequestParts` support via the `Required<T>` wrapper.
 pub trait RequiredHeader: std::str::FromStr + Send {
     const HEADER_NAME: &'static str;
+
+    /// Fallback header names, tried in order when `HEADER_NAME` is absent.
+    const HEADER_ALIASES: &'static [&'static str] = &[];
+
+    /// Whether an empty value counts as an absent header, e.g. `x-org-id: `.
+    ///
+    /// `false` by default, empty values being parsed like any other.
+    const EMPTY_IS_MISSING: bool = false;
+
+    /// Validates the raw header value before it is parsed.
+    ///
+    /// The default implementation accepts every value.
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        let _ = value;
+        Ok(())
+    }
 }
 
 /// Trait for optional headers that can be parsed from a string.
@@ -24,6 +43,75 @@ pub trait RequiredHeader: std::str::FromStr + Send {
 /// `FromRequestParts` support via the `Optional<T>` wrapper.
 pub trait OptionalHeader: std::str::FromStr + Send {
     const HEADER_NAME: &'static str;
+
+    /// Fallback header names, tried in order when `HEADER_NAME` is absent.
+    const HEADER_ALIASES: &'static [&'static str] = &[];
+
+    /// Whether an empty value counts as an absent header, e.g. `x-org-id: `.
+    ///
+    /// `false` by default, empty values being parsed like any other.
+    const EMPTY_IS_MISSING: bool = false;
+
+    /// Validates the raw header value before it is parsed.
+    ///
+    /// The default implementation accepts every value.
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        let _ = value;
+        Ok(())
+    }
+}
+
+/// Implements [`RequiredHeader`] and [`OptionalHeader`] for a `FromStr` type, without the
+/// `Header` derive.
+///
+/// `impl_header!(Type, "header-name")` expands to both trait impls, so the type works with
+/// `Required<T>` and `Optional<T>`. The name can be any `&'static str` constant expression,
+/// e.g. a `const`. Optional `aliases = [...]` set `HEADER_ALIASES`.
+///
+/// The expansion is an ordinary trait impl in the calling crate, so the orphan rule still
+/// applies: the type must be defined in your crate. To extract a type from a third-party crate,
+/// wrap it in a local newtype implementing `FromStr` and invoke the macro on the newtype.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Optional, Required, impl_header};
+/// use std::net::IpAddr;
+///
+/// // `IpAddr` is foreign, so it gets a local newtype
+/// struct ClientIp(IpAddr);
+///
+/// impl std::str::FromStr for ClientIp {
+///     type Err = std::net::AddrParseError;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         s.parse().map(ClientIp)
+///     }
+/// }
+///
+/// impl_header!(ClientIp, "x-client-ip", aliases = ["x-real-ip"]);
+///
+/// async fn handler(Required(ClientIp(ip)): Required<ClientIp>) -> String {
+///     format!("client: {ip}")
+/// }
+///
+/// async fn optional_handler(Optional(ip): Optional<ClientIp>) -> String {
+///     format!("client: {:?}", ip.map(|ClientIp(ip)| ip))
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_header {
+    ($ty:ty, $name:expr $(, aliases = [$($alias:expr),* $(,)?])? $(,)?) => {
+        impl $crate::RequiredHeader for $ty {
+            const HEADER_NAME: &'static str = $name;
+            const HEADER_ALIASES: &'static [&'static str] = &[$($($alias),*)?];
+        }
+
+        impl $crate::OptionalHeader for $ty {
+            const HEADER_NAME: &'static str = $name;
+            const HEADER_ALIASES: &'static [&'static str] = &[$($($alias),*)?];
+        }
+    };
 }
 
 /// Wrapper type for required headers implementing `RequiredHeader`.
@@ -53,9 +141,19 @@ pub trait OptionalHeader: std::str::FromStr + Send {
 ///     println!("User: {}", user_id.0);
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Required<T>(pub T);
 
+impl<T> Required<T> {
+    /// Returns the wrapped value.
+    ///
+    /// This is the conversion to `T`: `impl<T> From<Required<T>> for T` is ruled out by the
+    /// orphan rule, as `T` would be an uncovered type parameter.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
 impl<T> Deref for Required<T> {
     type Target = T;
 
@@ -70,11 +168,32 @@ impl<T> DerefMut for Required<T> {
     }
 }
 
+/// Compares the wrapped value, e.g. `assert_eq!(user_id, UserId("u1".into()))` in tests.
+impl<T: PartialEq> PartialEq<T> for Required<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<T: RequiredHeader + std::fmt::Display> Required<T> {
+    /// Formats the value with `Display` for re-emitting it, e.g. on a response or an outgoing
+    /// request.
+    ///
+    /// Fails with [`HeaderError::InvalidValue`] naming `T::HEADER_NAME` if the formatted value
+    /// contains characters not allowed in a header value.
+    pub fn to_header_value(&self) -> Result<HeaderValue, HeaderError> {
+        display_header_value(&self.0, T::HEADER_NAME)
+    }
+}
+
 /// Wrapper type for optional headers implementing `OptionalHeader`.
 ///
 /// This wrapper allows you to use `OptionalHeader` types directly in
 /// Axum handlers without manual `FromRequestParts` implementation.
 ///
+/// Only an absent header yields `None`: a header that is present but not valid ASCII or fails
+/// to parse still rejects the request. Use [`Lenient<T>`] to treat malformed values as absent.
+///
 /// # Examples
 ///
 /// ```
@@ -100,9 +219,22 @@ impl<T> DerefMut for Required<T> {
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Optional<T>(pub Option<T>);
 
+impl<T> Optional<T> {
+    /// Returns the wrapped option, `None` if the header was absent.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> From<Optional<T>> for Option<T> {
+    fn from(optional: Optional<T>) -> Self {
+        optional.0
+    }
+}
+
 impl<T> Deref for Optional<T> {
     type Target = Option<T>;
 
@@ -117,6 +249,179 @@ impl<T> DerefMut for Optional<T> {
     }
 }
 
+/// Compares the wrapped option, e.g. `assert_eq!(tenant_id, Some(TenantId("t1".into())))` in
+/// tests.
+impl<T: PartialEq> PartialEq<Option<T>> for Optional<T> {
+    fn eq(&self, other: &Option<T>) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<T: OptionalHeader + std::fmt::Display> Optional<T> {
+    /// Formats the value with `Display` for re-emitting it, `None` if the header was absent.
+    ///
+    /// Fails with [`HeaderError::InvalidValue`] naming `T::HEADER_NAME` if the formatted value
+    /// contains characters not allowed in a header value.
+    pub fn to_header_value(&self) -> Option<Result<HeaderValue, HeaderError>> {
+        self.0
+            .as_ref()
+            .map(|value| display_header_value(value, T::HEADER_NAME))
+    }
+}
+
+fn display_header_value(
+    value: &impl std::fmt::Display,
+    name: &'static str,
+) -> Result<HeaderValue, HeaderError> {
+    HeaderValue::try_from(value.to_string()).map_err(|_| HeaderError::InvalidValue(name))
+}
+
+/// Sets the response header `name` to `value`, replacing previous values.
+#[doc(hidden)]
+pub fn write_header(
+    mut res: ResponseParts,
+    name: &'static str,
+    value: Result<HeaderValue, HeaderError>,
+) -> Result<ResponseParts, HeaderError> {
+    let header_name = HeaderName::try_from(name).map_err(|_| HeaderError::Validation {
+        name,
+        reason: "not a valid header name".to_owned(),
+    })?;
+    res.headers_mut().insert(header_name, value?);
+    Ok(res)
+}
+
+/// Failing to write a header into a response is a bug on the server side, so the error is
+/// rendered with `500 Internal Server Error`.
+#[doc(hidden)]
+pub fn response_header_rejection(error: HeaderError) -> HeaderRejection {
+    error.with_status(http::StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Writes the header back into a response as `T::HEADER_NAME: value`, formatted with `Display`.
+///
+/// A value that isn't a valid header value fails the response with `500 Internal Server Error`.
+impl<T: RequiredHeader + std::fmt::Display> IntoResponseParts for Required<T> {
+    type Error = HeaderRejection;
+
+    fn into_response_parts(self, res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        write_header(res, T::HEADER_NAME, self.to_header_value()).map_err(response_header_rejection)
+    }
+}
+
+/// Writes the header back into a response like `Required<T>`, leaving the response untouched
+/// when absent.
+impl<T: OptionalHeader + std::fmt::Display> IntoResponseParts for Optional<T> {
+    type Error = HeaderRejection;
+
+    fn into_response_parts(self, res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        match self.to_header_value() {
+            Some(value) => {
+                write_header(res, T::HEADER_NAME, value).map_err(response_header_rejection)
+            }
+            None => Ok(res),
+        }
+    }
+}
+
+/// Wrapper type for optional headers that falls back to `T::default()`.
+///
+/// The header-side analog of the `#[header(default = ...)]` field option: extraction never
+/// fails, an absent or malformed header yields `T::default()`. Implement `Default` for `T` to
+/// provide the fallback value.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{OptionalHeader, OptionalOr};
+///
+/// struct PageSize(u32);
+///
+/// impl Default for PageSize {
+///     fn default() -> Self {
+///         PageSize(20)
+///     }
+/// }
+///
+/// impl std::str::FromStr for PageSize {
+///     type Err = std::num::ParseIntError;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         s.parse().map(PageSize)
+///     }
+/// }
+///
+/// impl OptionalHeader for PageSize {
+///     const HEADER_NAME: &'static str = "x-page-size";
+/// }
+///
+/// async fn handler(page_size: OptionalOr<PageSize>) {
+///     println!("Page size: {}", page_size.0.0);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OptionalOr<T>(pub T);
+
+impl<T> Deref for OptionalOr<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for OptionalOr<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Wrapper type for optional headers that never rejects the request.
+///
+/// Unlike [`Optional<T>`], which rejects headers that are present but malformed, `Lenient<T>`
+/// is `None` both when the header is absent and when it isn't valid ASCII or fails to parse,
+/// for best-effort headers such as client hints. The two cases can't be told apart; use
+/// `Optional<T>` when a malformed value should be reported to the client.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Lenient, OptionalHeader};
+///
+/// struct Viewport(u32);
+///
+/// impl std::str::FromStr for Viewport {
+///     type Err = std::num::ParseIntError;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         s.parse().map(Viewport)
+///     }
+/// }
+///
+/// impl OptionalHeader for Viewport {
+///     const HEADER_NAME: &'static str = "viewport-width";
+/// }
+///
+/// async fn handler(Lenient(viewport): Lenient<Viewport>) {
+///     let width = viewport.map_or(1280, |viewport| viewport.0);
+///     println!("Rendering for {width}px");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lenient<T>(pub Option<T>);
+
+impl<T> Deref for Lenient<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Lenient<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 /// Blanket implementation for `RequiredHeader` types via `Required<T>` wrapper.
 impl<S, T> FromRequestParts<S> for Required<T>
 where
@@ -127,18 +432,64 @@ where
     type Rejection = HeaderError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        let value = parts
-            .headers
-            .get(T::HEADER_NAME)
-            .ok_or(HeaderError::Missing(T::HEADER_NAME))?
-            .to_str()
-            .map_err(|_| HeaderError::InvalidValue(T::HEADER_NAME))?;
+        required_value(&parts.headers).map(Required)
+    }
+}
+
+/// Extracts the required header of `T`, the shared implementation of [`Required<T>`] and
+/// [`RequiredAll`].
+fn required_value<T>(headers: &HeaderMap) -> Result<T, HeaderError>
+where
+    T: RequiredHeader,
+    <T as std::str::FromStr>::Err: std::error::Error,
+{
+    required_str::<T>(headers).and_then(parse_required::<T>)
+}
+
+/// The value of the required header of `T`, checked to be ASCII and passed through
+/// `validate_raw`.
+fn required_str<T: RequiredHeader>(headers: &HeaderMap) -> Result<&str, HeaderError> {
+    let header = lookup::<T>(headers).ok_or(HeaderError::Missing(T::HEADER_NAME))?;
+    required_header_str::<T>(header)
+}
+
+/// `header` as a string, checked to be ASCII and passed through `validate_raw` of `T`.
+fn required_header_str<T: RequiredHeader>(header: &HeaderValue) -> Result<&str, HeaderError> {
+    let value = header
+        .to_str()
+        .map_err(|_| HeaderError::InvalidValue(T::HEADER_NAME))?;
 
-        let parsed = value
-            .parse::<T>()
-            .map_err(|_| HeaderError::Parse(T::HEADER_NAME))?;
+    T::validate_raw(value)?;
 
-        Ok(Required(parsed))
+    Ok(value)
+}
+
+/// Parses a value returned by [`required_str`].
+fn parse_required<T: RequiredHeader>(value: &str) -> Result<T, HeaderError> {
+    value
+        .parse::<T>()
+        .map_err(|_| HeaderError::Parse(T::HEADER_NAME))
+}
+
+/// `Option<Required<T>>` is `None` when the header is absent, but still rejects malformed values.
+impl<S, T> OptionalFromRequestParts<S> for Required<T>
+where
+    T: RequiredHeader,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if lookup::<T>(&parts.headers).is_none() {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
     }
 }
 
@@ -152,13 +503,15 @@ where
     type Rejection = HeaderError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        match parts.headers.get(T::HEADER_NAME) {
+        match lookup_optional::<T>(&parts.headers) {
             None => Ok(Optional(None)),
             Some(header) => {
                 let value = header
                     .to_str()
                     .map_err(|_| HeaderError::InvalidValue(T::HEADER_NAME))?;
 
+                T::validate_raw(value)?;
+
                 let parsed = value
                     .parse::<T>()
                     .map_err(|_| HeaderError::Parse(T::HEADER_NAME))?;
@@ -168,3 +521,859 @@ where
         }
     }
 }
+
+/// Blanket implementation for `OptionalHeader + Default` types via `OptionalOr<T>` wrapper.
+impl<S, T> FromRequestParts<S> for OptionalOr<T>
+where
+    T: OptionalHeader + Default,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = <Optional<T> as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|Optional(value)| value);
+
+        Ok(OptionalOr(value.unwrap_or_default()))
+    }
+}
+
+/// Blanket implementation for `OptionalHeader` types via `Lenient<T>` wrapper.
+impl<S, T> FromRequestParts<S> for Lenient<T>
+where
+    T: OptionalHeader,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = <Optional<T> as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|Optional(value)| value);
+
+        Ok(Lenient(value))
+    }
+}
+
+/// Extractor trying `A` first and falling back to `B` when `A` is rejected.
+///
+/// Useful to adopt this crate incrementally, e.g. accepting `Required<T>` while still
+/// supporting a `TypedHeader<U>` from `axum-extra` during a migration. When both extractors
+/// fail, the rejection of `A` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{FirstOf, Required, RequiredHeader};
+///
+/// struct UserId(String);
+///
+/// impl std::str::FromStr for UserId {
+///     type Err = std::convert::Infallible;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(UserId(s.to_string()))
+///     }
+/// }
+///
+/// impl RequiredHeader for UserId {
+///     const HEADER_NAME: &'static str = "x-user-id";
+/// }
+///
+/// struct LegacyUserId(String);
+///
+/// impl std::str::FromStr for LegacyUserId {
+///     type Err = std::convert::Infallible;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(LegacyUserId(s.to_string()))
+///     }
+/// }
+///
+/// impl RequiredHeader for LegacyUserId {
+///     const HEADER_NAME: &'static str = "x-legacy-user";
+/// }
+///
+/// async fn handler(user: FirstOf<Required<UserId>, Required<LegacyUserId>>) -> String {
+///     match user {
+///         FirstOf::First(Required(UserId(id))) => id,
+///         FirstOf::Second(Required(LegacyUserId(id))) => id,
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub enum FirstOf<A, B> {
+    /// `A` was extracted
+    First(A),
+    /// `A` was rejected and `B` was extracted
+    Second(B),
+}
+
+impl<S, A, B> FromRequestParts<S> for FirstOf<A, B>
+where
+    A: FromRequestParts<S>,
+    A::Rejection: Send,
+    B: FromRequestParts<S>,
+    S: Send + Sync,
+{
+    type Rejection = A::Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let rejection = match A::from_request_parts(parts, state).await {
+            Ok(first) => return Ok(FirstOf::First(first)),
+            Err(rejection) => rejection,
+        };
+        match B::from_request_parts(parts, state).await {
+            Ok(second) => Ok(FirstOf::Second(second)),
+            Err(_) => Err(rejection),
+        }
+    }
+}
+
+/// Extractor for a tuple of `RequiredHeader` types, extracted in order, for handlers needing
+/// several required headers.
+///
+/// Rejects with the [`HeaderError`] of the first header that is missing or malformed, like the
+/// equivalent list of [`Required<T>`] arguments would. Implemented for tuples of up to six
+/// types.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{RequiredAll, RequiredHeader};
+///
+/// struct OrgId(String);
+///
+/// impl std::str::FromStr for OrgId {
+///     type Err = std::convert::Infallible;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(OrgId(s.to_string()))
+///     }
+/// }
+///
+/// impl RequiredHeader for OrgId {
+///     const HEADER_NAME: &'static str = "x-org-id";
+/// }
+///
+/// struct UserId(String);
+///
+/// impl std::str::FromStr for UserId {
+///     type Err = std::convert::Infallible;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(UserId(s.to_string()))
+///     }
+/// }
+///
+/// impl RequiredHeader for UserId {
+///     const HEADER_NAME: &'static str = "x-user-id";
+/// }
+///
+/// async fn handler(RequiredAll((org_id, user_id)): RequiredAll<(OrgId, UserId)>) -> String {
+///     format!("{}/{}", org_id.0, user_id.0)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredAll<T>(pub T);
+
+impl<T> Deref for RequiredAll<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for RequiredAll<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+macro_rules! impl_required_all {
+    ($($ty:ident),+) => {
+        impl<S, $($ty),+> FromRequestParts<S> for RequiredAll<($($ty,)+)>
+        where
+            $(
+                $ty: RequiredHeader,
+                <$ty as std::str::FromStr>::Err: std::error::Error,
+            )+
+            S: Send + Sync,
+        {
+            type Rejection = HeaderError;
+
+            async fn from_request_parts(
+                parts: &mut Parts,
+                _state: &S,
+            ) -> Result<Self, Self::Rejection> {
+                Ok(RequiredAll(($(required_value::<$ty>(&parts.headers)?,)+)))
+            }
+        }
+    };
+}
+
+impl_required_all!(T1);
+impl_required_all!(T1, T2);
+impl_required_all!(T1, T2, T3);
+impl_required_all!(T1, T2, T3, T4);
+impl_required_all!(T1, T2, T3, T4, T5);
+impl_required_all!(T1, T2, T3, T4, T5, T6);
+
+/// Requires the header of `T` to be present and to parse, without keeping the parsed value.
+///
+/// Extraction runs exactly like [`Required<T>`], rejecting missing and malformed headers, then
+/// drops the value: `Present<T>` is zero-sized. It states in the handler signature that only the
+/// presence of a valid header matters, e.g. to gate a route.
+///
+/// # Examples
+///
+/// ```
+/// use axum::{Router, routing::get};
+/// use axum_required_headers::{Header, Present};
+///
+/// #[derive(Header)]
+/// #[header("x-api-key", infallible)]
+/// struct ApiKey(String);
+///
+/// async fn beta_feature(_: Present<ApiKey>) -> &'static str {
+///     "welcome to the beta"
+/// }
+///
+/// let app: Router = Router::new().route("/beta", get(beta_feature));
+/// ```
+pub struct Present<T>(PhantomData<fn() -> T>);
+
+impl<T> Clone for Present<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Present<T> {}
+
+impl<T> std::fmt::Debug for Present<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Present")
+    }
+}
+
+impl<S, T> FromRequestParts<S> for Present<T>
+where
+    T: RequiredHeader,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        required_value::<T>(&parts.headers).map(|_| Present(PhantomData))
+    }
+}
+
+/// An asynchronous check of a parsed header against the router state `S`, run by
+/// [`Validated<T>`].
+///
+/// Implement it for checks that need I/O, e.g. looking an API key up in a database. Implementors
+/// can write `async fn validate`; the returned future must be `Send`.
+///
+/// [`Required<T>`] doesn't run this check: without specialization a blanket no-op impl would rule
+/// out every other impl, so the asynchronous check is opted into with the `Validated<T>` extractor
+/// instead, and existing `Required<T>` extractors are unaffected.
+pub trait AsyncValidateHeader<S>: RequiredHeader {
+    /// Checks the parsed header, rejecting the request with the returned error on failure.
+    fn validate(&self, state: &S) -> impl Future<Output = Result<(), HeaderError>> + Send;
+}
+
+/// Extractor for a required header that is parsed like [`Required<T>`] and then checked with
+/// [`AsyncValidateHeader::validate`] against the router state.
+///
+/// Missing and malformed headers are rejected before the check runs. `Option<Validated<T>>` is
+/// `None` when the header is absent and validates present values.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use std::sync::Arc;
+///
+/// use axum::{Router, routing::get};
+/// use axum_required_headers::{AsyncValidateHeader, Header, HeaderError, Validated};
+///
+/// #[derive(Header)]
+/// #[header("x-api-key", infallible)]
+/// struct ApiKey(String);
+///
+/// /// A stand-in for a database of API keys
+/// #[derive(Clone)]
+/// struct KeyStore(Arc<HashSet<String>>);
+///
+/// impl KeyStore {
+///     async fn contains(&self, key: &str) -> bool {
+///         self.0.contains(key)
+///     }
+/// }
+///
+/// impl AsyncValidateHeader<KeyStore> for ApiKey {
+///     async fn validate(&self, store: &KeyStore) -> Result<(), HeaderError> {
+///         if store.contains(&self.0).await {
+///             Ok(())
+///         } else {
+///             Err(HeaderError::Validation {
+///                 name: "x-api-key",
+///                 reason: "unknown API key".to_owned(),
+///             })
+///         }
+///     }
+/// }
+///
+/// async fn handler(Validated(ApiKey(key)): Validated<ApiKey>) -> String {
+///     format!("authenticated with {key}")
+/// }
+///
+/// let store = KeyStore(Arc::new(HashSet::from(["secret".to_owned()])));
+/// let app: Router = Router::new().route("/", get(handler)).with_state(store);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validated<T>(pub T);
+
+impl<T> Validated<T> {
+    /// Consumes the wrapper, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S, T> FromRequestParts<S> for Validated<T>
+where
+    T: AsyncValidateHeader<S>,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = required_value::<T>(&parts.headers)?;
+        value.validate(state).await?;
+        Ok(Validated(value))
+    }
+}
+
+/// `Option<Validated<T>>` is `None` when the header is absent, but still rejects malformed
+/// values and failed checks.
+impl<S, T> OptionalFromRequestParts<S> for Validated<T>
+where
+    T: AsyncValidateHeader<S>,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        match required_value::<T>(&parts.headers) {
+            Ok(value) => {
+                value.validate(state).await?;
+                Ok(Some(Validated(value)))
+            }
+            Err(HeaderError::Missing(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// Looks up the header of a [`RequiredHeader`] type, see [`find_header`].
+fn lookup<T: RequiredHeader>(headers: &HeaderMap) -> Option<&HeaderValue> {
+    find_header(
+        headers,
+        T::HEADER_NAME,
+        const { static_name(T::HEADER_NAME) }.as_ref(),
+        T::HEADER_ALIASES,
+        T::EMPTY_IS_MISSING,
+    )
+}
+
+/// Looks up the header of an [`OptionalHeader`] type, see [`find_header`].
+fn lookup_optional<T: OptionalHeader>(headers: &HeaderMap) -> Option<&HeaderValue> {
+    find_header(
+        headers,
+        T::HEADER_NAME,
+        const { static_name(T::HEADER_NAME) }.as_ref(),
+        T::HEADER_ALIASES,
+        T::EMPTY_IS_MISSING,
+    )
+}
+
+/// Looks up a header by its name, falling back to its aliases in order.
+///
+/// `static_name` is `name` parsed at compile time by [`static_name`], which spares parsing the
+/// name on every lookup. With `skip_empty`, empty values are treated as absent.
+pub(crate) fn find_header<'a>(
+    headers: &'a HeaderMap,
+    name: &'static str,
+    static_name: Option<&HeaderName>,
+    aliases: &[&'static str],
+    skip_empty: bool,
+) -> Option<&'a HeaderValue> {
+    let present = |value: &&HeaderValue| !(skip_empty && value.is_empty());
+    match static_name {
+        Some(static_name) => headers.get(static_name),
+        None => headers.get(name),
+    }
+    .filter(present)
+    .or_else(|| {
+        aliases
+            .iter()
+            .find_map(|alias| headers.get(*alias).filter(present))
+    })
+}
+
+/// Parses `name` at compile time, e.g. `const { static_name(T::HEADER_NAME) }`.
+///
+/// `None` when `name` isn't a valid lowercase header name (e.g. `X-User-Id`), which
+/// `HeaderName::from_static` would reject. Such names are parsed on every lookup instead.
+pub(crate) const fn static_name(name: &'static str) -> Option<HeaderName> {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+    let mut i = 0;
+    while i < bytes.len() {
+        let valid = matches!(
+            bytes[i],
+            b'a'..=b'z'
+                | b'0'..=b'9'
+                | b'!'
+                | b'#'
+                | b'$'
+                | b'%'
+                | b'&'
+                | b'\''
+                | b'*'
+                | b'+'
+                | b'-'
+                | b'.'
+                | b'^'
+                | b'_'
+                | b'`'
+                | b'|'
+                | b'~'
+        );
+        if !valid {
+            return None;
+        }
+        i += 1;
+    }
+    Some(HeaderName::from_static(name))
+}
+
+/// Header type adapter that rejects values longer than `N` bytes.
+///
+/// Wrapping a header type `T` in `MaxLen<T, N>` keeps its header name but makes
+/// `Required<MaxLen<T, N>>` and `Optional<MaxLen<T, N>>` reject values whose byte length
+/// exceeds `N` with [`HeaderError::TooLong`], before `T` is parsed.
+///
+/// `MaxLen<T, N>` also implements `FromStr`, so it can be used as a `Headers` field type,
+/// in which case a too long value is reported as [`HeaderError::Parse`].
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Header, MaxLen, Required};
+///
+/// #[derive(Header)]
+/// #[header("x-user-id")]
+/// struct UserId(String);
+///
+/// impl std::str::FromStr for UserId {
+///     type Err = std::convert::Infallible;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         Ok(UserId(s.to_string()))
+///     }
+/// }
+///
+/// async fn handler(Required(user_id): Required<MaxLen<UserId, 64>>) {
+///     let UserId(user_id) = user_id.into_inner();
+///     println!("User: {user_id}");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxLen<T, const N: usize>(pub T);
+
+impl<T, const N: usize> MaxLen<T, N> {
+    /// The maximum accepted length of the header value, in bytes.
+    pub const MAX: usize = N;
+
+    /// Consumes the wrapper, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Deref for MaxLen<T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> DerefMut for MaxLen<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Error returned when parsing a [`MaxLen`] value fails.
+#[derive(Debug, thiserror::Error)]
+pub enum MaxLenError<E> {
+    #[error("value is {len} bytes long, the maximum is {max}")]
+    TooLong { len: usize, max: usize },
+    #[error(transparent)]
+    Inner(E),
+}
+
+impl<T, const N: usize> std::str::FromStr for MaxLen<T, N>
+where
+    T: std::str::FromStr,
+{
+    type Err = MaxLenError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > N {
+            return Err(MaxLenError::TooLong {
+                len: s.len(),
+                max: N,
+            });
+        }
+        s.parse().map(MaxLen).map_err(MaxLenError::Inner)
+    }
+}
+
+impl<T, const N: usize> RequiredHeader for MaxLen<T, N>
+where
+    T: RequiredHeader,
+{
+    const HEADER_NAME: &'static str = T::HEADER_NAME;
+    const HEADER_ALIASES: &'static [&'static str] = T::HEADER_ALIASES;
+    const EMPTY_IS_MISSING: bool = T::EMPTY_IS_MISSING;
+
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        if value.len() > N {
+            return Err(HeaderError::TooLong {
+                name: Self::HEADER_NAME,
+                max: N,
+            });
+        }
+        T::validate_raw(value)
+    }
+}
+
+impl<T, const N: usize> OptionalHeader for MaxLen<T, N>
+where
+    T: OptionalHeader,
+{
+    const HEADER_NAME: &'static str = T::HEADER_NAME;
+    const HEADER_ALIASES: &'static [&'static str] = T::HEADER_ALIASES;
+    const EMPTY_IS_MISSING: bool = T::EMPTY_IS_MISSING;
+
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        if value.len() > N {
+            return Err(HeaderError::TooLong {
+                name: Self::HEADER_NAME,
+                max: N,
+            });
+        }
+        T::validate_raw(value)
+    }
+}
+
+/// Header type adapter that redacts the value in `Debug` output.
+///
+/// Wrapping a header type `T` in `Sensitive<T>` keeps its header name, aliases and parsing, but
+/// `{:?}` prints `<redacted>`, so `Required<Sensitive<T>>` and `Optional<Sensitive<T>>` (and
+/// structs deriving `Debug` around them) can be logged without leaking secrets such as API keys.
+/// `Sensitive<T>` also implements `FromStr`, so it can be used as a `Headers` field type.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Header, Required, Sensitive};
+///
+/// #[derive(Debug, Header)]
+/// #[header("x-api-key", infallible)]
+/// struct ApiKey(String);
+///
+/// async fn handler(api_key: Required<Sensitive<ApiKey>>) {
+///     println!("{api_key:?}"); // Required(<redacted>)
+///     let ApiKey(key) = api_key.into_inner().into_inner();
+///     # let _ = key;
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sensitive<T>(pub T);
+
+impl<T> Sensitive<T> {
+    /// Consumes the wrapper, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::fmt::Debug for Sensitive<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl<T> Deref for Sensitive<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Sensitive<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: std::str::FromStr> std::str::FromStr for Sensitive<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Sensitive)
+    }
+}
+
+impl<T: RequiredHeader> RequiredHeader for Sensitive<T> {
+    const HEADER_NAME: &'static str = T::HEADER_NAME;
+    const HEADER_ALIASES: &'static [&'static str] = T::HEADER_ALIASES;
+    const EMPTY_IS_MISSING: bool = T::EMPTY_IS_MISSING;
+
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        T::validate_raw(value)
+    }
+}
+
+impl<T: OptionalHeader> OptionalHeader for Sensitive<T> {
+    const HEADER_NAME: &'static str = T::HEADER_NAME;
+    const HEADER_ALIASES: &'static [&'static str] = T::HEADER_ALIASES;
+    const EMPTY_IS_MISSING: bool = T::EMPTY_IS_MISSING;
+
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        T::validate_raw(value)
+    }
+}
+
+/// Extractor keeping the raw value of a required header instead of parsing it into `T`.
+///
+/// `T` only provides the header name, aliases and `validate_raw`; its `FromStr` impl is never
+/// called. The value is checked to be valid ASCII during extraction and then stored as a clone of
+/// the request's `HeaderValue`, and [`RawHeader::as_str`] borrows from it without allocating a
+/// `String`. The clone shares the bytes, though a value parsed at runtime may allocate once to
+/// become shareable. Useful for hot paths that only validate or hash a header.
+///
+/// `Option<RawHeader<T>>` is `None` when the header is absent.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Header, RawHeader};
+///
+/// #[derive(Header)]
+/// #[header("x-api-key", infallible)]
+/// struct ApiKey(String);
+///
+/// async fn handler(api_key: RawHeader<ApiKey>) -> &'static str {
+///     if api_key.as_str().starts_with("live_") {
+///         "live"
+///     } else {
+///         "test"
+///     }
+/// }
+/// ```
+pub struct RawHeader<T> {
+    value: HeaderValue,
+    _header: PhantomData<fn() -> T>,
+}
+
+impl<T> RawHeader<T> {
+    /// The header value, validated to be ASCII during extraction.
+    pub fn as_str(&self) -> &str {
+        self.value
+            .to_str()
+            .expect("header value validated during extraction")
+    }
+
+    /// The header value as received.
+    pub fn as_header_value(&self) -> &HeaderValue {
+        &self.value
+    }
+
+    /// Consumes the extractor, returning the header value.
+    pub fn into_inner(self) -> HeaderValue {
+        self.value
+    }
+}
+
+impl<T> Clone for RawHeader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            _header: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for RawHeader<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RawHeader").field(&self.value).finish()
+    }
+}
+
+impl<S, T> FromRequestParts<S> for RawHeader<T>
+where
+    T: RequiredHeader,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = lookup::<T>(&parts.headers).ok_or(HeaderError::Missing(T::HEADER_NAME))?;
+        required_header_str::<T>(value)?;
+
+        Ok(RawHeader {
+            value: value.clone(),
+            _header: PhantomData,
+        })
+    }
+}
+
+/// `Option<RawHeader<T>>` is `None` when the header is absent, but still rejects invalid values.
+impl<S, T> OptionalFromRequestParts<S> for RawHeader<T>
+where
+    T: RequiredHeader,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if lookup::<T>(&parts.headers).is_none() {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
+
+/// Extractor yielding both the parsed value of a required header and the string it was parsed
+/// from.
+///
+/// Useful for logging exactly what the client sent while working with the typed value, without
+/// extracting the header twice. Rejections match [`Required<T>`].
+///
+/// `Option<WithRaw<T>>` is `None` when the header is absent, but still rejects malformed values.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Header, WithRaw};
+/// use std::num::ParseIntError;
+/// use std::str::FromStr;
+///
+/// #[derive(Header)]
+/// #[header("x-page")]
+/// struct Page(u32);
+///
+/// impl FromStr for Page {
+///     type Err = ParseIntError;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         s.parse().map(Page)
+///     }
+/// }
+///
+/// async fn handler(page: WithRaw<Page>) -> String {
+///     format!("page {} (sent as {:?})", page.0, page.raw)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithRaw<T> {
+    /// The parsed header value.
+    pub value: T,
+    /// The header value as sent by the client.
+    pub raw: String,
+}
+
+impl<T> WithRaw<T> {
+    /// Consumes the extractor, returning the parsed value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for WithRaw<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<S, T> FromRequestParts<S> for WithRaw<T>
+where
+    T: RequiredHeader,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let raw = required_str::<T>(&parts.headers)?;
+
+        Ok(WithRaw {
+            value: parse_required::<T>(raw)?,
+            raw: raw.to_owned(),
+        })
+    }
+}
+
+/// `Option<WithRaw<T>>` is `None` when the header is absent, but still rejects malformed values.
+impl<S, T> OptionalFromRequestParts<S> for WithRaw<T>
+where
+    T: RequiredHeader,
+    <T as std::str::FromStr>::Err: std::error::Error,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        if lookup::<T>(&parts.headers).is_none() {
+            return Ok(None);
+        }
+        <Self as FromRequestParts<S>>::from_request_parts(parts, state)
+            .await
+            .map(Some)
+    }
+}
@@ -0,0 +1,106 @@
+//! Client certificate fingerprints forwarded by TLS-terminating proxies.
+
+use std::str::FromStr;
+
+use crate::{OptionalHeader, RequiredHeader};
+
+/// The SHA-256 fingerprint of the client certificate, from the `x-client-cert-sha256` header set
+/// by mTLS-terminating proxies.
+///
+/// The encoding is detected from the value: hex digits (either case, optionally as
+/// colon-separated pairs like `AB:CD:...`) are decoded as hex, anything else as base64 (padded
+/// standard alphabet, or URL-safe alphabet with optional padding). Base64 values require the
+/// `base64` feature and are rejected without it. Values that don't decode to exactly 32 bytes
+/// are rejected.
+///
+/// Only trust this header when the proxy strips it from incoming requests.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{CertFingerprint, Required};
+///
+/// const ADMIN_CERT: [u8; 32] = [0xab; 32];
+///
+/// async fn handler(Required(fingerprint): Required<CertFingerprint>) -> &'static str {
+///     if fingerprint.0 == ADMIN_CERT { "admin" } else { "user" }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CertFingerprint(pub [u8; 32]);
+
+/// Error returned when a certificate fingerprint can't be decoded.
+#[derive(Debug, thiserror::Error)]
+pub enum ParseFingerprintError {
+    /// The value is neither valid hex nor (with the `base64` feature) valid base64
+    #[error("fingerprint is neither valid hex nor base64")]
+    InvalidEncoding,
+    /// The value decoded to the given number of bytes instead of 32
+    #[error("fingerprint is {0} bytes long, expected 32")]
+    InvalidLength(usize),
+}
+
+impl FromStr for CertFingerprint {
+    type Err = ParseFingerprintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = if s.bytes().all(|b| b.is_ascii_hexdigit() || b == b':') {
+            decode_hex(s)?
+        } else {
+            decode_base64(s)?
+        };
+
+        <[u8; 32]>::try_from(bytes.as_slice())
+            .map(CertFingerprint)
+            .map_err(|_| ParseFingerprintError::InvalidLength(bytes.len()))
+    }
+}
+
+impl RequiredHeader for CertFingerprint {
+    const HEADER_NAME: &'static str = "x-client-cert-sha256";
+}
+
+impl OptionalHeader for CertFingerprint {
+    const HEADER_NAME: &'static str = "x-client-cert-sha256";
+}
+
+/// Decodes hex digits, either contiguous or as colon-separated pairs.
+fn decode_hex(s: &str) -> Result<Vec<u8>, ParseFingerprintError> {
+    let digits: Vec<u8> = if s.contains(':') {
+        let pairs: Vec<&str> = s.split(':').collect();
+        if pairs.iter().any(|pair| pair.len() != 2) {
+            return Err(ParseFingerprintError::InvalidEncoding);
+        }
+        pairs.concat().into_bytes()
+    } else {
+        s.as_bytes().to_vec()
+    };
+    if digits.is_empty() || !digits.len().is_multiple_of(2) {
+        return Err(ParseFingerprintError::InvalidEncoding);
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or(ParseFingerprintError::InvalidEncoding)
+        })
+        .collect()
+}
+
+/// Decodes base64 in the standard alphabet, falling back to the URL-safe one.
+#[cfg(feature = "base64")]
+fn decode_base64(s: &str) -> Result<Vec<u8>, ParseFingerprintError> {
+    use crate::__private::decode_base64;
+
+    decode_base64(s, false)
+        .or_else(|| decode_base64(s, true))
+        .ok_or(ParseFingerprintError::InvalidEncoding)
+}
+
+#[cfg(not(feature = "base64"))]
+fn decode_base64(_: &str) -> Result<Vec<u8>, ParseFingerprintError> {
+    Err(ParseFingerprintError::InvalidEncoding)
+}
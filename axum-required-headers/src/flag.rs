@@ -0,0 +1,81 @@
+//! Lenient parsing of boolean header values.
+
+use std::str::FromStr;
+
+/// A boolean header value accepting the usual spellings of on and off.
+///
+/// `true`, `1`, `yes` and `on` parse as `Flag(true)`, `false`, `0`, `no` and `off` as
+/// `Flag(false)`, compared case-insensitively after trimming whitespace. Anything else, including
+/// an empty value, fails with [`ParseFlagError`], which extractors report as
+/// [`HeaderError::Parse`](crate::HeaderError::Parse). `bool` itself only accepts `true` and
+/// `false`.
+///
+/// `Flag` has no header name of its own: use it as a `Headers` field, or wrap it in a named
+/// header type to extract it with `Required<T>` or `Optional<T>`.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Flag, Headers, Required, impl_header};
+///
+/// #[derive(Headers)]
+/// struct FeatureHeaders {
+///     #[header("x-enabled")]
+///     enabled: Flag,
+///     #[header("x-dry-run")]
+///     dry_run: Option<Flag>,
+/// }
+///
+/// struct Verbose(Flag);
+///
+/// impl std::str::FromStr for Verbose {
+///     type Err = axum_required_headers::ParseFlagError;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         s.parse().map(Verbose)
+///     }
+/// }
+///
+/// impl_header!(Verbose, "x-verbose");
+///
+/// async fn handler(headers: FeatureHeaders, Required(Verbose(verbose)): Required<Verbose>) {
+///     if headers.enabled.0 && !headers.dry_run.is_some_and(|dry_run| dry_run.0) {
+///         println!("running, verbose: {}", bool::from(verbose));
+///     }
+/// }
+///
+/// assert_eq!("YES".parse::<Flag>().unwrap(), Flag(true));
+/// assert_eq!("off".parse::<Flag>().unwrap(), Flag(false));
+/// assert!("maybe".parse::<Flag>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Flag(pub bool);
+
+/// Error returned when a value is none of the accepted [`Flag`] spellings.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid flag, expected one of true, false, 1, 0, yes, no, on or off")]
+pub struct ParseFlagError(());
+
+impl FromStr for Flag {
+    type Err = ParseFlagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const TRUE: [&str; 4] = ["true", "1", "yes", "on"];
+        const FALSE: [&str; 4] = ["false", "0", "no", "off"];
+
+        let s = s.trim();
+        if TRUE.iter().any(|token| s.eq_ignore_ascii_case(token)) {
+            Ok(Flag(true))
+        } else if FALSE.iter().any(|token| s.eq_ignore_ascii_case(token)) {
+            Ok(Flag(false))
+        } else {
+            Err(ParseFlagError(()))
+        }
+    }
+}
+
+impl From<Flag> for bool {
+    fn from(flag: Flag) -> Self {
+        flag.0
+    }
+}
@@ -0,0 +1,110 @@
+//! The `Idempotency-Key` header.
+
+use std::{convert::Infallible, str::FromStr};
+
+use crate::{HeaderError, OptionalHeader, RequiredHeader};
+
+/// The `Idempotency-Key` header, identifying retries of the same non-idempotent request.
+///
+/// Any non-empty value is accepted, use [`IdempotencyKey::is_uuid`] (or the `uuid` field option
+/// of `#[derive(Headers)]`) to require UUID keys.
+///
+/// # Checking uniqueness
+///
+/// Whether a key was already used depends on your store, so it's checked by wrapping
+/// `Required<IdempotencyKey>` in an extractor of your own that reads the store from the router
+/// state:
+///
+/// ```
+/// use std::collections::HashSet;
+/// use std::sync::{Arc, Mutex};
+///
+/// use axum_required_headers::axum::extract::{FromRef, FromRequestParts};
+/// use axum_required_headers::axum::response::{IntoResponse, Response};
+/// use axum_required_headers::http::{StatusCode, request::Parts};
+/// use axum_required_headers::{IdempotencyKey, Required};
+///
+/// #[derive(Clone, Default)]
+/// struct SeenKeys(Arc<Mutex<HashSet<String>>>);
+///
+/// /// An idempotency key that wasn't used before.
+/// struct FreshKey(String);
+///
+/// impl<S> FromRequestParts<S> for FreshKey
+/// where
+///     SeenKeys: FromRef<S>,
+///     S: Send + Sync,
+/// {
+///     type Rejection = Response;
+///
+///     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+///         let Required(IdempotencyKey(key)) = Required::<IdempotencyKey>::from_request_parts(parts, state)
+///             .await
+///             .map_err(IntoResponse::into_response)?;
+///         if !SeenKeys::from_ref(state).0.lock().unwrap().insert(key.clone()) {
+///             return Err((StatusCode::CONFLICT, "duplicate idempotency key").into_response());
+///         }
+///         Ok(FreshKey(key))
+///     }
+/// }
+///
+/// async fn create_payment(FreshKey(key): FreshKey) -> String {
+///     format!("processing {key}")
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(pub String);
+
+impl IdempotencyKey {
+    /// Whether the key is a UUID in its hyphenated form, e.g.
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8` (of any version, in either case).
+    pub fn is_uuid(&self) -> bool {
+        is_uuid(&self.0)
+    }
+}
+
+impl FromStr for IdempotencyKey {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(IdempotencyKey(s.to_owned()))
+    }
+}
+
+fn validate_key(value: &str) -> Result<(), HeaderError> {
+    if value.is_empty() {
+        return Err(HeaderError::Validation {
+            name: "idempotency-key",
+            reason: "idempotency key must not be empty".to_owned(),
+        });
+    }
+    Ok(())
+}
+
+impl RequiredHeader for IdempotencyKey {
+    const HEADER_NAME: &'static str = "idempotency-key";
+
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        validate_key(value)
+    }
+}
+
+impl OptionalHeader for IdempotencyKey {
+    const HEADER_NAME: &'static str = "idempotency-key";
+
+    fn validate_raw(value: &str) -> Result<(), HeaderError> {
+        validate_key(value)
+    }
+}
+
+/// Whether `value` is a hyphenated UUID, `8-4-4-4-12` hex digits.
+pub(crate) fn is_uuid(value: &str) -> bool {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+    let mut groups = value.split('-');
+    GROUPS.iter().all(|len| {
+        groups.next().is_some_and(|group| {
+            group.len() == *len && group.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+    }) && groups.next().is_none()
+}
@@ -55,13 +55,386 @@
 //! }
 //! ```
 
+mod auth;
+mod content_length;
+mod disposition;
 mod error;
 mod extractors;
+mod fingerprint;
+mod flag;
+mod idempotency;
+mod link;
+mod negotiation;
+mod privacy;
+mod range;
+mod rejection;
+mod retry_after;
+mod schema;
+mod syntax;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod websocket;
 
+pub use auth::{AnyAuth, Credential, ParseAuthError};
 pub use axum_required_headers_derive::{Header, Headers};
-pub use error::HeaderError;
-pub use extractors::{Optional, OptionalHeader, Required, RequiredHeader};
+pub use content_length::VerifiedContentLength;
+pub use disposition::{ContentDisposition, ParseContentDispositionError};
+#[cfg(feature = "json")]
+pub use error::ProblemJson;
+pub use error::{HeaderError, HeaderWarning, ParseEnumError};
+pub use extractors::{
+    AsyncValidateHeader, FirstOf, Lenient, MaxLen, MaxLenError, Optional, OptionalHeader,
+    OptionalOr, Present, RawHeader, Required, RequiredAll, RequiredHeader, Sensitive, Validated,
+    WithRaw,
+};
+pub use fingerprint::{CertFingerprint, ParseFingerprintError};
+pub use flag::{Flag, ParseFlagError};
+pub use idempotency::IdempotencyKey;
+pub use link::{Link, Links, ParseLinkError};
+pub use negotiation::{
+    AcceptCharset, AcceptedType, CharsetRange, MediaRange, ParseAcceptError, ParseQualityListError,
+    Quality, QualityList, ResponseFormat, Vary,
+};
+pub use privacy::{DoNotTrack, GlobalPrivacyControl};
+pub use range::{ByteRanges, ByteUnit, ParseRangeError, Range, RangeSpec, RangeUnit};
+pub use rejection::{HeaderRejection, X_REQUIRED_HEADERS};
+pub use retry_after::{ParseRetryAfterError, RetryAfter};
+pub use schema::{HeaderSchema, HeaderSchemaBuilder, SchemaHeaders};
+pub use websocket::WebSocketHandshake;
+
+/// Items used by the derive macros, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    /// Implemented for every `FromStr` type, with a diagnostic pointing at the derive.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` derives `Header` but does not implement `FromStr`",
+        label = "`Header` types are parsed from the header value with `FromStr`",
+        note = "implement `std::str::FromStr` for `{Self}`"
+    )]
+    pub trait HeaderFromStr {}
+
+    impl<T: std::str::FromStr> HeaderFromStr for T {}
+
+    pub fn assert_from_str<T: HeaderFromStr + ?Sized>() {}
+
+    /// `FromStr` for the field types of `#[derive(Headers)]`, with a diagnostic pointing at the
+    /// field type.
+    #[diagnostic::on_unimplemented(
+        message = "the `Headers` field type `{Self}` does not implement `FromStr`",
+        label = "header values are parsed into this type with `FromStr`",
+        note = "implement `std::str::FromStr` for `{Self}`, store the raw value in a `HeaderValue` \
+                or `Vec<u8>` field, or decode it with `typed` or `json`"
+    )]
+    pub trait FieldFromStr: Sized {
+        fn parse_field(value: &str) -> Option<Self>;
+    }
+
+    impl<T: std::str::FromStr> FieldFromStr for T {
+        fn parse_field(value: &str) -> Option<Self> {
+            value.parse().ok()
+        }
+    }
+
+    /// Parses a `Headers` field value, reporting types without a `FromStr` impl at the field.
+    pub fn parse_field<T: FieldFromStr>(value: &str) -> Option<T> {
+        T::parse_field(value)
+    }
+
+    /// Checks a header value for `#[header(uuid)]`.
+    pub fn is_uuid(value: &str) -> bool {
+        crate::idempotency::is_uuid(value)
+    }
+
+    /// Selects the OpenAPI schema of a field type for the `utoipa` feature.
+    ///
+    /// `(&&FieldSchema::<T>(PhantomData)).field_schema()` resolves to [`TypeSchema`] when `T`
+    /// implements `utoipa::PartialSchema`, and to [`StringSchema`] otherwise, as header values are
+    /// strings on the wire.
+    #[cfg(feature = "utoipa")]
+    pub struct FieldSchema<T: ?Sized>(pub std::marker::PhantomData<T>);
+
+    #[cfg(feature = "utoipa")]
+    pub trait TypeSchema {
+        fn field_schema(&self) -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>;
+    }
+
+    #[cfg(feature = "utoipa")]
+    impl<T: utoipa::PartialSchema + ?Sized> TypeSchema for &FieldSchema<T> {
+        fn field_schema(&self) -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+            T::schema()
+        }
+    }
+
+    #[cfg(feature = "utoipa")]
+    pub trait StringSchema {
+        fn field_schema(&self) -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>;
+    }
+
+    #[cfg(feature = "utoipa")]
+    impl<T: ?Sized> StringSchema for FieldSchema<T> {
+        fn field_schema(&self) -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+            utoipa::openapi::ObjectBuilder::new()
+                .schema_type(utoipa::openapi::schema::Type::String)
+                .into()
+        }
+    }
+
+    /// A `header` parameter of the generated `utoipa::IntoParams` impl.
+    #[cfg(feature = "utoipa")]
+    pub fn header_parameter(
+        name: &str,
+        required: bool,
+        schema: utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+    ) -> utoipa::openapi::path::Parameter {
+        use utoipa::openapi::{
+            Required,
+            path::{ParameterBuilder, ParameterIn},
+        };
+
+        ParameterBuilder::new()
+            .name(name)
+            .parameter_in(ParameterIn::Header)
+            .required(if required {
+                Required::True
+            } else {
+                Required::False
+            })
+            .schema(Some(schema))
+            .build()
+    }
+
+    /// Parses a duration such as `500ms` or `30s` for `#[header(duration_suffix)]`.
+    ///
+    /// Supports the `ms`, `s`, `m` and `h` units; a bare number is in seconds. Unknown units,
+    /// fractions and overflowing values are rejected.
+    pub fn parse_duration(value: &str) -> Option<std::time::Duration> {
+        use std::time::Duration;
+
+        let digits = value.bytes().take_while(u8::is_ascii_digit).count();
+        let (number, unit) = value.split_at(digits);
+        let number: u64 = number.parse().ok()?;
+        let seconds_per_unit = match unit {
+            "ms" => return Some(Duration::from_millis(number)),
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            _ => return None,
+        };
+        number
+            .checked_mul(seconds_per_unit)
+            .map(Duration::from_secs)
+    }
+
+    /// Decodes a base64 header value for `#[header(base64)]`.
+    ///
+    /// The standard alphabet requires padding, the URL-safe one accepts it either way.
+    #[cfg(feature = "base64")]
+    pub fn decode_base64(value: &str, url_safe: bool) -> Option<Vec<u8>> {
+        use base64::Engine;
+        use base64::engine::{
+            DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig, general_purpose,
+        };
+
+        const URL_SAFE: GeneralPurpose = GeneralPurpose::new(
+            &base64::alphabet::URL_SAFE,
+            GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+        );
+
+        if url_safe {
+            URL_SAFE.decode(value).ok()
+        } else {
+            general_purpose::STANDARD.decode(value).ok()
+        }
+    }
+
+    /// The maximum size of a decompressed `#[header(gzip)]` payload, guarding against
+    /// decompression bombs.
+    #[cfg(feature = "gzip")]
+    const MAX_GUNZIPPED_LEN: u64 = 1024 * 1024;
+
+    /// Decompresses a gzip payload for `#[header(gzip)]`, failing for payloads decompressing to
+    /// more than 1 MiB.
+    #[cfg(feature = "gzip")]
+    pub fn gunzip(bytes: &[u8]) -> Option<Vec<u8>> {
+        use std::io::Read;
+
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(bytes)
+            .take(MAX_GUNZIPPED_LEN + 1)
+            .read_to_end(&mut decompressed)
+            .ok()?;
+        (decompressed.len() as u64 <= MAX_GUNZIPPED_LEN).then_some(decompressed)
+    }
+
+    /// Deserializes a JSON header value (or decoded payload) for `#[header(json)]`.
+    #[cfg(feature = "json")]
+    pub fn decode_json<T: serde::de::DeserializeOwned>(value: &str) -> Option<T> {
+        serde_json::from_str(value).ok()
+    }
+
+    pub use crate::extractors::{response_header_rejection, write_header};
+
+    /// Sets an attribute on the active OpenTelemetry span for `#[header(otel_attr = "...")]`.
+    #[cfg(feature = "otel")]
+    pub fn set_span_attribute(key: &'static str, value: &dyn std::fmt::Display) {
+        let value = value.to_string();
+        opentelemetry::trace::get_active_span(|span| {
+            span.set_attribute(opentelemetry::KeyValue::new(key, value));
+        });
+    }
+
+    #[cfg(feature = "regex")]
+    pub use regex;
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    /// Decodes the values of a header for `#[header(typed)]`.
+    #[cfg(feature = "headers")]
+    pub fn decode_typed<T: headers::Header>(
+        values: http::header::GetAll<'_, http::HeaderValue>,
+        name: &'static str,
+    ) -> Result<T, crate::HeaderError> {
+        T::decode(&mut values.iter()).map_err(|_| crate::HeaderError::Parse(name))
+    }
+
+    /// Deserializes a map of header names to values into a `HeaderMap`, for
+    /// `#[headers(deserialize)]`.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_header_map<'de, D>(deserializer: D) -> Result<http::HeaderMap, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let map = <std::collections::HashMap<String, String> as serde::Deserialize>::deserialize(
+            deserializer,
+        )?;
+        let mut headers = http::HeaderMap::with_capacity(map.len());
+        for (name, value) in map {
+            let name = http::HeaderName::try_from(name.as_str())
+                .map_err(|_| D::Error::custom(format_args!("invalid header name `{name}`")))?;
+            let value = http::HeaderValue::try_from(value)
+                .map_err(|_| D::Error::custom(format_args!("invalid value for header `{name}`")))?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Logs the names of the headers extraction read (the request headers, or the
+    /// `source_extension` map) when it fails, for `#[headers(debug_on_error)]`. Does nothing
+    /// without the `tracing` feature.
+    pub fn debug_header_error(headers: &http::HeaderMap, error: &crate::HeaderError) {
+        #[cfg(feature = "tracing")]
+        {
+            let names: Vec<&str> = headers.keys().map(http::HeaderName::as_str).collect();
+            tracing::debug!(%error, request_headers = ?names, "header extraction failed");
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (headers, error);
+    }
+
+    /// Logs a header value that only matched the enum variant `expected` after case
+    /// normalization, for `#[header(warn_on_normalize)]`. Does nothing without the `tracing`
+    /// feature.
+    pub fn warn_normalized_value(name: &'static str, value: &str, expected: &'static str) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            header = name,
+            value,
+            expected,
+            "header value matched only after case normalization"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = (name, value, expected);
+    }
+
+    /// The value of the first `param` parameter of the comma-separated media types in the
+    /// `header` headers, with surrounding quotes removed, for `#[header(media_param(...))]`.
+    ///
+    /// Values that aren't valid ASCII are skipped.
+    pub fn media_type_param(
+        headers: &http::HeaderMap,
+        header: &str,
+        param: &str,
+    ) -> Option<String> {
+        headers
+            .get_all(header)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .flat_map(|media_type| media_type.split(';').skip(1))
+            .find_map(|parameter| {
+                let (name, value) = parameter.split_once('=')?;
+                name.trim().eq_ignore_ascii_case(param).then(|| {
+                    let value = value.trim();
+                    value
+                        .strip_prefix('"')
+                        .and_then(|value| value.strip_suffix('"'))
+                        .unwrap_or(value)
+                        .to_owned()
+                })
+            })
+    }
+
+    /// The URL-decoded value of the first `name` parameter of the `query` string, for
+    /// `#[header(or_query = "...")]`: `None` when there is no such parameter (or no query string),
+    /// `Some(None)` when its value isn't valid percent-encoded UTF-8.
+    ///
+    /// Keys and values are decoded as `application/x-www-form-urlencoded`, `+` being a space.
+    pub fn query_param(query: Option<&str>, name: &str) -> Option<Option<String>> {
+        let decode = |encoded: &str| {
+            let bytes = crate::syntax::percent_decode(&encoded.replace('+', " "))?;
+            String::from_utf8(bytes).ok()
+        };
+        query?
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .find_map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(key)? == name).then(|| decode(value))
+            })
+    }
+
+    /// Fails with `HeaderError::IncompleteGroup` when some but not all of the `group` headers are
+    /// present, for `#[headers(all_or_none(...))]`.
+    pub fn check_header_group(
+        headers: &http::HeaderMap,
+        group: &'static [&'static str],
+    ) -> Result<(), crate::HeaderError> {
+        let mut missing = group.iter().filter(|name| !headers.contains_key(**name));
+        match missing.next() {
+            Some(name) if missing.count() < group.len() - 1 => {
+                Err(crate::HeaderError::IncompleteGroup { name, group })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a header value as the `field` of the current span, for `#[header(trace)]`. Does
+    /// nothing without the `tracing` feature.
+    pub fn record_span_field(field: &'static str, value: &dyn std::fmt::Display) {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record(field, tracing::field::display(value));
+        #[cfg(not(feature = "tracing"))]
+        let _ = (field, value);
+    }
+
+    /// Logs a malformed header value, for `#[header(trace)]`. Does nothing without the `tracing`
+    /// feature.
+    pub fn warn_header_error(error: &crate::HeaderError) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(%error, "header extraction failed");
+        #[cfg(not(feature = "tracing"))]
+        let _ = error;
+    }
+}
 
 // Re-exports for convenience
 pub use axum;
+#[cfg(feature = "chrono")]
+pub use chrono;
+#[cfg(feature = "headers")]
+pub use headers;
 pub use http;
+#[cfg(feature = "utoipa")]
+pub use utoipa;
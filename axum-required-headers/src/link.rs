@@ -0,0 +1,140 @@
+//! Parsing of the `Link` header (RFC 8288).
+
+use std::str::FromStr;
+
+use crate::{OptionalHeader, RequiredHeader, syntax::Cursor};
+
+/// A single link of a `Link` header, e.g. `<https://api.example.com/items?page=2>; rel="next"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The target URI, without the surrounding `<>`
+    pub uri: String,
+    /// The `rel` parameter, possibly several space-separated relation types
+    pub rel: String,
+    /// The other parameters in order, with lowercase names and unquoted values (empty for
+    /// parameters without a value)
+    pub params: Vec<(String, String)>,
+}
+
+impl Link {
+    /// Whether `rel` is one of the link's relation types, compared case-insensitively.
+    pub fn has_rel(&self, rel: &str) -> bool {
+        self.rel
+            .split_ascii_whitespace()
+            .any(|own| own.eq_ignore_ascii_case(rel))
+    }
+
+    /// The value of the parameter `name`, compared case-insensitively.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The links of a `Link` header, in the order they were sent.
+///
+/// Commas and semicolons inside `<...>` URIs and quoted parameter values are handled; only the
+/// first `Link` header of a request is read. Links without a `rel` parameter are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Links, Optional};
+///
+/// async fn handler(Optional(links): Optional<Links>) -> String {
+///     match links.as_ref().and_then(|links| links.find("next")) {
+///         Some(next) => format!("next page: {}", next.uri),
+///         None => "last page".to_owned(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Links(pub Vec<Link>);
+
+impl Links {
+    /// The first link with the relation type `rel`, e.g. `"next"`.
+    pub fn find(&self, rel: &str) -> Option<&Link> {
+        self.0.iter().find(|link| link.has_rel(rel))
+    }
+}
+
+/// Error returned when a `Link` header can't be parsed.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid link: {0}")]
+pub struct ParseLinkError(String);
+
+impl FromStr for Links {
+    type Err = ParseLinkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Cursor::new(s);
+        let mut links = Vec::new();
+        loop {
+            parser.skip_whitespace();
+            if parser.eat(',') {
+                continue;
+            }
+            if parser.is_done() {
+                break;
+            }
+            links.push(link(&mut parser)?);
+            parser.skip_whitespace();
+            if !parser.is_done() && !parser.eat(',') {
+                return Err(ParseLinkError(parser.error("expected `,` between links")));
+            }
+        }
+        Ok(Links(links))
+    }
+}
+
+impl RequiredHeader for Links {
+    const HEADER_NAME: &'static str = "link";
+}
+
+impl OptionalHeader for Links {
+    const HEADER_NAME: &'static str = "link";
+}
+
+/// `"<" URI-Reference ">" *( OWS ";" OWS link-param )`
+fn link(parser: &mut Cursor<'_>) -> Result<Link, ParseLinkError> {
+    if !parser.eat('<') {
+        return Err(ParseLinkError(parser.error("expected `<`")));
+    }
+    let Some(end) = parser.rest().find('>') else {
+        return Err(ParseLinkError(parser.error("unterminated `<`")));
+    };
+    let uri = parser.rest()[..end].to_owned();
+    parser.advance(end + 1);
+
+    let mut rel = None;
+    let mut params = Vec::new();
+    loop {
+        parser.skip_whitespace();
+        if !parser.eat(';') {
+            break;
+        }
+        parser.skip_whitespace();
+        let name = parser.token().to_ascii_lowercase();
+        if name.is_empty() {
+            return Err(ParseLinkError(parser.error("expected a parameter name")));
+        }
+        parser.skip_whitespace();
+        let value = if parser.eat('=') {
+            parser.skip_whitespace();
+            parser.param_value().map_err(ParseLinkError)?
+        } else {
+            String::new()
+        };
+        // Only the first occurrence of `rel` counts
+        if name == "rel" {
+            rel.get_or_insert(value);
+        } else {
+            params.push((name, value));
+        }
+    }
+
+    let rel = rel.ok_or_else(|| ParseLinkError(format!("missing `rel` for `<{uri}>`")))?;
+    Ok(Link { uri, rel, params })
+}
@@ -0,0 +1,505 @@
+//! Content negotiation helpers for the `Accept` and `Accept-Charset` headers, and weighted lists
+//! of other `Accept-*` headers.
+
+use axum::response::{IntoResponseParts, ResponseParts};
+use http::{HeaderName, HeaderValue, header::VARY, request::Parts};
+use std::{convert::Infallible, str::FromStr};
+
+use crate::{OptionalHeader, RequiredHeader};
+
+/// A response format a handler is able to produce.
+///
+/// Used with [`AcceptedType::negotiate`] to pick the format that best matches
+/// the client's `Accept` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseFormat {
+    /// `application/json`
+    Json,
+    /// `application/xml`
+    Xml,
+    /// `text/html`
+    Html,
+    /// `text/plain`
+    PlainText,
+}
+
+impl ResponseFormat {
+    /// The media type of the format, e.g. `application/json`.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::Xml => "application/xml",
+            ResponseFormat::Html => "text/html",
+            ResponseFormat::PlainText => "text/plain",
+        }
+    }
+}
+
+/// A single media range of an `Accept` header, e.g. `text/html;q=0.9`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRange {
+    /// The type, e.g. `text` (or `*`)
+    pub type_: String,
+    /// The subtype, e.g. `html` (or `*`)
+    pub subtype: String,
+    /// The quality weight in `0.0..=1.0`, defaults to `1.0`
+    pub quality: f32,
+}
+
+impl MediaRange {
+    /// Returns how specifically this range matches `media_type`, or `None` if it doesn't.
+    ///
+    /// `*/*` matches with specificity 0, `type/*` with 1 and `type/subtype` with 2.
+    fn specificity(&self, media_type: &str) -> Option<u8> {
+        let (type_, subtype) = media_type.split_once('/')?;
+        match (self.type_.as_str(), self.subtype.as_str()) {
+            ("*", "*") => Some(0),
+            (t, "*") if t.eq_ignore_ascii_case(type_) => Some(1),
+            (t, s) if t.eq_ignore_ascii_case(type_) && s.eq_ignore_ascii_case(subtype) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned when an `Accept` or `Accept-*` header can't be parsed.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid media range: `{0}`")]
+pub struct ParseAcceptError(String);
+
+/// Parses the `q` parameter among the parameters of an `Accept*` element, defaulting to `1.0`.
+fn parse_quality<'a>(
+    params: impl Iterator<Item = &'a str>,
+    invalid: impl Fn() -> ParseAcceptError,
+) -> Result<f32, ParseAcceptError> {
+    let mut quality = 1.0;
+    for param in params {
+        let Some((key, value)) = param.split_once('=') else {
+            continue;
+        };
+        if key.trim().eq_ignore_ascii_case("q") {
+            quality = parse_q(value).ok_or_else(&invalid)?;
+        }
+    }
+    Ok(quality)
+}
+
+/// Parses the value of a `q` parameter, a weight between `0` and `1`.
+fn parse_q(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|q| (0.0..=1.0).contains(q))
+}
+
+/// The media ranges accepted by the client, parsed from the `Accept` header.
+///
+/// Media ranges are kept in the order they were sent; use [`AcceptedType::negotiate`]
+/// to pick the best supported [`ResponseFormat`].
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{AcceptedType, ResponseFormat};
+/// use axum_required_headers::http::request::Parts;
+///
+/// async fn handler(parts: Parts) -> &'static str {
+///     match AcceptedType::negotiate(&parts, &[ResponseFormat::Json, ResponseFormat::Xml]) {
+///         Some(ResponseFormat::Xml) => "<ok/>",
+///         _ => "{\"ok\":true}",
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptedType(pub Vec<MediaRange>);
+
+impl AcceptedType {
+    /// Picks the best of the `supported` formats for the request's `Accept` header.
+    ///
+    /// For each supported format the most specific matching media range decides its quality.
+    /// The format with the highest quality wins, ties are broken by the order of `supported`.
+    /// Formats with a quality of `0` are never chosen. A missing or unparseable `Accept` header
+    /// is treated as `*/*`.
+    pub fn negotiate(parts: &Parts, supported: &[ResponseFormat]) -> Option<ResponseFormat> {
+        let accepted = parts
+            .headers
+            .get(http::header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<AcceptedType>().ok());
+
+        match accepted {
+            Some(accepted) => accepted.best(supported),
+            None => supported.first().copied(),
+        }
+    }
+
+    /// Picks the best of the `supported` formats for these media ranges.
+    ///
+    /// See [`AcceptedType::negotiate`] for the selection rules.
+    pub fn best(&self, supported: &[ResponseFormat]) -> Option<ResponseFormat> {
+        let mut best: Option<(ResponseFormat, f32)> = None;
+        for format in supported {
+            let Some(quality) = self.quality_of(format.media_type()) else {
+                continue;
+            };
+            if quality <= 0.0 {
+                continue;
+            }
+            if best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((*format, quality));
+            }
+        }
+        best.map(|(format, _)| format)
+    }
+
+    /// The quality of the most specific media range matching `media_type`, if any.
+    pub fn quality_of(&self, media_type: &str) -> Option<f32> {
+        self.0
+            .iter()
+            .filter_map(|range| Some((range.specificity(media_type)?, range.quality)))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, quality)| quality)
+    }
+}
+
+impl FromStr for AcceptedType {
+    type Err = ParseAcceptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges = Vec::new();
+        for element in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || ParseAcceptError(element.to_owned());
+
+            let mut params = element.split(';').map(str::trim);
+            let media_range = params.next().ok_or_else(invalid)?;
+            let (type_, subtype) = media_range.split_once('/').ok_or_else(invalid)?;
+            if type_.is_empty() || subtype.is_empty() || (type_ == "*" && subtype != "*") {
+                return Err(invalid());
+            }
+
+            let quality = parse_quality(params, invalid)?;
+
+            ranges.push(MediaRange {
+                type_: type_.to_owned(),
+                subtype: subtype.to_owned(),
+                quality,
+            });
+        }
+        Ok(AcceptedType(ranges))
+    }
+}
+
+impl RequiredHeader for AcceptedType {
+    const HEADER_NAME: &'static str = "accept";
+}
+
+impl OptionalHeader for AcceptedType {
+    const HEADER_NAME: &'static str = "accept";
+}
+
+/// A single charset of an `Accept-Charset` header, e.g. `utf-8;q=0.9`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharsetRange {
+    /// The charset name, e.g. `utf-8` (or `*`)
+    pub charset: String,
+    /// The quality weight in `0.0..=1.0`, defaults to `1.0`
+    pub quality: f32,
+}
+
+/// The charsets accepted by the client, parsed from the `Accept-Charset` header.
+///
+/// A missing header means any charset is acceptable, which is what [`AcceptCharset::default`]
+/// represents.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{AcceptCharset, Optional};
+///
+/// async fn handler(Optional(accept): Optional<AcceptCharset>) -> String {
+///     let charset = accept
+///         .unwrap_or_default()
+///         .best(&["utf-8", "iso-8859-1"])
+///         .unwrap_or_else(|| "utf-8".to_owned());
+///     format!("charset: {charset}")
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AcceptCharset(pub Vec<CharsetRange>);
+
+impl AcceptCharset {
+    /// Picks the best of the `supported` charsets.
+    ///
+    /// A supported charset listed explicitly gets its own quality, any other one the quality of
+    /// `*` (or is unacceptable without `*`). The charset with the highest quality wins, ties are
+    /// broken by the order of `supported`, and charsets with a quality of `0` are never chosen.
+    /// Charset names are compared case-insensitively.
+    pub fn best(&self, supported: &[&str]) -> Option<String> {
+        let mut best: Option<(&str, f32)> = None;
+        for charset in supported {
+            let Some(quality) = self.quality_of(charset) else {
+                continue;
+            };
+            if quality <= 0.0 {
+                continue;
+            }
+            if best.is_none_or(|(_, best_quality)| quality > best_quality) {
+                best = Some((charset, quality));
+            }
+        }
+        best.map(|(charset, _)| charset.to_owned())
+    }
+
+    /// The quality of `charset`, from its explicit entry or else from `*`.
+    pub fn quality_of(&self, charset: &str) -> Option<f32> {
+        let explicit = self
+            .0
+            .iter()
+            .find(|range| range.charset.eq_ignore_ascii_case(charset));
+        explicit
+            .or_else(|| self.0.iter().find(|range| range.charset == "*"))
+            .map(|range| range.quality)
+    }
+}
+
+impl Default for AcceptCharset {
+    /// Accepts any charset, like a missing `Accept-Charset` header.
+    fn default() -> Self {
+        AcceptCharset(vec![CharsetRange {
+            charset: "*".to_owned(),
+            quality: 1.0,
+        }])
+    }
+}
+
+impl FromStr for AcceptCharset {
+    type Err = ParseAcceptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges = Vec::new();
+        for element in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || ParseAcceptError(element.to_owned());
+
+            let mut params = element.split(';').map(str::trim);
+            let charset = params
+                .next()
+                .filter(|c| !c.is_empty())
+                .ok_or_else(invalid)?;
+            let quality = parse_quality(params, invalid)?;
+
+            ranges.push(CharsetRange {
+                charset: charset.to_owned(),
+                quality,
+            });
+        }
+        Ok(AcceptCharset(ranges))
+    }
+}
+
+impl RequiredHeader for AcceptCharset {
+    const HEADER_NAME: &'static str = "accept-charset";
+}
+
+impl OptionalHeader for AcceptCharset {
+    const HEADER_NAME: &'static str = "accept-charset";
+}
+
+/// An element of a [`QualityList`] with its weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quality<T> {
+    /// The element, parsed without its `q` parameter
+    pub value: T,
+    /// The quality weight in `0.0..=1.0`, defaults to `1.0`
+    pub quality: f32,
+}
+
+/// A comma-separated list of `T` weighted by `q` parameters, e.g. the `gzip, br;q=0.9, *;q=0.1`
+/// of `Accept-Encoding`, ordered by descending quality.
+///
+/// Each element is parsed into `T` with `FromStr` after removing its `q` parameter, so other
+/// parameters (`text/html;level=1`) are passed on. Elements of equal quality keep the order they
+/// were sent in. A `q` outside `0..=1`, or not a number, fails to parse rather than being
+/// clamped, like an element `T` can't parse. Empty elements are skipped.
+///
+/// With `T: RequiredHeader` (or `OptionalHeader`), the list is read from the header of `T`, so a
+/// `Header` type extracts its own weighted list as `Required<QualityList<T>>`.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Header, QualityList, Required};
+///
+/// #[derive(Header, Debug, Clone, Copy, PartialEq)]
+/// #[header("accept-encoding")]
+/// enum Encoding {
+///     Gzip,
+///     Br,
+///     Identity,
+/// }
+///
+/// async fn handler(Required(encodings): Required<QualityList<Encoding>>) -> String {
+///     format!("{:?}", encodings.preferred().next())
+/// }
+///
+/// let encodings: QualityList<Encoding> = "gzip;q=0.8, br, identity;q=0".parse().unwrap();
+/// assert_eq!(
+///     encodings.preferred().collect::<Vec<_>>(),
+///     [&Encoding::Br, &Encoding::Gzip]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityList<T>(Vec<Quality<T>>);
+
+impl<T> QualityList<T> {
+    /// Every element with its quality, by descending quality.
+    pub fn items(&self) -> &[Quality<T>] {
+        &self.0
+    }
+
+    /// The acceptable elements, by descending preference.
+    ///
+    /// Elements with a quality of `0` are explicitly unacceptable and left out.
+    pub fn preferred(&self) -> impl Iterator<Item = &T> {
+        self.0
+            .iter()
+            .filter(|item| item.quality > 0.0)
+            .map(|item| &item.value)
+    }
+
+    /// Whether the list has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Error returned when an element of a [`QualityList`] can't be parsed.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid quality list element: `{0}`")]
+pub struct ParseQualityListError(String);
+
+impl<T: FromStr> FromStr for QualityList<T> {
+    type Err = ParseQualityListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut items = Vec::new();
+        for element in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || ParseQualityListError(element.to_owned());
+
+            let mut quality = 1.0;
+            let mut value = Vec::new();
+            for param in element.split(';').map(str::trim) {
+                match param.split_once('=') {
+                    Some((key, q)) if key.trim().eq_ignore_ascii_case("q") => {
+                        quality = parse_q(q).ok_or_else(invalid)?;
+                    }
+                    _ => value.push(param),
+                }
+            }
+
+            let value = value.join(";").parse().map_err(|_| invalid())?;
+            items.push(Quality { value, quality });
+        }
+        // A stable sort keeps the order of elements of equal quality
+        items.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+        Ok(QualityList(items))
+    }
+}
+
+impl<T: RequiredHeader> RequiredHeader for QualityList<T> {
+    const HEADER_NAME: &'static str = T::HEADER_NAME;
+    const HEADER_ALIASES: &'static [&'static str] = T::HEADER_ALIASES;
+    const EMPTY_IS_MISSING: bool = T::EMPTY_IS_MISSING;
+}
+
+impl<T: OptionalHeader> OptionalHeader for QualityList<T> {
+    const HEADER_NAME: &'static str = T::HEADER_NAME;
+    const HEADER_ALIASES: &'static [&'static str] = T::HEADER_ALIASES;
+    const EMPTY_IS_MISSING: bool = T::EMPTY_IS_MISSING;
+}
+
+/// Records the request headers that influenced a response and emits them as a `Vary` header.
+///
+/// Responses whose content depends on negotiated request headers (e.g. `Accept`) must list
+/// those headers in `Vary` so caches don't serve the wrong representation. Names already
+/// present in the response's `Vary` header are merged rather than duplicated.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{AcceptedType, ResponseFormat, Vary};
+/// use axum_required_headers::http::request::Parts;
+///
+/// async fn handler(parts: Parts) -> (Vary, &'static str) {
+///     let body = match AcceptedType::negotiate(&parts, &[ResponseFormat::Json, ResponseFormat::Xml]) {
+///         Some(ResponseFormat::Xml) => "<ok/>",
+///         _ => "{\"ok\":true}",
+///     };
+///     (Vary::accept(), body)
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vary(Vec<HeaderName>);
+
+impl Vary {
+    /// Creates an empty `Vary`, which emits nothing until a header name is added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A `Vary` listing the `Accept` header, for responses negotiated with [`AcceptedType`].
+    pub fn accept() -> Self {
+        Self::new().with(http::header::ACCEPT)
+    }
+
+    /// Adds a request header name that influenced the response.
+    pub fn with(mut self, name: HeaderName) -> Self {
+        self.insert(name);
+        self
+    }
+
+    /// Adds a request header name that influenced the response.
+    pub fn insert(&mut self, name: HeaderName) {
+        if !self.0.contains(&name) {
+            self.0.push(name);
+        }
+    }
+
+    /// The recorded header names, in insertion order.
+    pub fn header_names(&self) -> &[HeaderName] {
+        &self.0
+    }
+}
+
+impl IntoResponseParts for Vary {
+    type Error = Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if self.0.is_empty() {
+            return Ok(res);
+        }
+
+        let mut names: Vec<String> = res
+            .headers()
+            .get_all(VARY)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|name| name.trim().to_owned())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        // `*` already covers every request header
+        if !names.iter().any(|name| name == "*") {
+            for name in &self.0 {
+                if !names.iter().any(|n| n.eq_ignore_ascii_case(name.as_str())) {
+                    names.push(name.as_str().to_owned());
+                }
+            }
+        }
+
+        // Joined header names are always a valid header value
+        let value = HeaderValue::from_str(&names.join(", ")).expect("valid header names");
+        res.headers_mut().insert(VARY, value);
+        Ok(res)
+    }
+}
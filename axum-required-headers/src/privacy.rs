@@ -0,0 +1,76 @@
+//! Typed privacy signals from the `DNT` and `Sec-GPC` headers.
+
+use std::{convert::Infallible, str::FromStr};
+
+use crate::OptionalHeader;
+
+/// The `DNT` (Do Not Track) header.
+///
+/// `1` means the user opted out of tracking (`Some(true)`), `0` that they consented to it
+/// (`Some(false)`). Any other value expresses no preference and parses as `None` rather than
+/// rejecting the request, like an absent header.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{DoNotTrack, Optional};
+///
+/// async fn handler(Optional(dnt): Optional<DoNotTrack>) -> &'static str {
+///     match dnt.and_then(|dnt| dnt.0) {
+///         Some(true) => "tracking disabled",
+///         Some(false) => "tracking allowed",
+///         None => "no preference",
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DoNotTrack(pub Option<bool>);
+
+impl FromStr for DoNotTrack {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "1" => DoNotTrack(Some(true)),
+            "0" => DoNotTrack(Some(false)),
+            _ => DoNotTrack(None),
+        })
+    }
+}
+
+impl OptionalHeader for DoNotTrack {
+    const HEADER_NAME: &'static str = "dnt";
+}
+
+/// The `Sec-GPC` (Global Privacy Control) header.
+///
+/// Only `1` signals the user's opt-out (`true`); `0` or any other value parses as `false`, the
+/// same as an absent header.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{GlobalPrivacyControl, Optional};
+///
+/// async fn handler(Optional(gpc): Optional<GlobalPrivacyControl>) -> &'static str {
+///     if gpc.unwrap_or_default().0 {
+///         "do not sell or share"
+///     } else {
+///         "no signal"
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlobalPrivacyControl(pub bool);
+
+impl FromStr for GlobalPrivacyControl {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(GlobalPrivacyControl(s.trim() == "1"))
+    }
+}
+
+impl OptionalHeader for GlobalPrivacyControl {
+    const HEADER_NAME: &'static str = "sec-gpc";
+}
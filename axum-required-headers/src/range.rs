@@ -0,0 +1,215 @@
+//! Parsing of the `Range` header (RFC 9110, section 14.2), generic over the range unit.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::{OptionalHeader, RequiredHeader};
+
+/// A range unit accepted by [`Range`], identified by its token.
+///
+/// Implement it on a marker type to accept a custom unit:
+///
+/// ```
+/// use axum_required_headers::{Range, RangeUnit};
+///
+/// struct Items;
+///
+/// impl RangeUnit for Items {
+///     const UNIT: &'static str = "items";
+/// }
+///
+/// let range: Range<Items> = "items=0-24".parse().unwrap();
+/// assert_eq!(range.ranges().len(), 1);
+/// assert!("bytes=0-24".parse::<Range<Items>>().is_err());
+/// ```
+pub trait RangeUnit {
+    /// The unit token, compared case-insensitively, e.g. `"bytes"`
+    const UNIT: &'static str;
+}
+
+/// The `bytes` range unit, the only one registered by RFC 9110.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteUnit;
+
+impl RangeUnit for ByteUnit {
+    const UNIT: &'static str = "bytes";
+}
+
+/// A single range of a `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeSpec {
+    /// `first-last`, both inclusive, e.g. `0-499`
+    FromTo(u64, u64),
+    /// `first-`, everything from `first` on, e.g. `500-`
+    From(u64),
+    /// `-length`, the last `length` units, e.g. `-500`
+    Suffix(u64),
+}
+
+impl RangeSpec {
+    /// The half-open range this spec selects in a representation of `len` units, `None` when it
+    /// is unsatisfiable.
+    ///
+    /// Last positions past the end are clamped to it, as are suffixes longer than `len`.
+    pub fn resolve(&self, len: u64) -> Option<std::ops::Range<u64>> {
+        match *self {
+            RangeSpec::FromTo(first, last) if first < len => {
+                Some(first..last.saturating_add(1).min(len))
+            }
+            RangeSpec::From(first) if first < len => Some(first..len),
+            RangeSpec::Suffix(length) if length > 0 && len > 0 => {
+                Some(len.saturating_sub(length)..len)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The `Range` header of a request, in the unit `U` ([`ByteUnit`] by default).
+///
+/// Values in another unit are rejected, e.g. `items=0-9` as `Range<ByteUnit>`. Every range spec is
+/// kept in the order sent; positions beyond the representation are only detected when resolving
+/// against its length.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{ByteRanges, Optional};
+///
+/// async fn handler(Optional(range): Optional<ByteRanges>) -> String {
+///     let len = 10_000;
+///     match range {
+///         Some(range) => format!("parts: {:?}", range.satisfiable(len)),
+///         None => "full body".to_owned(),
+///     }
+/// }
+/// ```
+pub struct Range<U = ByteUnit> {
+    ranges: Vec<RangeSpec>,
+    unit: PhantomData<fn() -> U>,
+}
+
+/// The `Range` header in `bytes`, the common case.
+pub type ByteRanges = Range<ByteUnit>;
+
+impl<U: RangeUnit> Range<U> {
+    /// The range specs in the order they were sent, never empty.
+    pub fn ranges(&self) -> &[RangeSpec] {
+        &self.ranges
+    }
+
+    /// The unit token of `U`.
+    pub fn unit(&self) -> &'static str {
+        U::UNIT
+    }
+}
+
+impl Range<ByteUnit> {
+    /// The satisfiable byte ranges of a representation of `len` bytes, as half-open ranges in the
+    /// order sent. Empty when no range is satisfiable, which calls for `416 Range Not
+    /// Satisfiable`.
+    pub fn satisfiable(&self, len: u64) -> Vec<std::ops::Range<u64>> {
+        self.ranges
+            .iter()
+            .filter_map(|spec| spec.resolve(len))
+            .collect()
+    }
+}
+
+impl<U> fmt::Debug for Range<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Range").field(&self.ranges).finish()
+    }
+}
+
+impl<U> Clone for Range<U> {
+    fn clone(&self) -> Self {
+        Self {
+            ranges: self.ranges.clone(),
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<U> PartialEq for Range<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges == other.ranges
+    }
+}
+
+impl<U> Eq for Range<U> {}
+
+/// Error returned when a `Range` header can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseRangeError {
+    /// The header uses another unit than the expected one
+    #[error("unexpected range unit `{found}`, expected `{expected}`")]
+    UnitMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// The value isn't a `unit=range-set`, or a range spec is malformed
+    #[error("invalid range `{0}`")]
+    Invalid(String),
+}
+
+impl<U: RangeUnit> FromStr for Range<U> {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseRangeError::Invalid(s.to_owned());
+        let (unit, range_set) = s.split_once('=').ok_or_else(invalid)?;
+        if !unit.eq_ignore_ascii_case(U::UNIT) {
+            return Err(ParseRangeError::UnitMismatch {
+                expected: U::UNIT,
+                found: unit.to_owned(),
+            });
+        }
+
+        // `1#range-spec`: empty list elements are allowed, but at least one spec is required
+        let ranges = range_set
+            .split(',')
+            .map(|spec| spec.trim_matches([' ', '\t']))
+            .filter(|spec| !spec.is_empty())
+            .map(|spec| parse_spec(spec).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        if ranges.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            ranges,
+            unit: PhantomData,
+        })
+    }
+}
+
+fn parse_spec(spec: &str) -> Option<RangeSpec> {
+    let (first, last) = spec.split_once('-')?;
+    match (parse_position(first), last) {
+        (None, _) if first.is_empty() => parse_position(last).map(RangeSpec::Suffix),
+        (Some(first), "") => Some(RangeSpec::From(first)),
+        (Some(first), last) => {
+            let last = parse_position(last)?;
+            (first <= last).then_some(RangeSpec::FromTo(first, last))
+        }
+        (None, _) => None,
+    }
+}
+
+/// Parses `1*DIGIT`, rejecting signs and whitespace that `u64::from_str` would accept
+fn parse_position(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl<U: RangeUnit> RequiredHeader for Range<U> {
+    const HEADER_NAME: &'static str = "range";
+}
+
+impl<U: RangeUnit> OptionalHeader for Range<U> {
+    const HEADER_NAME: &'static str = "range";
+}
@@ -0,0 +1,304 @@
+//! A configurable rejection wrapping [`HeaderError`].
+
+#[cfg(feature = "json")]
+use axum::Json;
+use axum::{
+    http::{
+        HeaderMap, HeaderName, HeaderValue, StatusCode,
+        header::{CONTENT_TYPE, LOCATION},
+    },
+    response::{IntoResponse, Response},
+};
+use http::request::Parts;
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+use crate::{AcceptedType, HeaderError, ResponseFormat};
+
+/// The formats a [`HeaderRejection`] can render its body in, in order of preference.
+const ERROR_FORMATS: &[ResponseFormat] = &[
+    ResponseFormat::Json,
+    ResponseFormat::Html,
+    ResponseFormat::PlainText,
+];
+
+/// The response header listing the required headers of an endpoint, see
+/// [`HeaderRejection::with_required_headers`].
+pub const X_REQUIRED_HEADERS: HeaderName = HeaderName::from_static("x-required-headers");
+
+/// A [`HeaderError`] together with how it should be rendered as a response.
+///
+/// `IntoResponse::into_response` has no access to the request, so anything request dependent
+/// (like the client's `Accept` header) has to be captured when the rejection is created.
+///
+/// The `Headers` derive uses `HeaderRejection` as its rejection when rendering options are
+/// given, e.g. `#[headers(negotiate_error)]` captures the negotiated error format during
+/// extraction.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{HeaderError, HeaderRejection};
+/// use axum_required_headers::http::request::Parts;
+///
+/// fn reject(parts: &Parts) -> HeaderRejection {
+///     // Renders as JSON, HTML or plain text depending on the request's `Accept` header
+///     HeaderRejection::negotiate(HeaderError::Missing("x-user-id"), parts)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HeaderRejection {
+    error: HeaderError,
+    format: ResponseFormat,
+    required_headers: &'static [&'static str],
+    status: Option<StatusCode>,
+    missing_status: Option<StatusCode>,
+    missing_redirect: Option<HeaderValue>,
+    headers: HeaderMap,
+    #[cfg(feature = "json")]
+    json_body: Option<serde_json::Value>,
+}
+
+impl HeaderRejection {
+    /// Creates a rejection rendering `error` exactly like [`HeaderError`] does.
+    pub fn new(error: HeaderError) -> Self {
+        Self {
+            error,
+            format: ResponseFormat::Json,
+            required_headers: &[],
+            status: None,
+            missing_status: None,
+            missing_redirect: None,
+            headers: HeaderMap::new(),
+            #[cfg(feature = "json")]
+            json_body: None,
+        }
+    }
+
+    /// Creates a rejection whose body format is negotiated from the request's `Accept` header.
+    ///
+    /// JSON, HTML and plain text bodies are supported, JSON is used when the client
+    /// accepts none of them.
+    pub fn negotiate(error: HeaderError, parts: &Parts) -> Self {
+        let format = AcceptedType::negotiate(parts, ERROR_FORMATS).unwrap_or(ResponseFormat::Json);
+        Self::new(error).with_format(format)
+    }
+
+    /// Sets the body format, `ResponseFormat::Xml` is rendered as JSON (and JSON as plain text
+    /// without the `json` feature).
+    pub fn with_format(mut self, format: ResponseFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Lists the headers the endpoint requires in an `x-required-headers` response header
+    /// when the rejection is caused by (or includes) a missing header.
+    pub fn with_required_headers(mut self, names: &'static [&'static str]) -> Self {
+        self.required_headers = names;
+        self
+    }
+
+    /// Sets the response status, `400 Bad Request` by default.
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets the response status used when the rejection is caused by (or includes) a missing
+    /// header, e.g. `401 Unauthorized` for a missing `Authorization` header.
+    ///
+    /// Takes precedence over [`HeaderRejection::with_status`] for missing headers.
+    pub fn with_missing_status(mut self, status: StatusCode) -> Self {
+        self.missing_status = Some(status);
+        self
+    }
+
+    /// Redirects to `location` with `303 See Other` and an empty body when the rejection is
+    /// caused by (or includes) a missing header, e.g. to send clients without a session header
+    /// to a login page.
+    ///
+    /// Takes precedence over [`HeaderRejection::with_missing_status`]; other errors are rendered
+    /// as usual.
+    ///
+    /// ```
+    /// use axum_required_headers::{HeaderError, HeaderRejection};
+    /// use axum_required_headers::http::HeaderValue;
+    ///
+    /// let rejection = HeaderRejection::new(HeaderError::Missing("x-session"))
+    ///     .with_missing_redirect(HeaderValue::from_static("/login"));
+    /// ```
+    pub fn with_missing_redirect(mut self, location: HeaderValue) -> Self {
+        self.missing_redirect = Some(location);
+        self
+    }
+
+    /// Adds a header to the response, e.g. a `Link` to the documentation of the missing header.
+    ///
+    /// Added headers are merged into the response after it is rendered, replacing headers of the
+    /// same name; calling this repeatedly with one name sends every value.
+    ///
+    /// ```
+    /// use axum_required_headers::HeaderError;
+    /// use axum_required_headers::http::{HeaderValue, header::LINK};
+    ///
+    /// let rejection = HeaderError::Missing("idempotency-key").with_header(
+    ///     LINK,
+    ///     HeaderValue::from_static("<https://example.com/docs/idempotency>; rel=\"help\""),
+    /// );
+    /// ```
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Renders JSON bodies as the error DTO `T`, built from the error, instead of the default
+    /// `{"error": ..., "message": ...}` body, e.g. to fit an API wide error envelope.
+    ///
+    /// Only the body changes, the status is chosen as usual. HTML and plain text bodies are not
+    /// affected, and a DTO that fails to serialize falls back to the default body. Requires the
+    /// `json` feature.
+    ///
+    /// ```
+    /// use axum_required_headers::{HeaderError, HeaderRejection};
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct ErrorResponse {
+    ///     code: &'static str,
+    /// }
+    ///
+    /// impl From<&HeaderError> for ErrorResponse {
+    ///     fn from(_: &HeaderError) -> Self {
+    ///         ErrorResponse { code: "BAD_HEADERS" }
+    ///     }
+    /// }
+    ///
+    /// let rejection = HeaderRejection::new(HeaderError::Missing("x-user-id"))
+    ///     .with_error_dto::<ErrorResponse>();
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn with_error_dto<T>(mut self) -> Self
+    where
+        T: for<'a> From<&'a HeaderError> + Serialize,
+    {
+        self.json_body = serde_json::to_value(T::from(&self.error)).ok();
+        self
+    }
+
+    /// The status code of the response.
+    pub fn status(&self) -> StatusCode {
+        if self.redirect_location().is_some() {
+            return StatusCode::SEE_OTHER;
+        }
+        self.missing_status
+            .filter(|_| self.error.is_missing())
+            .or(self.status)
+            .unwrap_or(StatusCode::BAD_REQUEST)
+    }
+
+    /// The location to redirect to, when the error calls for the missing header redirect.
+    fn redirect_location(&self) -> Option<&HeaderValue> {
+        self.missing_redirect
+            .as_ref()
+            .filter(|_| self.error.is_missing())
+    }
+
+    /// The underlying error.
+    pub fn error(&self) -> &HeaderError {
+        &self.error
+    }
+
+    /// The format the body is rendered in.
+    pub fn format(&self) -> ResponseFormat {
+        self.format
+    }
+
+    /// Consumes the rejection, returning the underlying error.
+    pub fn into_error(self) -> HeaderError {
+        self.error
+    }
+}
+
+impl From<HeaderError> for HeaderRejection {
+    fn from(error: HeaderError) -> Self {
+        Self::new(error)
+    }
+}
+
+impl std::fmt::Display for HeaderRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for HeaderRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl IntoResponse for HeaderRejection {
+    fn into_response(mut self) -> Response {
+        let status = self.status();
+        let mut response = match self.redirect_location() {
+            Some(location) => (status, [(LOCATION, location.clone())]).into_response(),
+            None => self.render_body(status),
+        };
+
+        if self.error.is_missing()
+            && !self.required_headers.is_empty()
+            && let Ok(value) = HeaderValue::from_str(&self.required_headers.join(", "))
+        {
+            response.headers_mut().insert(X_REQUIRED_HEADERS, value);
+        }
+        response
+            .headers_mut()
+            .extend(std::mem::take(&mut self.headers));
+
+        response
+    }
+}
+
+impl HeaderRejection {
+    fn render_body(&self, status: StatusCode) -> Response {
+        match self.format {
+            #[cfg(feature = "json")]
+            ResponseFormat::Json | ResponseFormat::Xml => {
+                let body = self
+                    .json_body
+                    .clone()
+                    .unwrap_or_else(|| self.error.json_body());
+                (status, Json(body)).into_response()
+            }
+            #[cfg(not(feature = "json"))]
+            ResponseFormat::Json | ResponseFormat::Xml => {
+                (status, self.error.to_string()).into_response()
+            }
+            ResponseFormat::PlainText => (status, self.error.to_string()).into_response(),
+            ResponseFormat::Html => {
+                let reason = status.canonical_reason().unwrap_or_default();
+                let body = format!(
+                    "<!DOCTYPE html><html><head><title>{code} {reason}</title></head>\
+                     <body><h1>{reason}</h1><p>{message}</p></body></html>",
+                    code = status.as_u16(),
+                    message = escape_html(&self.error.to_string()),
+                );
+                (status, [(CONTENT_TYPE, "text/html; charset=utf-8")], body).into_response()
+            }
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
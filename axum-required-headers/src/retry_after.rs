@@ -0,0 +1,72 @@
+//! Parsing of the `Retry-After` header (RFC 9110, section 10.2.3).
+
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use crate::OptionalHeader;
+
+/// The `Retry-After` header, either a delay in seconds or an HTTP-date.
+///
+/// The value is parsed as a number of seconds first, then as an HTTP-date (IMF-fixdate, or the
+/// obsolete RFC 850 and asctime formats); it is only rejected when neither matches. Use
+/// [`RetryAfter::retry_at`] to get an absolute time for both forms.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{Optional, RetryAfter};
+/// use std::time::SystemTime;
+///
+/// async fn handler(Optional(retry_after): Optional<RetryAfter>) -> String {
+///     match retry_after {
+///         Some(retry_after) => format!("retry at {:?}", retry_after.retry_at(SystemTime::now())),
+///         None => "retry whenever".to_owned(),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// A delay relative to when the message was received, e.g. `Retry-After: 120`
+    Delay(Duration),
+    /// An absolute time, e.g. `Retry-After: Fri, 31 Dec 1999 23:59:59 GMT`
+    Date(SystemTime),
+}
+
+impl RetryAfter {
+    /// The time to retry at, resolving a delay relative to `now`.
+    ///
+    /// Delays too large to be represented saturate to `now`.
+    pub fn retry_at(&self, now: SystemTime) -> SystemTime {
+        match *self {
+            RetryAfter::Delay(delay) => now.checked_add(delay).unwrap_or(now),
+            RetryAfter::Date(date) => date,
+        }
+    }
+}
+
+/// Error returned when a `Retry-After` header is neither a number of seconds nor an HTTP-date.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid retry-after value `{0}`, expected a number of seconds or an HTTP-date")]
+pub struct ParseRetryAfterError(String);
+
+impl FromStr for RetryAfter {
+    type Err = ParseRetryAfterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `delay-seconds = 1*DIGIT`, so signs and fractions fall through to the date parser
+        if !s.is_empty()
+            && s.bytes().all(|b| b.is_ascii_digit())
+            && let Ok(seconds) = s.parse()
+        {
+            return Ok(RetryAfter::Delay(Duration::from_secs(seconds)));
+        }
+
+        httpdate::parse_http_date(s)
+            .map(RetryAfter::Date)
+            .map_err(|_| ParseRetryAfterError(s.to_owned()))
+    }
+}
+
+impl OptionalHeader for RetryAfter {
+    const HEADER_NAME: &'static str = "retry-after";
+}
@@ -0,0 +1,149 @@
+//! Header extraction configured at runtime instead of with `#[derive(Headers)]`.
+
+use std::{collections::HashMap, sync::Arc};
+
+use axum::extract::{FromRef, FromRequestParts};
+use http::{HeaderMap, HeaderName, header::InvalidHeaderName, request::Parts};
+
+use crate::{HeaderError, extractors::find_header};
+
+/// A set of required and optional headers, for routes whose headers are only known at runtime
+/// (e.g. configured by plugins).
+///
+/// Build one with [`HeaderSchema::builder`], then either call [`HeaderSchema::extract`] or add it
+/// to the router state and extract [`SchemaHeaders`]. Cloning is cheap.
+///
+/// `HeaderError` holds `&'static str` header names, so the names of a schema are leaked when it
+/// is built: build schemas once at startup rather than per request.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::{HeaderSchema, SchemaHeaders};
+/// use axum_required_headers::axum::{Router, routing::get};
+///
+/// async fn handler(SchemaHeaders(headers): SchemaHeaders) -> String {
+///     format!("id: {}, hint: {:?}", headers["x-id"], headers.get("x-hint"))
+/// }
+///
+/// let schema = HeaderSchema::builder()
+///     .require("x-id")
+///     .optional("x-hint")
+///     .build()
+///     .unwrap();
+/// let app: Router = Router::new().route("/", get(handler)).with_state(schema);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeaderSchema {
+    headers: Arc<[SchemaHeader]>,
+}
+
+#[derive(Debug)]
+struct SchemaHeader {
+    name: &'static str,
+    header_name: HeaderName,
+    required: bool,
+}
+
+impl HeaderSchema {
+    /// Starts building a schema without any headers.
+    pub fn builder() -> HeaderSchemaBuilder {
+        HeaderSchemaBuilder::default()
+    }
+
+    /// Extracts the headers of the schema, keyed by their lowercase names.
+    ///
+    /// A missing required header fails with [`HeaderError::Missing`], a value that isn't valid
+    /// ASCII with [`HeaderError::InvalidValue`]. Absent optional headers are left out of the map.
+    pub fn extract(&self, headers: &HeaderMap) -> Result<HashMap<String, String>, HeaderError> {
+        let mut values = HashMap::with_capacity(self.headers.len());
+        for header in self.headers.iter() {
+            let Some(value) =
+                find_header(headers, header.name, Some(&header.header_name), &[], false)
+            else {
+                if header.required {
+                    return Err(HeaderError::Missing(header.name));
+                }
+                continue;
+            };
+            let value = value
+                .to_str()
+                .map_err(|_| HeaderError::InvalidValue(header.name))?;
+            values.insert(header.name.to_owned(), value.to_owned());
+        }
+        Ok(values)
+    }
+
+    /// The names of the headers whose absence fails extraction.
+    pub fn required_header_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.headers
+            .iter()
+            .filter(|header| header.required)
+            .map(|header| header.name)
+    }
+}
+
+/// Builder for a [`HeaderSchema`], created with [`HeaderSchema::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct HeaderSchemaBuilder {
+    headers: Vec<(String, bool)>,
+}
+
+impl HeaderSchemaBuilder {
+    /// Adds a header whose absence fails extraction.
+    pub fn require(mut self, name: impl Into<String>) -> Self {
+        self.headers.push((name.into(), true));
+        self
+    }
+
+    /// Adds a header that may be absent.
+    pub fn optional(mut self, name: impl Into<String>) -> Self {
+        self.headers.push((name.into(), false));
+        self
+    }
+
+    /// Validates the header names and builds the schema.
+    ///
+    /// Names are case-insensitive. A header added more than once is required if any of the
+    /// additions requires it.
+    pub fn build(self) -> Result<HeaderSchema, InvalidHeaderName> {
+        let mut headers: Vec<SchemaHeader> = Vec::with_capacity(self.headers.len());
+        for (name, required) in self.headers {
+            let name = HeaderName::try_from(name)?;
+            match headers
+                .iter_mut()
+                .find(|header| header.name == name.as_str())
+            {
+                Some(header) => header.required |= required,
+                None => headers.push(SchemaHeader {
+                    name: String::leak(name.as_str().to_owned()),
+                    header_name: name,
+                    required,
+                }),
+            }
+        }
+        Ok(HeaderSchema {
+            headers: headers.into(),
+        })
+    }
+}
+
+/// The headers of the [`HeaderSchema`] in the router state, keyed by their lowercase names.
+///
+/// Rejects the request with the [`HeaderError`] of [`HeaderSchema::extract`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaHeaders(pub HashMap<String, String>);
+
+impl<S> FromRequestParts<S> for SchemaHeaders
+where
+    HeaderSchema: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        HeaderSchema::from_ref(state)
+            .extract(&parts.headers)
+            .map(SchemaHeaders)
+    }
+}
@@ -0,0 +1,94 @@
+//! Building blocks shared by the parsers of structured header values.
+
+/// A cursor over a header value made of tokens, quoted strings and separators (RFC 9110,
+/// section 5.6).
+pub(crate) struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    pub(crate) fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    /// Advances past `len` bytes of [`Cursor::rest`].
+    pub(crate) fn advance(&mut self, len: usize) {
+        self.pos += len;
+    }
+
+    pub(crate) fn eat(&mut self, c: char) -> bool {
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    }
+
+    /// The message of a parse error at the current position.
+    pub(crate) fn error(&self, reason: &str) -> String {
+        format!("{reason} at `{}`", self.rest())
+    }
+
+    pub(crate) fn token(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// `token / quoted-string`, unescaping quoted strings
+    pub(crate) fn param_value(&mut self) -> Result<String, String> {
+        if !self.eat('"') {
+            return Ok(self.token().to_owned());
+        }
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        Err(self.error("unterminated quoted string"))
+    }
+}
+
+/// Decodes `%XX` escapes, `None` when an escape is truncated or not hexadecimal.
+pub(crate) fn percent_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut input = encoded.bytes();
+    while let Some(b) = input.next() {
+        if b == b'%' {
+            let hex = [input.next()?, input.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    Some(bytes)
+}
@@ -0,0 +1,109 @@
+//! Helpers for testing header extraction.
+//!
+//! Enabled with the `testing` feature.
+
+use axum::extract::FromRequestParts;
+use axum::response::{IntoResponse, Response};
+use http::{HeaderMap, Request, request::Parts};
+use std::collections::BTreeMap;
+
+/// A sorted, deterministic view of the request headers for snapshot assertions.
+///
+/// Header names are lowercase (as normalized by `HeaderMap`) and sorted. Repeated headers are
+/// joined with `", "` in the order they were received. Values that are not valid ASCII are
+/// skipped.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::http::Request;
+/// use axum_required_headers::testing::snapshot;
+///
+/// let (parts, _) = Request::builder()
+///     .header("X-User-Id", "user123")
+///     .header("accept", "text/html")
+///     .body(())
+///     .unwrap()
+///     .into_parts();
+///
+/// let snapshot = snapshot(&parts);
+/// assert_eq!(
+///     snapshot.into_iter().collect::<Vec<_>>(),
+///     [
+///         ("accept".to_string(), "text/html".to_string()),
+///         ("x-user-id".to_string(), "user123".to_string()),
+///     ]
+/// );
+/// ```
+pub fn snapshot(parts: &Parts) -> BTreeMap<String, String> {
+    let mut snapshot = BTreeMap::new();
+    for name in parts.headers.keys() {
+        let values: Vec<&str> = parts
+            .headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect();
+        if !values.is_empty() {
+            snapshot.insert(name.as_str().to_owned(), values.join(", "));
+        }
+    }
+    snapshot
+}
+
+/// Runs the extractor `T` against `headers`, turning a rejection into the response axum would
+/// send.
+///
+/// Tests can then assert the status and body of a rejection without a router. The request has
+/// no URI, method or extensions besides the headers.
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use axum_required_headers::Headers;
+/// use axum_required_headers::http::{HeaderMap, StatusCode};
+/// use axum_required_headers::testing::extract_response;
+///
+/// #[derive(Headers)]
+/// struct AppHeaders {
+///     #[header("x-user-id")]
+///     user_id: String,
+/// }
+///
+/// let mut headers = HeaderMap::new();
+/// let response = extract_response::<AppHeaders>(&headers).await.err().unwrap();
+/// assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+///
+/// headers.insert("x-user-id", "user123".parse().unwrap());
+/// let app_headers = extract_response::<AppHeaders>(&headers).await.ok().unwrap();
+/// assert_eq!(app_headers.user_id, "user123");
+/// # }
+/// ```
+#[allow(clippy::result_large_err)] // a test helper, boxing the response would only add noise
+pub async fn extract_response<T>(headers: &HeaderMap) -> Result<T, Response>
+where
+    T: FromRequestParts<()>,
+{
+    extract_response_with_state(headers, &()).await
+}
+
+/// [`extract_response`] for extractors needing the router state, e.g. `Headers` structs with
+/// `validate_with`.
+#[allow(clippy::result_large_err)]
+pub async fn extract_response_with_state<T, S>(
+    headers: &HeaderMap,
+    state: &S,
+) -> Result<T, Response>
+where
+    T: FromRequestParts<S>,
+    S: Send + Sync,
+{
+    let (mut parts, ()) = Request::new(()).into_parts();
+    parts.headers = headers.clone();
+
+    T::from_request_parts(&mut parts, state)
+        .await
+        .map_err(IntoResponse::into_response)
+}
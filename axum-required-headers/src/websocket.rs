@@ -0,0 +1,144 @@
+//! Validation of the WebSocket upgrade handshake headers.
+
+use axum::extract::FromRequestParts;
+use http::{HeaderMap, header::SEC_WEBSOCKET_PROTOCOL, request::Parts};
+
+use crate::HeaderError;
+
+/// The only WebSocket protocol version defined by RFC 6455.
+const SUPPORTED_VERSION: &str = "13";
+
+/// The validated headers of a WebSocket upgrade handshake (RFC 6455, section 4.2.1).
+///
+/// Extraction checks that:
+/// - `Connection` contains the `upgrade` token
+/// - `Upgrade` contains the `websocket` token
+/// - `Sec-WebSocket-Key` is the base64 encoding of 16 bytes
+/// - `Sec-WebSocket-Version` is `13`
+///
+/// Missing headers are rejected with [`HeaderError::Missing`], malformed ones with
+/// [`HeaderError::Validation`]. Completing the upgrade is left to the handler.
+///
+/// # Examples
+///
+/// ```
+/// use axum_required_headers::WebSocketHandshake;
+///
+/// async fn handler(handshake: WebSocketHandshake) {
+///     println!("key: {}, protocols: {:?}", handshake.key, handshake.protocols);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketHandshake {
+    /// The `Sec-WebSocket-Key` sent by the client
+    pub key: String,
+    /// The `Sec-WebSocket-Version` sent by the client
+    pub version: String,
+    /// The subprotocols listed in `Sec-WebSocket-Protocol`, in order of preference
+    pub protocols: Vec<String>,
+}
+
+impl WebSocketHandshake {
+    /// Validates the handshake headers of `headers`.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, HeaderError> {
+        if !has_token(headers, "connection", "upgrade")? {
+            return Err(invalid("connection", "expected the `upgrade` token"));
+        }
+        if !has_token(headers, "upgrade", "websocket")? {
+            return Err(invalid("upgrade", "expected the `websocket` token"));
+        }
+
+        let key = required_str(headers, "sec-websocket-key")?;
+        if !is_valid_key(key) {
+            return Err(invalid(
+                "sec-websocket-key",
+                "expected the base64 encoding of 16 bytes",
+            ));
+        }
+
+        let version = required_str(headers, "sec-websocket-version")?;
+        if version.trim() != SUPPORTED_VERSION {
+            return Err(invalid(
+                "sec-websocket-version",
+                "only version `13` is supported",
+            ));
+        }
+
+        let mut protocols = Vec::new();
+        for value in headers.get_all(SEC_WEBSOCKET_PROTOCOL) {
+            let value = value
+                .to_str()
+                .map_err(|_| HeaderError::InvalidValue("sec-websocket-protocol"))?;
+            protocols.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|protocol| !protocol.is_empty())
+                    .map(str::to_owned),
+            );
+        }
+
+        Ok(Self {
+            key: key.to_owned(),
+            version: version.trim().to_owned(),
+            protocols,
+        })
+    }
+}
+
+impl<S> FromRequestParts<S> for WebSocketHandshake
+where
+    S: Send + Sync,
+{
+    type Rejection = HeaderError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+fn invalid(name: &'static str, reason: &str) -> HeaderError {
+    HeaderError::Validation {
+        name,
+        reason: reason.to_owned(),
+    }
+}
+
+fn required_str<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, HeaderError> {
+    headers
+        .get(name)
+        .ok_or(HeaderError::Missing(name))?
+        .to_str()
+        .map_err(|_| HeaderError::InvalidValue(name))
+}
+
+/// Whether any value of the (comma separated) header `name` contains `token`, case-insensitively.
+fn has_token(headers: &HeaderMap, name: &'static str, token: &str) -> Result<bool, HeaderError> {
+    let mut values = headers.get_all(name).iter().peekable();
+    if values.peek().is_none() {
+        return Err(HeaderError::Missing(name));
+    }
+
+    for value in values {
+        let value = value
+            .to_str()
+            .map_err(|_| HeaderError::InvalidValue(name))?;
+        if value
+            .split(',')
+            .any(|candidate| candidate.trim().eq_ignore_ascii_case(token))
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// A base64 encoded 16 byte nonce is always 22 characters followed by `==`.
+fn is_valid_key(key: &str) -> bool {
+    let key = key.trim();
+    key.len() == 24
+        && key.ends_with("==")
+        && key[..22]
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
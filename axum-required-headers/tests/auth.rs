@@ -0,0 +1,84 @@
+use axum_required_headers::{AnyAuth, Credential, RequiredHeader};
+
+fn parse(value: &str) -> Credential {
+    value.parse::<AnyAuth>().unwrap().0
+}
+
+fn basic(username: &str, password: &str) -> Credential {
+    Credential::Basic {
+        username: username.to_owned(),
+        password: password.to_owned(),
+    }
+}
+
+// ============================================================================
+// PARSING TESTS
+// ============================================================================
+
+#[test]
+fn test_bearer() {
+    assert_eq!(
+        parse("Bearer abc.def-ghi_~+/=="),
+        Credential::Bearer("abc.def-ghi_~+/==".to_owned())
+    );
+    assert_eq!(
+        parse("bearer  token"),
+        Credential::Bearer("token".to_owned())
+    );
+}
+
+#[test]
+fn test_basic() {
+    assert_eq!(parse("Basic dXNlcjpwYXNz"), basic("user", "pass"));
+    assert_eq!(parse("BASIC YTo="), basic("a", ""));
+    assert_eq!(parse("Basic YWxpY2U6cDp3"), basic("alice", "p:w"));
+    assert_eq!(parse("Basic w6k6eA=="), basic("é", "x"));
+    assert_eq!(parse("Basic YTpi"), basic("a", "b"));
+}
+
+#[test]
+fn test_api_key() {
+    assert_eq!(
+        parse("ApiKey live_123"),
+        Credential::ApiKey("live_123".to_owned())
+    );
+}
+
+#[test]
+fn test_header_name() {
+    assert_eq!(AnyAuth::HEADER_NAME, "authorization");
+}
+
+#[test]
+fn test_invalid() {
+    for value in [
+        "",
+        "Bearer",
+        "Bearer ",
+        "Bearer a b",
+        "Digest username=\"a\"",
+        "Negotiate abc",
+        "Basic bm9jb2xvbg==",
+        "Basic dXNlcjpwYXNz=",
+        "Basic !!!!",
+        "Basic A===",
+        // `=` before the end
+        "Basic YQ==YQ==",
+        "Basic dX=jcjpw",
+        // non-zero trailing bits, `YQ==` and `YWI=` being canonical
+        "Basic YR==",
+        "Basic YWJ=",
+    ] {
+        assert!(value.parse::<AnyAuth>().is_err(), "{value:?}");
+    }
+}
+
+#[test]
+fn test_debug_redacts_secrets() {
+    let debug = format!("{:?}", parse("Basic dXNlcjpwYXNz"));
+    assert!(debug.contains("user"));
+    assert!(!debug.contains("pass\""));
+
+    let debug = format!("{:?}", parse("Bearer secret-token"));
+    assert!(!debug.contains("secret-token"));
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "base64")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, response::IntoResponse, routing::get};
+use axum_required_headers::Headers;
+use common::{read_body_json, read_body_string, send};
+
+#[derive(Headers)]
+pub struct SignedHeaders {
+    #[header("x-signature", base64)]
+    pub signature: Vec<u8>,
+
+    #[header("x-token", base64 = "url_safe")]
+    pub token: Option<Vec<u8>>,
+
+    #[header("x-count", base64, trim)]
+    pub count: Option<u32>,
+}
+
+async fn handler(headers: SignedHeaders) -> impl IntoResponse {
+    format!(
+        "signature: {:?}, token: {:?}, count: {:?}",
+        headers.signature, headers.token, headers.count
+    )
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler))
+}
+
+// ============================================================================
+// BASE64 TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_base64_decodes_bytes() {
+    let response = send(app(), &[("x-signature", "3q2+7w=="), ("x-token", "3q2-7w")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: [222, 173, 190, 239], token: Some([222, 173, 190, 239]), count: None"
+    );
+}
+
+#[tokio::test]
+async fn test_base64_parses_decoded_value() {
+    // "NDI=" is "42"
+    let response = send(app(), &[("x-signature", "AA=="), ("x-count", " NDI= ")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: [0], token: None, count: Some(42)"
+    );
+}
+
+#[tokio::test]
+async fn test_base64_standard_rejects_url_safe_alphabet() {
+    let response = send(app(), &[("x-signature", "3q2-7w==")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    assert!(body["message"].as_str().unwrap().contains("x-signature"));
+}
+
+#[tokio::test]
+async fn test_base64_standard_requires_padding() {
+    let response = send(app(), &[("x-signature", "3q2+7w")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[tokio::test]
+async fn test_base64_optional_invalid_is_none() {
+    // "YWJj" is "abc"
+    let response = send(
+        app(),
+        &[
+            ("x-signature", "AA=="),
+            ("x-count", "YWJj"),
+            ("x-token", "%%"),
+        ],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: [0], token: None, count: None"
+    );
+}
+
+#[derive(Headers)]
+pub struct LabelHeaders {
+    #[header("x-label", base64, trim)]
+    pub label: String,
+}
+
+#[tokio::test]
+async fn test_base64_trim_keeps_whitespace_of_decoded_value() {
+    async fn handler(headers: LabelHeaders) -> String {
+        format!("{:?}", headers.label)
+    }
+    let app = Router::new().route("/", get(handler));
+
+    // "IGhpIA==" is " hi ", only the encoded value is trimmed
+    let response = send(app, &[("x-label", "  IGhpIA== ")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "\" hi \"");
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "chrono")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, response::IntoResponse, routing::get};
+use axum_required_headers::Headers;
+use axum_required_headers::chrono::{DateTime, FixedOffset, Utc};
+use common::{read_body_json, read_body_string, send};
+
+#[derive(Headers)]
+pub struct EventHeaders {
+    #[header("x-event-time", datetime_fmt = "%Y-%m-%dT%H:%M:%S%z")]
+    pub event_time: DateTime<FixedOffset>,
+
+    #[header("x-received-at", datetime_fmt = "%d/%m/%Y %H:%M %z")]
+    pub received_at: Option<DateTime<Utc>>,
+}
+
+async fn handler(headers: EventHeaders) -> impl IntoResponse {
+    format!(
+        "event: {}, received: {:?}",
+        headers.event_time.to_rfc3339(),
+        headers.received_at.map(|time| time.to_rfc3339())
+    )
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler))
+}
+
+// ============================================================================
+// DATETIME FORMAT TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_datetime_valid() {
+    let response = send(
+        app(),
+        &[
+            ("x-event-time", "2024-03-01T12:30:00+0200"),
+            ("x-received-at", "01/03/2024 11:00 +0100"),
+        ],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "event: 2024-03-01T12:30:00+02:00, received: Some(\"2024-03-01T10:00:00+00:00\")"
+    );
+}
+
+#[tokio::test]
+async fn test_datetime_invalid() {
+    let response = send(app(), &[("x-event-time", "2024-13-45T99:00:00+0200")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    assert!(body["message"].as_str().unwrap().contains("x-event-time"));
+}
+
+#[tokio::test]
+async fn test_datetime_wrong_format() {
+    // Valid RFC 2822, but not the configured format
+    let response = send(
+        app(),
+        &[("x-event-time", "Fri, 01 Mar 2024 12:30:00 +0200")],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[tokio::test]
+async fn test_optional_datetime_wrong_format() {
+    let response = send(
+        app(),
+        &[
+            ("x-event-time", "2024-03-01T12:30:00+0200"),
+            ("x-received-at", "2024-03-01T11:00:00+0100"),
+        ],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "event: 2024-03-01T12:30:00+02:00, received: None"
+    );
+}
@@ -0,0 +1,37 @@
+//! Request helpers shared by the integration tests, included with `mod common;`.
+
+#![allow(dead_code)]
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, Response, request::Builder},
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+/// A `GET /` request builder with the given headers.
+pub fn request(headers: &[(&str, &str)]) -> Builder {
+    headers
+        .iter()
+        .fold(Request::builder().uri("/"), |request, (name, value)| {
+            request.header(*name, *value)
+        })
+}
+
+/// Sends `GET /` with the given headers to `app`.
+pub async fn send(app: Router, headers: &[(&str, &str)]) -> Response<Body> {
+    app.oneshot(request(headers).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+pub async fn read_body_string(response: Response<Body>) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+pub async fn read_body_json(response: Response<Body>) -> serde_json::Value {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&bytes).unwrap()
+}
@@ -7,4 +7,13 @@
 fn compile_fail_tests() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile_fail/*.rs");
+
+    // The suggested `FromStr` implementors in these diagnostics depend on the enabled features
+    #[cfg(not(feature = "chrono"))]
+    t.compile_fail("tests/compile_fail/default_features/*.rs");
+
+    #[cfg(feature = "regex")]
+    t.compile_fail("tests/compile_fail/regex/*.rs");
+    #[cfg(not(feature = "regex"))]
+    t.compile_fail("tests/compile_fail/no_regex/*.rs");
 }
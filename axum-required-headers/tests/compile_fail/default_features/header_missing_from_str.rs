@@ -0,0 +1,9 @@
+//! Test that Header derive fails when the type does not implement FromStr
+
+use axum_required_headers::Header;
+
+#[derive(Header)]
+#[header("x-user-id")]
+struct UserId(String);
+
+fn main() {}
@@ -0,0 +1,16 @@
+//! Test that Headers derive reports a field type without FromStr at the field
+
+use axum_required_headers::Headers;
+
+pub struct UserId(String);
+
+#[derive(Headers)]
+struct UserHeaders {
+    #[header("x-user-id")]
+    user_id: UserId,
+
+    #[header("x-tenant-id")]
+    tenant_id: Option<String>,
+}
+
+fn main() {}
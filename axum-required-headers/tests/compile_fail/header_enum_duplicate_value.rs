@@ -0,0 +1,13 @@
+//! Test that Header derive rejects enum variants matching the same value
+
+use axum_required_headers::Header;
+
+#[derive(Header)]
+#[header("x-forwarded-proto")]
+enum Scheme {
+    Https,
+    #[header_value("HTTPS")]
+    Secure,
+}
+
+fn main() {}
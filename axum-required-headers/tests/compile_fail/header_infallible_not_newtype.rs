@@ -0,0 +1,11 @@
+//! Test that Header derive only generates `FromStr` for newtype structs
+
+use axum_required_headers::Header;
+
+#[derive(Header)]
+#[header("x-user-id", infallible)]
+struct UserId {
+    id: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test that `all_or_none` groups need at least two headers
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+#[headers(all_or_none("x-signature"))]
+struct SignedHeaders {
+    #[header("x-signature")]
+    signature: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test that Headers derive explains why case-sensitive matching is unsupported
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct CaseSensitive {
+    #[header("X-Signature", case_sensitive)]
+    signature: String,
+}
+
+fn main() {}
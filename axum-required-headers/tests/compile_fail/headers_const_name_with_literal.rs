@@ -0,0 +1,13 @@
+//! Test that Headers derive fails when a field gives both a header name literal and a const
+
+use axum_required_headers::Headers;
+
+const X_USER_ID: &str = "x-user-id";
+
+#[derive(Headers)]
+struct UserHeaders {
+    #[header("x-user-id", name = X_USER_ID)]
+    user_id: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test that Headers derive fails with a default on an optional field
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct DefaultOnOption {
+    #[header("x-region", default = "us-east-1")]
+    region: Option<String>,
+}
+
+fn main() {}
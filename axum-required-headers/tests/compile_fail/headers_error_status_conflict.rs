@@ -0,0 +1,12 @@
+//! Test that Headers derive fails when `error_status` is combined with `status`
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+#[headers(status = 422, error_status = 200)]
+struct ConflictingStatus {
+    #[header("x-client-id")]
+    client_id: String,
+}
+
+fn main() {}
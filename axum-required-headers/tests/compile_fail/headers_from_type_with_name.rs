@@ -0,0 +1,15 @@
+//! Test that Headers derive fails when `from_type` is combined with a header name
+
+use axum_required_headers::{Header, Headers};
+
+#[derive(Header)]
+#[header("x-user-id", infallible)]
+struct UserId(String);
+
+#[derive(Headers)]
+struct UserHeaders {
+    #[header("x-user", from_type)]
+    user_id: UserId,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test that `gzip` requires `base64`
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct ContextHeaders {
+    #[header("x-ctx", gzip)]
+    context: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test that `#[headers(infallible)]` requires a `problems` field
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+#[headers(infallible)]
+struct InspectedHeaders {
+    #[header("x-user-id")]
+    user_id: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+//! Test that Headers derive fails with an out of range status code
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+#[headers(missing_status = 42)]
+struct InvalidStatus {
+    #[header("authorization")]
+    authorization: String,
+}
+
+fn main() {}
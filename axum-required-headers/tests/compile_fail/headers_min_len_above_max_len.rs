@@ -0,0 +1,11 @@
+//! Test that Headers derive fails when `min_len` exceeds `max_len`
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct InvalidLength {
+    #[header("x-api-key", min_len = 64, max_len = 32)]
+    api_key: String,
+}
+
+fn main() {}
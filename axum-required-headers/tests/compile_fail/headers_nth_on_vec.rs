@@ -0,0 +1,11 @@
+//! Test that Headers derive fails when `nth` is used on a `Vec` field
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct HopHeaders {
+    #[header("x-hop", nth = 1)]
+    hops: Vec<String>,
+}
+
+fn main() {}
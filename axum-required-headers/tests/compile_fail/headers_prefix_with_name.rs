@@ -0,0 +1,12 @@
+//! Test that Headers derive fails when a prefix field also names a header
+
+use axum_required_headers::Headers;
+use std::collections::HashMap;
+
+#[derive(Headers)]
+struct MetaHeaders {
+    #[header("x-meta", prefix = "x-meta-")]
+    meta: HashMap<String, String>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+//! Test that `#[header(problems)]` is rejected outside of `infallible` structs
+
+use axum_required_headers::{HeaderError, Headers};
+
+#[derive(Headers)]
+struct InspectedHeaders {
+    #[header("x-user-id")]
+    user_id: String,
+
+    #[header(problems)]
+    problems: Vec<HeaderError>,
+}
+
+fn main() {}
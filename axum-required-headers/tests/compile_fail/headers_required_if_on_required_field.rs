@@ -0,0 +1,11 @@
+//! Test that `required_if` is rejected on non-`Option` fields
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct SignedHeaders {
+    #[header("x-signature", required_if = "x-signature-alg")]
+    signature: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+//! Test that `required_if` and `required_if_present` can't both be given
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct CsrfHeaders {
+    #[header("x-csrf-token", required_if = "cookie", required_if_present = "authorization")]
+    csrf_token: Option<String>,
+}
+
+fn main() {}
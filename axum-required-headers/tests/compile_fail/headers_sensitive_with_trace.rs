@@ -0,0 +1,11 @@
+//! Test that Headers derive fails when a `sensitive` field is recorded with `trace`
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct AuthHeaders {
+    #[header("authorization", sensitive, trace)]
+    authorization: String,
+}
+
+fn main() {}
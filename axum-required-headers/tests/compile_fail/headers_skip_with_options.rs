@@ -0,0 +1,14 @@
+//! Test that `skip` can't be combined with other options
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct EnrichedHeaders {
+    #[header("x-user-id")]
+    user_id: String,
+
+    #[header(skip, trim)]
+    display_name: String,
+}
+
+fn main() {}
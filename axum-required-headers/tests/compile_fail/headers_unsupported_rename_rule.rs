@@ -0,0 +1,11 @@
+//! Test that Headers derive fails with an unsupported `rename_all` rule
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+#[headers(rename_all = "camelCase", prefix = "x-")]
+struct CamelCaseHeaders {
+    user_id: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+//! Test that `validate_with` requires the struct-level `state` option
+
+use axum_required_headers::Headers;
+
+fn check_key(_key: &str, _state: &()) -> Result<(), String> {
+    Ok(())
+}
+
+#[derive(Headers)]
+struct KeyedHeaders {
+    #[header("x-api-key", validate_with = check_key)]
+    api_key: String,
+}
+
+fn main() {}
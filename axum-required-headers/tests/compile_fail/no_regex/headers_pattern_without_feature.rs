@@ -0,0 +1,11 @@
+//! Test that `pattern` requires the `regex` feature
+
+use axum_required_headers::Headers;
+
+#[derive(Headers)]
+struct TraceHeaders {
+    #[header("x-trace-id", pattern = "^[0-9a-f-]{36}$")]
+    trace_id: String,
+}
+
+fn main() {}
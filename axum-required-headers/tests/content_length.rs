@@ -0,0 +1,70 @@
+mod common;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Response, StatusCode},
+    routing::post,
+};
+use axum_required_headers::VerifiedContentLength;
+use common::{read_body_json, read_body_string, request};
+use tower::ServiceExt;
+
+async fn handler(body: VerifiedContentLength) -> String {
+    format!("length: {}, body: {:?}", body.length, body.body)
+}
+
+/// Posts `body` with the given headers, unlike `common::send` which sends an empty `GET`
+async fn upload(headers: &[(&str, &str)], body: &'static str) -> Response<Body> {
+    let app = Router::new().route("/", post(handler));
+    let request = request(headers)
+        .method("POST")
+        .body(Body::from(body))
+        .unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+// ============================================================================
+// VERIFIED CONTENT LENGTH TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_content_length_matches() {
+    let response = upload(&[("content-length", "5")], "hello").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "length: 5, body: b\"hello\""
+    );
+}
+
+#[tokio::test]
+async fn test_content_length_mismatch() {
+    for declared in ["4", "10"] {
+        let response = upload(&[("content-length", declared)], "hello").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = read_body_json(response).await;
+        assert_eq!(body["error"], "content_length_mismatch");
+        assert_eq!(
+            body["message"],
+            format!("Body length (5 bytes) does not match `content-length` ({declared} bytes)")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_content_length_missing_or_invalid() {
+    let response = upload(&[], "hello").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(read_body_json(response).await["error"], "missing_header");
+
+    let response = upload(&[("content-length", "five")], "hello").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        read_body_json(response).await["error"],
+        "header_parse_error"
+    );
+}
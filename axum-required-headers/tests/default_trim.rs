@@ -0,0 +1,79 @@
+#![cfg(feature = "default-trim")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::Headers;
+use common::{read_body_string, send};
+
+#[derive(Headers)]
+pub struct DefaultTrimHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-count")]
+    pub count: u32,
+
+    #[header("x-signature", no_trim)]
+    pub signature: Option<String>,
+
+    #[header("x-tags", list)]
+    pub tags: Option<Vec<String>>,
+
+    #[header("x-raw")]
+    pub raw: Option<axum::http::HeaderValue>,
+}
+
+async fn handler(headers: DefaultTrimHeaders) -> String {
+    format!(
+        "{:?} {} {:?} {:?} {:?}",
+        headers.user_id, headers.count, headers.signature, headers.tags, headers.raw
+    )
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler))
+}
+
+// ============================================================================
+// DEFAULT TRIM TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_default_trim_applies_to_every_field() {
+    let response = send(
+        app(),
+        &[
+            ("x-user-id", "  user123 "),
+            ("x-count", " 42\t"),
+            ("x-tags", " a , b "),
+        ],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        r#""user123" 42 None Some(["a", "b"]) None"#
+    );
+}
+
+#[tokio::test]
+async fn test_default_trim_no_trim_and_raw_fields_keep_whitespace() {
+    let response = send(
+        app(),
+        &[
+            ("x-user-id", "user123"),
+            ("x-count", "1"),
+            ("x-signature", " abc= "),
+            ("x-raw", " raw "),
+        ],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        r#""user123" 1 Some(" abc= ") None Some(" raw ")"#
+    );
+}
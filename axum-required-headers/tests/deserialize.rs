@@ -0,0 +1,105 @@
+#![cfg(feature = "serde")]
+
+use axum_required_headers::Headers;
+use std::collections::HashMap;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub struct Page(u32);
+
+impl FromStr for Page {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Page)
+    }
+}
+
+#[derive(Debug, Headers)]
+#[headers(deserialize)]
+pub struct FixtureHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-page", default = "1")]
+    pub page: Page,
+
+    #[header("x-tags", list)]
+    pub tags: Vec<String>,
+
+    #[header("x-trace-id")]
+    pub trace_id: Option<String>,
+}
+
+fn map(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn deserialize(map: HashMap<String, String>) -> Result<FixtureHeaders, serde_json::Error> {
+    serde_json::from_value(serde_json::to_value(map).unwrap())
+}
+
+// ============================================================================
+// DESERIALIZE TESTS
+// ============================================================================
+
+#[test]
+fn test_deserialize_from_map() {
+    let headers = deserialize(map(&[
+        ("x-user-id", "user123"),
+        ("x-page", "3"),
+        ("x-tags", "a, b"),
+        ("x-trace-id", "abc"),
+    ]))
+    .unwrap();
+
+    assert_eq!(headers.user_id, "user123");
+    assert_eq!(headers.page, Page(3));
+    assert_eq!(headers.tags, vec!["a", "b"]);
+    assert_eq!(headers.trace_id.as_deref(), Some("abc"));
+}
+
+#[test]
+fn test_deserialize_uses_defaults_and_options() {
+    let headers = deserialize(map(&[("x-user-id", "user123"), ("x-tags", "a")])).unwrap();
+
+    assert_eq!(headers.page, Page(1));
+    assert_eq!(headers.trace_id, None);
+}
+
+#[test]
+fn test_deserialize_header_names_are_case_insensitive() {
+    let headers = deserialize(map(&[("X-User-Id", "user123"), ("X-Tags", "a")])).unwrap();
+
+    assert_eq!(headers.user_id, "user123");
+}
+
+#[test]
+fn test_deserialize_keys_are_header_names() {
+    let error = deserialize(map(&[("user_id", "user123"), ("x-tags", "a")])).unwrap_err();
+
+    assert!(error.to_string().contains("x-user-id"));
+}
+
+#[test]
+fn test_deserialize_rejects_malformed_values() {
+    let error = deserialize(map(&[
+        ("x-user-id", "user123"),
+        ("x-page", "three"),
+        ("x-tags", "a"),
+    ]))
+    .unwrap_err();
+
+    assert!(error.to_string().contains("x-page"));
+}
+
+#[test]
+fn test_deserialize_rejects_invalid_header_names() {
+    let error = deserialize(map(&[("x user id", "user123")])).unwrap_err();
+
+    assert_eq!(error.to_string(), "invalid header name `x user id`");
+}
@@ -0,0 +1,88 @@
+use axum_required_headers::{ContentDisposition, RequiredHeader};
+
+fn parse(value: &str) -> ContentDisposition {
+    value.parse().unwrap()
+}
+
+// ============================================================================
+// PARSING TESTS
+// ============================================================================
+
+#[test]
+fn test_plain_filename() {
+    let disposition = parse("attachment; filename=report.pdf");
+    assert_eq!(disposition.disposition, "attachment");
+    assert_eq!(disposition.filename.as_deref(), Some("report.pdf"));
+}
+
+#[test]
+fn test_quoted_filename() {
+    let disposition = parse(r#"Attachment;filename="annual report; 2024 \"final\".pdf""#);
+    assert_eq!(disposition.disposition, "attachment");
+    assert_eq!(
+        disposition.filename.as_deref(),
+        Some(r#"annual report; 2024 "final".pdf"#)
+    );
+}
+
+#[test]
+fn test_encoded_filename() {
+    let disposition = parse("attachment; filename*=UTF-8''%E2%82%AC%20rates.txt");
+    assert_eq!(disposition.filename.as_deref(), Some("€ rates.txt"));
+
+    let disposition = parse("attachment; filename*=iso-8859-1'en'%A3%20rates.txt");
+    assert_eq!(disposition.filename.as_deref(), Some("£ rates.txt"));
+}
+
+#[test]
+fn test_encoded_filename_preferred() {
+    let disposition =
+        parse("attachment; filename=\"EURO rates.txt\"; filename*=utf-8''%e2%82%ac%20rates.txt");
+    assert_eq!(disposition.filename.as_deref(), Some("€ rates.txt"));
+}
+
+#[test]
+fn test_undecodable_encoded_filename_falls_back() {
+    let disposition = parse("attachment; filename*=UTF-8''%ZZ; filename=fallback.txt");
+    assert_eq!(disposition.filename.as_deref(), Some("fallback.txt"));
+
+    let disposition = parse("attachment; filename*=koi8-r''%C1");
+    assert_eq!(disposition.filename, None);
+}
+
+#[test]
+fn test_without_filename() {
+    let disposition = parse("inline");
+    assert_eq!(disposition.disposition, "inline");
+    assert_eq!(disposition.filename, None);
+
+    let disposition = parse("form-data; name=\"file\";");
+    assert_eq!(disposition.disposition, "form-data");
+    assert_eq!(disposition.filename, None);
+}
+
+#[test]
+fn test_header_name() {
+    assert_eq!(ContentDisposition::HEADER_NAME, "content-disposition");
+}
+
+#[test]
+fn test_invalid() {
+    assert!("".parse::<ContentDisposition>().is_err());
+    assert!("; filename=a.txt".parse::<ContentDisposition>().is_err());
+    assert!(
+        "attachment filename=a.txt"
+            .parse::<ContentDisposition>()
+            .is_err()
+    );
+    assert!(
+        "attachment; filename"
+            .parse::<ContentDisposition>()
+            .is_err()
+    );
+    assert!(
+        "attachment; filename=\"a.txt"
+            .parse::<ContentDisposition>()
+            .is_err()
+    );
+}
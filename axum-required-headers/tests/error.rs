@@ -0,0 +1,213 @@
+use axum::{
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use axum_required_headers::{HeaderError, ProblemJson};
+use http_body_util::BodyExt;
+
+// ============================================================================
+// ORDERING TESTS
+// ============================================================================
+
+#[test]
+fn test_sort_by_kind_then_name() {
+    let mut errors = vec![
+        HeaderError::Validation {
+            name: "x-offset",
+            reason: "value must be within `0..=10`".to_string(),
+        },
+        HeaderError::Parse("x-count"),
+        HeaderError::Missing("x-user-id"),
+        HeaderError::TooLong {
+            name: "x-token",
+            max: 8,
+        },
+        HeaderError::InvalidValue("x-name"),
+        HeaderError::Missing("x-tenant-id"),
+        HeaderError::Parse("x-age"),
+    ];
+
+    errors.sort();
+
+    assert_eq!(
+        errors,
+        vec![
+            HeaderError::Missing("x-tenant-id"),
+            HeaderError::Missing("x-user-id"),
+            HeaderError::InvalidValue("x-name"),
+            HeaderError::Parse("x-age"),
+            HeaderError::Parse("x-count"),
+            HeaderError::TooLong {
+                name: "x-token",
+                max: 8,
+            },
+            HeaderError::Validation {
+                name: "x-offset",
+                reason: "value must be within `0..=10`".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_multiple_sorts_last() {
+    let mut errors = [
+        HeaderError::Multiple(vec![HeaderError::Missing("x-a"), HeaderError::Parse("x-b")]),
+        HeaderError::Validation {
+            name: "x-offset",
+            reason: "out of range".to_string(),
+        },
+    ];
+
+    errors.sort();
+
+    assert!(matches!(errors[1], HeaderError::Multiple(_)));
+}
+
+#[test]
+fn test_multiple_display_lists_every_error() {
+    let error = HeaderError::Multiple(vec![
+        HeaderError::Missing("x-user-id"),
+        HeaderError::Parse("x-count"),
+    ]);
+
+    assert_eq!(
+        error.to_string(),
+        "Multiple header errors: Missing required header: `x-user-id`; \
+         Failed to parse header value: `x-count`"
+    );
+}
+
+#[test]
+fn test_ordering_consistent_with_equality() {
+    let short = HeaderError::TooLong {
+        name: "x-token",
+        max: 8,
+    };
+    let long = HeaderError::TooLong {
+        name: "x-token",
+        max: 16,
+    };
+
+    assert!(short < long);
+    assert_ne!(short, long);
+    assert_eq!(
+        HeaderError::Missing("x-user-id").cmp(&HeaderError::Missing("x-user-id")),
+        std::cmp::Ordering::Equal
+    );
+}
+
+// ============================================================================
+// CONSTRUCTOR AND ACCESSOR TESTS
+// ============================================================================
+
+#[test]
+fn test_missing_all() {
+    assert_eq!(HeaderError::missing_all(&[]), None);
+    assert_eq!(
+        HeaderError::missing_all(&["x-user-id"]),
+        Some(HeaderError::Missing("x-user-id"))
+    );
+    assert_eq!(
+        HeaderError::missing_all(&["x-user-id", "x-tenant-id"]),
+        Some(HeaderError::Multiple(vec![
+            HeaderError::Missing("x-user-id"),
+            HeaderError::Missing("x-tenant-id"),
+        ]))
+    );
+}
+
+#[test]
+fn test_header_name() {
+    assert_eq!(
+        HeaderError::Missing("x-user-id").header_name(),
+        Some("x-user-id")
+    );
+    assert_eq!(
+        HeaderError::Validation {
+            name: "x-offset",
+            reason: "too large".to_string(),
+        }
+        .header_name(),
+        Some("x-offset")
+    );
+    assert_eq!(
+        HeaderError::ContentLengthMismatch {
+            declared: 4,
+            actual: 2,
+        }
+        .header_name(),
+        Some("content-length")
+    );
+    assert_eq!(
+        HeaderError::missing_all(&["x-user-id", "x-tenant-id"])
+            .unwrap()
+            .header_name(),
+        None
+    );
+}
+
+// ============================================================================
+// PROBLEM JSON TESTS
+// ============================================================================
+
+async fn problem_response(error: HeaderError) -> (StatusCode, String, serde_json::Value) {
+    let response = ProblemJson(error).into_response();
+    let status = response.status();
+    let content_type = response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        content_type,
+        serde_json::from_slice(&bytes).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_problem_json_body() {
+    let (status, content_type, body) = problem_response(HeaderError::Missing("x-user-id")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(content_type, "application/problem+json");
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "type": "about:blank",
+            "title": "Bad Request",
+            "status": 400,
+            "detail": "Missing required header: `x-user-id`",
+            "code": "missing_header",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_problem_json_multiple() {
+    let (_, _, body) = problem_response(HeaderError::Multiple(vec![
+        HeaderError::Missing("x-user-id"),
+        HeaderError::Parse("x-page"),
+    ]))
+    .await;
+
+    assert_eq!(body["code"], "multiple_header_errors");
+    assert_eq!(
+        body["errors"],
+        serde_json::json!([
+            { "code": "missing_header", "detail": "Missing required header: `x-user-id`" },
+            { "code": "header_parse_error", "detail": "Failed to parse header value: `x-page`" },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_plain_error_body_is_unchanged() {
+    let response = HeaderError::Missing("x-user-id").into_response();
+
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"], "missing_header");
+}
@@ -3,7 +3,9 @@ use axum::{
     http::{Request, StatusCode},
     routing::get,
 };
-use axum_required_headers::{Header, Optional, Required};
+use axum_required_headers::{
+    FirstOf, Header, Lenient, Optional, OptionalOr, Present, Required, RequiredAll, impl_header,
+};
 use http_body_util::BodyExt;
 use std::convert::Infallible;
 use std::num::ParseIntError;
@@ -35,7 +37,7 @@ impl FromStr for UserId {
 }
 
 // Custom type with fallible parsing for testing parse errors
-#[derive(Header, Debug, Clone, PartialEq)]
+#[derive(Header, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[header("x-positive-int")]
 struct PositiveInt(u32);
 
@@ -719,6 +721,90 @@ async fn test_optional_deref_mut() {
     assert!(optional.is_none());
 }
 
+// ============================================================================
+// PARTIALEQ AGAINST INNER VALUE TESTS
+// ============================================================================
+
+#[test]
+fn test_required_eq_inner() {
+    let required = Required(PositiveInt(42));
+
+    assert_eq!(required, PositiveInt(42));
+    assert_ne!(required, PositiveInt(7));
+    assert_eq!(required, Required(PositiveInt(42)));
+}
+
+#[test]
+fn test_optional_eq_inner() {
+    let present = Optional(Some(PositiveInt(42)));
+    let absent: Optional<PositiveInt> = Optional(None);
+
+    assert_eq!(present, Some(PositiveInt(42)));
+    assert_ne!(present, Some(PositiveInt(7)));
+    assert_ne!(present, None);
+    assert_eq!(absent, None);
+    assert_eq!(present, Optional(Some(PositiveInt(42))));
+}
+
+#[test]
+fn test_required_eq_hash_ord() {
+    use std::collections::{BTreeSet, HashSet};
+
+    assert_eq!(Required(PositiveInt(1)), Required(PositiveInt(1)));
+    assert_ne!(Required(PositiveInt(1)), Required(PositiveInt(2)));
+    assert!(Required(PositiveInt(1)) < Required(PositiveInt(2)));
+
+    let set: HashSet<_> = [
+        Required(PositiveInt(1)),
+        Required(PositiveInt(2)),
+        Required(PositiveInt(1)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&Required(PositiveInt(2))));
+
+    let sorted: BTreeSet<_> = [Optional(Some(PositiveInt(3))), Optional(None)]
+        .into_iter()
+        .collect();
+    assert_eq!(sorted.first(), Some(&Optional(None)));
+}
+
+// ============================================================================
+// INTO INNER CONVERSION TESTS
+// ============================================================================
+
+fn takes_positive_int(value: PositiveInt) -> u32 {
+    value.0
+}
+
+fn takes_option(value: impl Into<Option<PositiveInt>>) -> Option<u32> {
+    value.into().map(|value| value.0)
+}
+
+#[test]
+fn test_required_into_inner() {
+    assert_eq!(
+        takes_positive_int(Required(PositiveInt(42)).into_inner()),
+        42
+    );
+}
+
+#[test]
+fn test_optional_into_option() {
+    assert_eq!(
+        Optional(Some(PositiveInt(42))).into_inner(),
+        Some(PositiveInt(42))
+    );
+    assert_eq!(Optional::<PositiveInt>(None).into_inner(), None);
+
+    assert_eq!(takes_option(Optional(Some(PositiveInt(7)))), Some(7));
+    assert_eq!(takes_option(Optional(None)), None);
+
+    let option: Option<PositiveInt> = Optional(Some(PositiveInt(1))).into();
+    assert_eq!(option, Some(PositiveInt(1)));
+}
+
 // ============================================================================
 // OPTIONAL HEADER EDGE CASES
 // ============================================================================
@@ -755,3 +841,920 @@ async fn test_optional_header_unicode_returns_none() {
     // Optional extraction should return error for invalid ASCII
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
+
+// ============================================================================
+// OPTION<REQUIRED<T>> TESTS
+// ============================================================================
+
+async fn option_required_handler(value: Option<Required<PositiveInt>>) -> String {
+    match value {
+        Some(Required(value)) => format!("value: {}", value.0),
+        None => "no value".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_option_required_absent() {
+    let app = Router::new().route("/", get(option_required_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_option_required_malformed() {
+    let app = Router::new().route("/", get(option_required_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-positive-int", "-1")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// OPTIONAL OR TESTS
+// ============================================================================
+
+#[derive(Header, Debug)]
+#[header("x-page-size")]
+struct PageSize(u32);
+
+impl Default for PageSize {
+    fn default() -> Self {
+        Self(20)
+    }
+}
+
+impl FromStr for PageSize {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+async fn optional_or_handler(page_size: OptionalOr<PageSize>) -> String {
+    format!("page size: {}", page_size.0.0)
+}
+
+async fn send_page_size(value: Option<&str>) -> String {
+    let app = Router::new().route("/", get(optional_or_handler));
+
+    let mut request = Request::builder().uri("/");
+    if let Some(value) = value {
+        request = request.header("x-page-size", value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_optional_or_absent() {
+    assert_eq!(send_page_size(None).await, "page size: 20");
+}
+
+#[tokio::test]
+async fn test_optional_or_present_valid() {
+    assert_eq!(send_page_size(Some("50")).await, "page size: 50");
+}
+
+#[tokio::test]
+async fn test_optional_or_present_invalid() {
+    assert_eq!(send_page_size(Some("fifty")).await, "page size: 20");
+    assert_eq!(send_page_size(Some("-1")).await, "page size: 20");
+}
+
+#[test]
+fn test_optional_or_deref() {
+    let page_size = OptionalOr(PageSize(10));
+    let inner: &PageSize = &page_size;
+    assert_eq!(inner.0, 10);
+}
+
+// ============================================================================
+// LENIENT TESTS
+// ============================================================================
+
+async fn lenient_handler(Lenient(page_size): Lenient<PageSize>) -> String {
+    format!("page size: {:?}", page_size.map(|page_size| page_size.0))
+}
+
+async fn send_lenient(value: Option<&[u8]>) -> (StatusCode, String) {
+    let app = Router::new().route("/lenient", get(lenient_handler)).route(
+        "/optional",
+        get(|Optional(page_size): Optional<PageSize>| async move {
+            format!("page size: {:?}", page_size.map(|page_size| page_size.0))
+        }),
+    );
+
+    let mut lenient = Request::builder().uri("/lenient");
+    let mut optional = Request::builder().uri("/optional");
+    if let Some(value) = value {
+        let value = axum::http::HeaderValue::from_bytes(value).unwrap();
+        lenient = lenient.header("x-page-size", value.clone());
+        optional = optional.header("x-page-size", value);
+    }
+
+    // `Lenient` never rejects
+    let response = app
+        .clone()
+        .oneshot(lenient.body(axum::body::Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let lenient_body = String::from_utf8(body.to_vec()).unwrap();
+
+    let response = app
+        .oneshot(optional.body(axum::body::Body::empty()).unwrap())
+        .await
+        .unwrap();
+    (response.status(), lenient_body)
+}
+
+#[tokio::test]
+async fn test_lenient_absent() {
+    let (optional_status, body) = send_lenient(None).await;
+    assert_eq!(body, "page size: None");
+    assert_eq!(optional_status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_lenient_present_valid() {
+    let (optional_status, body) = send_lenient(Some(b"50")).await;
+    assert_eq!(body, "page size: Some(50)");
+    assert_eq!(optional_status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_lenient_malformed_is_none() {
+    // `Optional` rejects what `Lenient` treats as absent
+    let (optional_status, body) = send_lenient(Some(b"fifty")).await;
+    assert_eq!(body, "page size: None");
+    assert_eq!(optional_status, StatusCode::BAD_REQUEST);
+
+    let (optional_status, body) = send_lenient(Some(&[0xff, b'5'])).await;
+    assert_eq!(body, "page size: None");
+    assert_eq!(optional_status, StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// ALIAS TESTS
+// ============================================================================
+
+#[derive(Header, Debug)]
+#[header("x-request-id", alias = "x-correlation-id", alias = "x-trace-id")]
+struct RequestId(String);
+
+impl FromStr for RequestId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+async fn alias_handler(Required(id): Required<RequestId>) -> String {
+    format!("id: {}", id.0)
+}
+
+async fn optional_alias_handler(Optional(id): Optional<RequestId>) -> String {
+    format!("id: {:?}", id.map(|id| id.0))
+}
+
+async fn send_alias(app: Router, pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_alias_primary_name_preferred() {
+    let app = Router::new().route("/", get(alias_handler));
+
+    let response = send_alias(app, &[("x-trace-id", "trace"), ("x-request-id", "primary")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"id: primary");
+}
+
+#[tokio::test]
+async fn test_alias_fallback_in_order() {
+    let app = Router::new().route("/", get(alias_handler));
+
+    let response = send_alias(
+        app,
+        &[("X-Trace-Id", "trace"), ("X-Correlation-Id", "correlation")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"id: correlation");
+}
+
+#[tokio::test]
+async fn test_alias_missing_mentions_primary_name() {
+    let app = Router::new().route("/", get(alias_handler));
+
+    let response = send_alias(app, &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["message"], "Missing required header: `x-request-id`");
+}
+
+#[tokio::test]
+async fn test_optional_alias() {
+    let app = Router::new().route("/", get(optional_alias_handler));
+
+    let response = send_alias(app, &[("x-trace-id", "trace")]).await;
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"id: Some(\"trace\")");
+}
+
+// ============================================================================
+// FIRST OF TESTS
+// ============================================================================
+
+/// Stand-in for a `TypedHeader` from another crate
+struct LegacyUser(String);
+
+impl<S: Send + Sync> axum::extract::FromRequestParts<S> for LegacyUser {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        parts
+            .headers
+            .get("x-legacy-user")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| LegacyUser(value.to_owned()))
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
+
+async fn first_of_handler(user: FirstOf<Required<UserId>, LegacyUser>) -> String {
+    match user {
+        FirstOf::First(Required(user)) => format!("user: {}", user.0),
+        FirstOf::Second(legacy) => format!("legacy: {}", legacy.0),
+    }
+}
+
+async fn send_first_of(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(first_of_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_first_of_prefers_first() {
+    let response = send_first_of(&[("x-legacy-user", "old"), ("x-user-id", "new")]).await;
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"user: new");
+}
+
+#[tokio::test]
+async fn test_first_of_falls_back_to_second() {
+    let response = send_first_of(&[("x-legacy-user", "old")]).await;
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"legacy: old");
+}
+
+#[tokio::test]
+async fn test_first_of_both_fail_returns_first_rejection() {
+    let response = send_first_of(&[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"], "missing_header");
+}
+
+// ============================================================================
+// INFALLIBLE TESTS
+// ============================================================================
+
+#[derive(Header)]
+#[header("x-tenant", infallible)]
+struct Tenant(String);
+
+#[derive(Header)]
+#[header("x-session", infallible)]
+struct Session(Box<str>);
+
+async fn infallible_handler(
+    Required(tenant): Required<Tenant>,
+    Optional(session): Optional<Session>,
+) -> String {
+    format!("tenant: {}, session: {:?}", tenant.0, session.map(|s| s.0))
+}
+
+#[tokio::test]
+async fn test_infallible_generates_from_str() {
+    let app = Router::new().route("/", get(infallible_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-tenant", "acme")
+        .header("x-session", "s1")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"tenant: acme, session: Some(\"s1\")");
+    assert_eq!("x".parse::<Tenant>().unwrap().0, "x");
+}
+
+// ============================================================================
+// TO HEADER VALUE TESTS
+// ============================================================================
+
+#[derive(Header)]
+#[header("x-retry-count", to_header_value)]
+struct RetryCount(u32);
+
+impl FromStr for RetryCount {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+#[derive(Header)]
+#[header("x-tag", infallible, to_header_value)]
+struct Tag(String);
+
+#[test]
+fn test_to_header_value_round_trip() {
+    let value = RetryCount(3).to_header_value().unwrap();
+    assert_eq!(value, "3");
+    assert_eq!(value.to_str().unwrap().parse::<RetryCount>().unwrap().0, 3);
+
+    assert_eq!(Tag("blue".to_owned()).to_header_value().unwrap(), "blue");
+}
+
+#[test]
+fn test_to_header_value_rejects_invalid_characters() {
+    assert!(Tag("line\nbreak".to_owned()).to_header_value().is_err());
+}
+
+// ============================================================================
+// ENUM TESTS
+// ============================================================================
+
+#[derive(Debug, PartialEq, Header)]
+#[header("x-forwarded-proto")]
+enum Scheme {
+    Http,
+    Https,
+    #[header_value("ws")]
+    WebSocket,
+}
+
+async fn scheme_handler(Required(scheme): Required<Scheme>) -> String {
+    format!("scheme: {scheme:?}")
+}
+
+async fn send_scheme(value: &str) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(scheme_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-forwarded-proto", value)
+        .body(axum::body::Body::empty())
+        .unwrap();
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_enum_matches_variant_case_insensitively() {
+    let response = send_scheme("HTTPS").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"scheme: Https");
+}
+
+#[tokio::test]
+async fn test_enum_unknown_value_is_parse_error() {
+    let response = send_scheme("ftp").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[test]
+fn test_enum_header_value_attribute() {
+    assert_eq!("ws".parse::<Scheme>(), Ok(Scheme::WebSocket));
+    assert!("WebSocket".parse::<Scheme>().is_err());
+
+    let error = "ftp".parse::<Scheme>().unwrap_err();
+    assert_eq!(error.value(), "ftp");
+    assert_eq!(error.expected(), ["Http", "Https", "ws"]);
+    assert_eq!(
+        error.to_string(),
+        "unknown value `ftp`, expected one of: Http, Https, ws"
+    );
+}
+
+// ============================================================================
+// NON-STATIC HEADER NAME TESTS
+// ============================================================================
+
+#[derive(Header)]
+#[header("X-Request-Source", infallible)]
+struct RequestSource(String);
+
+async fn request_source_handler(
+    Required(source): Required<RequestSource>,
+    Optional(fallback): Optional<RequestSource>,
+) -> String {
+    format!(
+        "source: {}, optional: {:?}",
+        source.0,
+        fallback.map(|s| s.0)
+    )
+}
+
+#[tokio::test]
+async fn test_uppercase_header_name_falls_back_to_runtime_lookup() {
+    let app = Router::new().route("/", get(request_source_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-request-source", "cli")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"source: cli, optional: Some(\"cli\")");
+}
+
+// ============================================================================
+// TO HEADER VALUE TESTS
+// ============================================================================
+
+#[derive(Header, Debug)]
+#[header("x-region")]
+struct Region(String);
+
+impl FromStr for Region {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl std::fmt::Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+async fn echo_region_handler(region: Required<Region>) -> impl axum::response::IntoResponse {
+    let value = region.to_header_value().unwrap();
+    [("x-region", value)]
+}
+
+#[tokio::test]
+async fn test_required_to_header_value_round_trip() {
+    let app = Router::new().route("/", get(echo_region_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-region", "eu-west-1")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-region"], "eu-west-1");
+}
+
+#[test]
+fn test_required_to_header_value_invalid() {
+    let region = Required(Region("eu\nwest".to_string()));
+
+    assert_eq!(
+        region.to_header_value().unwrap_err(),
+        axum_required_headers::HeaderError::InvalidValue("x-region")
+    );
+}
+
+#[test]
+fn test_optional_to_header_value() {
+    let region = Optional(Some(Region("us-east-1".to_string())));
+    assert_eq!(region.to_header_value().unwrap().unwrap(), "us-east-1");
+
+    let absent: Optional<Region> = Optional(None);
+    assert!(absent.to_header_value().is_none());
+}
+
+const X_ZONE: &str = "x-zone";
+
+#[derive(Header, Debug)]
+#[header(name = X_ZONE, infallible, to_header_value)]
+struct Zone(String);
+
+impl std::fmt::Display for Zone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+async fn echo_zone_handler(Required(zone): Required<Zone>) -> impl axum::response::IntoResponse {
+    [(X_ZONE, zone.to_header_value().unwrap())]
+}
+
+#[tokio::test]
+async fn test_const_header_name_on_header_type() {
+    let app = Router::new().route("/", get(echo_zone_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header(X_ZONE, "eu-1")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-zone"], "eu-1");
+    assert_eq!(
+        <Zone as axum_required_headers::RequiredHeader>::HEADER_NAME,
+        "x-zone"
+    );
+}
+
+// ============================================================================
+// INTO RESPONSE PARTS TESTS
+// ============================================================================
+
+#[derive(Header, Debug)]
+#[header("x-correlation-id", infallible, to_header_value)]
+struct CorrelationId(String);
+
+async fn echo_correlation_handler(
+    Required(correlation_id): Required<CorrelationId>,
+) -> impl axum::response::IntoResponse {
+    (correlation_id, "echoed")
+}
+
+async fn echo_required_region_handler(
+    region: Required<Region>,
+) -> impl axum::response::IntoResponse {
+    (region, "echoed")
+}
+
+async fn echo_optional_region_handler(
+    region: Optional<Region>,
+) -> impl axum::response::IntoResponse {
+    (region, "echoed")
+}
+
+async fn invalid_region_handler() -> impl axum::response::IntoResponse {
+    (Required(Region("eu\nwest".to_string())), "echoed")
+}
+
+async fn send_echo(
+    handler: axum::routing::MethodRouter,
+    pairs: &[(&str, &str)],
+) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", handler);
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_derived_header_into_response_parts() {
+    let response = send_echo(
+        get(echo_correlation_handler),
+        &[("x-correlation-id", "req-42")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-correlation-id"], "req-42");
+}
+
+#[tokio::test]
+async fn test_required_into_response_parts() {
+    let response = send_echo(
+        get(echo_required_region_handler),
+        &[("x-region", "eu-west-1")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-region"], "eu-west-1");
+}
+
+#[tokio::test]
+async fn test_optional_into_response_parts() {
+    let response = send_echo(
+        get(echo_optional_region_handler),
+        &[("x-region", "eu-west-1")],
+    )
+    .await;
+    assert_eq!(response.headers()["x-region"], "eu-west-1");
+
+    let response = send_echo(get(echo_optional_region_handler), &[]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("x-region"));
+}
+
+#[tokio::test]
+async fn test_into_response_parts_invalid_value() {
+    let response = send_echo(get(invalid_region_handler), &[]).await;
+
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"], "invalid_header_value");
+}
+
+// ============================================================================
+// REQUIRED ALL TESTS
+// ============================================================================
+
+async fn required_all_handler(
+    RequiredAll((org_id, user_id, value)): RequiredAll<(OrganizationId, UserId, PositiveInt)>,
+) -> String {
+    format!("org: {}, user: {}, value: {}", org_id.0, user_id.0, value.0)
+}
+
+async fn send_required_all(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(required_all_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_required_all_extracts_tuple() {
+    let response = send_required_all(&[
+        ("x-organization-id", "org1"),
+        ("x-user-id", "user1"),
+        ("x-positive-int", "7"),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"org: org1, user: user1, value: 7");
+}
+
+#[tokio::test]
+async fn test_required_all_first_failure() {
+    // The first failing header in tuple order is reported
+    let response = send_required_all(&[("x-positive-int", "abc")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+    assert_eq!(
+        body["message"],
+        "Missing required header: `x-organization-id`"
+    );
+
+    let response = send_required_all(&[
+        ("x-organization-id", "org1"),
+        ("x-user-id", "user1"),
+        ("x-positive-int", "abc"),
+    ])
+    .await;
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[tokio::test]
+async fn test_required_all_arities() {
+    use axum::extract::FromRequestParts;
+
+    let (mut parts, ()) = Request::builder()
+        .uri("/")
+        .header("x-organization-id", "org1")
+        .header("x-positive-int", "7")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let RequiredAll((value,)) = RequiredAll::<(PositiveInt,)>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert_eq!(value, PositiveInt(7));
+
+    let RequiredAll((_, _, _, _, _, value)) = RequiredAll::<(
+        OrganizationId,
+        PositiveInt,
+        OrganizationId,
+        PositiveInt,
+        OrganizationId,
+        PositiveInt,
+    )>::from_request_parts(&mut parts, &())
+    .await
+    .unwrap();
+    assert_eq!(value, PositiveInt(7));
+}
+
+// ============================================================================
+// IMPL HEADER MACRO TESTS
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+struct ClientIp(std::net::IpAddr);
+
+impl FromStr for ClientIp {
+    type Err = std::net::AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(ClientIp)
+    }
+}
+
+const X_CLIENT_IP: &str = "x-client-ip";
+
+impl_header!(ClientIp, X_CLIENT_IP, aliases = ["x-real-ip"]);
+
+async fn client_ip_handler(
+    Required(ip): Required<ClientIp>,
+    Optional(maybe_ip): Optional<ClientIp>,
+) -> String {
+    format!("{} {:?}", ip.0, maybe_ip.map(|ip| ip.0))
+}
+
+#[tokio::test]
+async fn test_impl_header_macro() {
+    let send = |name: &'static str, value: &'static str| async move {
+        let app = Router::new().route("/", get(client_ip_handler));
+        let request = Request::builder()
+            .uri("/")
+            .header(name, value)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    };
+
+    let response = send("x-client-ip", "10.0.0.1").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"10.0.0.1 Some(10.0.0.1)");
+
+    let response = send("x-real-ip", "::1").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"::1 Some(::1)");
+
+    let response = send("x-client-ip", "not-an-ip").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// PRESENT TESTS
+// ============================================================================
+
+async fn present_handler(_: Present<PositiveInt>) -> &'static str {
+    "gated"
+}
+
+#[tokio::test]
+async fn test_present_gates_route() {
+    assert_eq!(std::mem::size_of::<Present<PositiveInt>>(), 0);
+
+    for (value, status) in [
+        (Some("5"), StatusCode::OK),
+        (Some("-5"), StatusCode::BAD_REQUEST),
+        (None, StatusCode::BAD_REQUEST),
+    ] {
+        let app = Router::new().route("/", get(present_handler));
+
+        let mut request = Request::builder().uri("/");
+        if let Some(value) = value {
+            request = request.header("x-positive-int", value);
+        }
+        let request = request.body(axum::body::Body::empty()).unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), status, "{value:?}");
+    }
+}
+
+// ============================================================================
+// NON EMPTY TESTS
+// ============================================================================
+
+#[derive(Header, Debug)]
+#[header("x-org-id", alias = "x-organization", non_empty, infallible)]
+struct OrgId(String);
+
+async fn org_handler(Required(OrgId(org)): Required<OrgId>) -> String {
+    format!("org: {org}")
+}
+
+async fn optional_org_handler(Optional(org): Optional<OrgId>) -> String {
+    format!("org: {:?}", org.map(|OrgId(org)| org))
+}
+
+async fn send_org(
+    handler: axum::routing::MethodRouter,
+    pairs: &[(&str, &str)],
+) -> (StatusCode, String) {
+    let app = Router::new().route("/", handler);
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_non_empty_required_rejects_empty_as_missing() {
+    let (status, body) = send_org(get(org_handler), &[("x-org-id", "")]).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body.contains("Missing required header: `x-org-id`"),
+        "{body}"
+    );
+
+    assert_eq!(
+        send_org(get(org_handler), &[("x-org-id", "acme")]).await,
+        (StatusCode::OK, "org: acme".to_owned())
+    );
+}
+
+#[tokio::test]
+async fn test_non_empty_falls_back_to_alias() {
+    assert_eq!(
+        send_org(
+            get(org_handler),
+            &[("x-org-id", ""), ("x-organization", "acme")]
+        )
+        .await,
+        (StatusCode::OK, "org: acme".to_owned())
+    );
+}
+
+#[tokio::test]
+async fn test_non_empty_optional_is_none() {
+    assert_eq!(
+        send_org(get(optional_org_handler), &[("x-org-id", "")]).await,
+        (StatusCode::OK, "org: None".to_owned())
+    );
+}
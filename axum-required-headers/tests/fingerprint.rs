@@ -0,0 +1,96 @@
+use axum_required_headers::{CertFingerprint, ParseFingerprintError, RequiredHeader};
+
+/// 32 bytes counting up from 0
+const BYTES: [u8; 32] = {
+    let mut bytes = [0; 32];
+    let mut i = 0;
+    while i < 32 {
+        bytes[i] = i as u8;
+        i += 1;
+    }
+    bytes
+};
+const HEX: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+// ============================================================================
+// FROM STR TESTS
+// ============================================================================
+
+#[test]
+fn test_fingerprint_header_name() {
+    assert_eq!(CertFingerprint::HEADER_NAME, "x-client-cert-sha256");
+}
+
+#[test]
+fn test_fingerprint_hex() {
+    assert_eq!(HEX.parse::<CertFingerprint>().unwrap().0, BYTES);
+    assert_eq!(
+        HEX.to_uppercase().parse::<CertFingerprint>().unwrap().0,
+        BYTES
+    );
+
+    let colon_separated = BYTES
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":");
+    assert_eq!(colon_separated.parse::<CertFingerprint>().unwrap().0, BYTES);
+}
+
+#[test]
+fn test_fingerprint_wrong_length() {
+    assert!(matches!(
+        HEX[2..].parse::<CertFingerprint>(),
+        Err(ParseFingerprintError::InvalidLength(31))
+    ));
+    assert!(matches!(
+        format!("{HEX}20").parse::<CertFingerprint>(),
+        Err(ParseFingerprintError::InvalidLength(33))
+    ));
+}
+
+#[test]
+fn test_fingerprint_bad_encoding() {
+    for value in ["", "abc", "00:01:2", "00::01", "not a fingerprint!"] {
+        assert!(
+            matches!(
+                value.parse::<CertFingerprint>(),
+                Err(ParseFingerprintError::InvalidEncoding)
+            ),
+            "{value}"
+        );
+    }
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn test_fingerprint_base64() {
+    use base64::Engine;
+    use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+
+    assert_eq!(
+        STANDARD.encode(BYTES).parse::<CertFingerprint>().unwrap().0,
+        BYTES
+    );
+    assert_eq!(
+        URL_SAFE_NO_PAD
+            .encode(BYTES)
+            .parse::<CertFingerprint>()
+            .unwrap()
+            .0,
+        BYTES
+    );
+    assert!(matches!(
+        STANDARD.encode([0u8; 20]).parse::<CertFingerprint>(),
+        Err(ParseFingerprintError::InvalidLength(20))
+    ));
+}
+
+#[cfg(not(feature = "base64"))]
+#[test]
+fn test_fingerprint_base64_requires_feature() {
+    assert!(matches!(
+        "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=".parse::<CertFingerprint>(),
+        Err(ParseFingerprintError::InvalidEncoding)
+    ));
+}
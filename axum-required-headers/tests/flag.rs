@@ -0,0 +1,131 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderMap, HeaderValue, Request},
+};
+use axum_required_headers::{Flag, HeaderError, Headers, ParseFlagError, Required, impl_header};
+use std::str::FromStr;
+
+#[derive(Headers)]
+pub struct FeatureHeaders {
+    #[header("x-enabled")]
+    pub enabled: Flag,
+
+    #[header("x-dry-run")]
+    pub dry_run: Option<Flag>,
+}
+
+pub struct Verbose(Flag);
+
+impl FromStr for Verbose {
+    type Err = ParseFlagError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Verbose)
+    }
+}
+
+impl_header!(Verbose, "x-verbose");
+
+fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+    pairs
+        .iter()
+        .map(|(name, value)| {
+            (
+                axum::http::HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            )
+        })
+        .collect()
+}
+
+// ============================================================================
+// PARSING TESTS
+// ============================================================================
+
+#[test]
+fn test_true_spellings() {
+    for value in ["true", "1", "yes", "on", "TRUE", "Yes", "ON", " on "] {
+        assert_eq!(value.parse::<Flag>().unwrap(), Flag(true), "{value:?}");
+    }
+}
+
+#[test]
+fn test_false_spellings() {
+    for value in ["false", "0", "no", "off", "False", "NO", "Off", "\t0"] {
+        assert_eq!(value.parse::<Flag>().unwrap(), Flag(false), "{value:?}");
+    }
+}
+
+#[test]
+fn test_rejected_spellings() {
+    for value in [
+        "",
+        "2",
+        "-1",
+        "y",
+        "n",
+        "t",
+        "enabled",
+        "yess",
+        "o n",
+        "true,false",
+    ] {
+        assert!(value.parse::<Flag>().is_err(), "{value:?}");
+    }
+}
+
+#[test]
+fn test_into_bool() {
+    assert!(bool::from(Flag(true)));
+    assert!(!bool::from(Flag::default()));
+}
+
+// ============================================================================
+// EXTRACTION TESTS
+// ============================================================================
+
+#[test]
+fn test_headers_field() {
+    let extracted =
+        FeatureHeaders::from_headers(&headers(&[("x-enabled", "yes"), ("x-dry-run", "off")]))
+            .unwrap();
+    assert_eq!(extracted.enabled, Flag(true));
+    assert_eq!(extracted.dry_run, Some(Flag(false)));
+}
+
+#[test]
+fn test_headers_field_rejects_unrecognized_value() {
+    assert_eq!(
+        FeatureHeaders::from_headers(&headers(&[("x-enabled", "maybe")])).err(),
+        Some(HeaderError::Parse("x-enabled"))
+    );
+    // malformed optional values are `None`
+    let extracted =
+        FeatureHeaders::from_headers(&headers(&[("x-enabled", "1"), ("x-dry-run", "maybe")]))
+            .unwrap();
+    assert_eq!(extracted.dry_run, None);
+}
+
+#[tokio::test]
+async fn test_required_named_flag() {
+    let (mut parts, ()) = Request::builder()
+        .header("x-verbose", "On")
+        .body(())
+        .unwrap()
+        .into_parts();
+    let Required(Verbose(verbose)) = Required::<Verbose>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert_eq!(verbose, Flag(true));
+
+    let (mut parts, ()) = Request::builder()
+        .header("x-verbose", "sometimes")
+        .body(())
+        .unwrap()
+        .into_parts();
+    let error = Required::<Verbose>::from_request_parts(&mut parts, &())
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(error, HeaderError::Parse("x-verbose"));
+}
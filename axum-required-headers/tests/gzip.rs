@@ -0,0 +1,109 @@
+#![cfg(all(feature = "base64", feature = "gzip", feature = "json"))]
+
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::Headers;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use common::{read_body_json, read_body_string, send};
+use flate2::{Compression, write::GzEncoder};
+use serde::Deserialize;
+use std::io::Write;
+
+#[derive(Debug, Deserialize)]
+pub struct Context {
+    pub tenant: String,
+    pub flags: Vec<String>,
+}
+
+#[derive(Headers)]
+pub struct ContextHeaders {
+    #[header("x-ctx", base64, gzip, json)]
+    pub context: Context,
+
+    #[header("x-extra", base64, gzip, json)]
+    pub extra: Option<Context>,
+}
+
+async fn handler(headers: ContextHeaders) -> String {
+    format!(
+        "tenant: {}, flags: {:?}, extra: {:?}",
+        headers.context.tenant,
+        headers.context.flags,
+        headers.extra.map(|extra| extra.tenant)
+    )
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler))
+}
+
+fn gzip_base64(payload: &[u8]) -> String {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(payload).unwrap();
+    STANDARD.encode(encoder.finish().unwrap())
+}
+
+async fn assert_stage_error(value: &str, stage: &str) {
+    let response = send(app(), &[("x-ctx", value)]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{stage}");
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("x-ctx"));
+    assert!(message.contains(&format!("`{stage}` stage")), "{message}");
+}
+
+#[tokio::test]
+async fn test_gzip_json_pipeline() {
+    let context = gzip_base64(br#"{"tenant": "acme", "flags": ["beta", "dark-mode"]}"#);
+    let extra = gzip_base64(br#"{"tenant": "other", "flags": []}"#);
+
+    let response = send(app(), &[("x-ctx", &context), ("x-extra", &extra)]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "tenant: acme, flags: [\"beta\", \"dark-mode\"], extra: Some(\"other\")"
+    );
+}
+
+#[tokio::test]
+async fn test_gzip_invalid_base64() {
+    assert_stage_error("not base64!", "base64").await;
+}
+
+#[tokio::test]
+async fn test_gzip_invalid_gzip() {
+    assert_stage_error(&STANDARD.encode(br#"{"tenant": "acme"}"#), "gzip").await;
+}
+
+#[tokio::test]
+async fn test_gzip_invalid_json() {
+    assert_stage_error(&gzip_base64(b"tenant=acme"), "json").await;
+    // valid JSON of the wrong shape
+    assert_stage_error(&gzip_base64(br#"{"tenant": 1}"#), "json").await;
+}
+
+#[tokio::test]
+async fn test_gzip_payload_too_large() {
+    let mut payload = br#"{"tenant": ""#.to_vec();
+    payload.extend(std::iter::repeat_n(b'a', 2 * 1024 * 1024));
+    payload.extend(br#"", "flags": []}"#);
+
+    assert_stage_error(&gzip_base64(&payload), "gzip").await;
+}
+
+#[tokio::test]
+async fn test_gzip_optional_invalid() {
+    let context = gzip_base64(br#"{"tenant": "acme", "flags": []}"#);
+
+    let response = send(app(), &[("x-ctx", &context), ("x-extra", "garbage")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "tenant: acme, flags: [], extra: None"
+    );
+}
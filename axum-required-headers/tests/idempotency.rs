@@ -0,0 +1,85 @@
+mod common;
+
+use axum::{Router, http::StatusCode, response::IntoResponse, routing::get};
+use axum_required_headers::{Headers, IdempotencyKey, Required};
+use common::{read_body_json, send};
+use http_body_util::BodyExt;
+
+#[derive(Headers)]
+pub struct PaymentHeaders {
+    #[header("idempotency-key", uuid)]
+    pub idempotency_key: IdempotencyKey,
+}
+
+async fn payment_handler(headers: PaymentHeaders) -> impl IntoResponse {
+    format!("key: {}", headers.idempotency_key.0)
+}
+
+async fn any_key_handler(Required(key): Required<IdempotencyKey>) -> impl IntoResponse {
+    format!("key: {}, uuid: {}", key.0, key.is_uuid())
+}
+
+// ============================================================================
+// IDEMPOTENCY KEY TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_idempotency_key_uuid() {
+    let app = Router::new().route("/", get(payment_handler));
+
+    let response = send(
+        app,
+        &[("idempotency-key", "67E55044-10b1-426f-9247-bb680e5fe0c8")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"key: 67E55044-10b1-426f-9247-bb680e5fe0c8");
+}
+
+#[tokio::test]
+async fn test_idempotency_key_not_a_uuid() {
+    for key in [
+        "order-42",
+        "67e5504410b1426f9247bb680e5fe0c8",
+        "67e55044-10b1-426f-9247-bb680e5fe0c",
+    ] {
+        let app = Router::new().route("/", get(payment_handler));
+
+        let response = send(app, &[("idempotency-key", key)]).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = read_body_json(response).await;
+        assert_eq!(body["error"], "header_validation_error");
+        assert!(body["message"].as_str().unwrap().contains("UUID"));
+    }
+}
+
+#[tokio::test]
+async fn test_idempotency_key_missing() {
+    let app = Router::new().route("/", get(payment_handler));
+
+    let response = send(app, &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_idempotency_key_any_format() {
+    let app = Router::new().route("/", get(any_key_handler));
+    let response = send(app, &[("idempotency-key", "order-42")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"key: order-42, uuid: false");
+
+    let app = Router::new().route("/", get(any_key_handler));
+    let response = send(app, &[("idempotency-key", "")]).await;
+    assert_eq!(
+        read_body_json(response).await["error"],
+        "header_validation_error"
+    );
+}
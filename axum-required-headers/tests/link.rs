@@ -0,0 +1,81 @@
+use axum_required_headers::{Link, Links};
+
+// ============================================================================
+// LINK PARSING TESTS
+// ============================================================================
+
+#[test]
+fn test_parse_multiple_links() {
+    let links: Links = concat!(
+        r#"<https://api.example.com/items?page=2&per_page=10>; rel="next", "#,
+        r#"<https://api.example.com/items?page=5>; rel=last"#,
+    )
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        links,
+        Links(vec![
+            Link {
+                uri: "https://api.example.com/items?page=2&per_page=10".to_owned(),
+                rel: "next".to_owned(),
+                params: vec![],
+            },
+            Link {
+                uri: "https://api.example.com/items?page=5".to_owned(),
+                rel: "last".to_owned(),
+                params: vec![],
+            },
+        ])
+    );
+    assert_eq!(
+        links.find("LAST").unwrap().uri,
+        "https://api.example.com/items?page=5"
+    );
+    assert!(links.find("prev").is_none());
+}
+
+#[test]
+fn test_parse_quoted_params() {
+    let links: Links = r#"</a,b;c>; Title="Chapter 2, \"Intro\"; part 1"; rel="next last"; type="text/html"; crossorigin"#
+        .parse()
+        .unwrap();
+
+    let link = &links.0[0];
+    assert_eq!(link.uri, "/a,b;c");
+    assert!(link.has_rel("next") && link.has_rel("last"));
+    assert_eq!(link.param("title"), Some(r#"Chapter 2, "Intro"; part 1"#));
+    assert_eq!(link.param("type"), Some("text/html"));
+    assert_eq!(link.param("crossorigin"), Some(""));
+}
+
+#[test]
+fn test_parse_invalid_links() {
+    for invalid in [
+        "https://example.com; rel=next",
+        "<https://example.com",
+        "<https://example.com>",
+        r#"<https://example.com>; rel="next"#,
+        "<https://example.com>; rel=next <https://example.com/2>; rel=last",
+    ] {
+        assert!(invalid.parse::<Links>().is_err(), "{invalid}");
+    }
+    assert_eq!("".parse::<Links>().unwrap(), Links::default());
+}
+
+#[test]
+fn test_find_next_link() {
+    let links: Links =
+        r#"<https://example.com/?page=1>; rel="prev", <https://example.com/?page=3>; rel="next""#
+            .parse()
+            .unwrap();
+
+    assert_eq!(
+        links.find("next").unwrap().uri,
+        "https://example.com/?page=3"
+    );
+    assert_eq!(
+        links.find("prev").unwrap().uri,
+        "https://example.com/?page=1"
+    );
+}
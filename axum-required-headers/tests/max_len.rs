@@ -0,0 +1,121 @@
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::{Header, MaxLen, Optional, Required};
+use common::{read_body_json, send};
+use std::convert::Infallible;
+use std::str::FromStr;
+
+#[derive(Header)]
+#[header("x-short-id")]
+struct ShortId(String);
+
+impl FromStr for ShortId {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+async fn required_handler(Required(id): Required<MaxLen<ShortId, 8>>) -> String {
+    format!("id: {}", id.into_inner().0)
+}
+
+async fn optional_handler(Optional(id): Optional<MaxLen<ShortId, 8>>) -> String {
+    match id {
+        Some(id) => format!("id: {}", id.into_inner().0),
+        None => "no id".to_string(),
+    }
+}
+
+// ============================================================================
+// REQUIRED MAX LEN TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_required_max_len_below_bound() {
+    let app = Router::new().route("/", get(required_handler));
+
+    let response = send(app, &[("x-short-id", "abc")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_required_max_len_at_bound() {
+    let app = Router::new().route("/", get(required_handler));
+
+    let response = send(app, &[("x-short-id", "abcdefgh")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_required_max_len_above_bound() {
+    let app = Router::new().route("/", get(required_handler));
+
+    let response = send(app, &[("x-short-id", "abcdefghi")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_too_long");
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("x-short-id"));
+    assert!(message.contains('8'));
+}
+
+#[tokio::test]
+async fn test_required_max_len_missing() {
+    let app = Router::new().route("/", get(required_handler));
+
+    let response = send(app, &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+// ============================================================================
+// OPTIONAL MAX LEN TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_optional_max_len_at_bound() {
+    let app = Router::new().route("/", get(optional_handler));
+
+    let response = send(app, &[("x-short-id", "abcdefgh")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_optional_max_len_above_bound() {
+    let app = Router::new().route("/", get(optional_handler));
+
+    let response = send(app, &[("x-short-id", "abcdefghi")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_too_long");
+}
+
+#[tokio::test]
+async fn test_optional_max_len_missing() {
+    let app = Router::new().route("/", get(optional_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// ============================================================================
+// FROM STR TESTS
+// ============================================================================
+
+#[test]
+fn test_max_len_from_str() {
+    assert!("abcdefgh".parse::<MaxLen<String, 8>>().is_ok());
+    assert!("abcdefghi".parse::<MaxLen<String, 8>>().is_err());
+    assert!("".parse::<MaxLen<String, 0>>().is_ok());
+}
@@ -0,0 +1,355 @@
+mod common;
+
+use axum::{
+    Router,
+    http::{HeaderName, Request, StatusCode, header, request::Parts},
+    routing::get,
+};
+use axum_required_headers::{
+    AcceptCharset, AcceptedType, Header, Optional, QualityList, Required, ResponseFormat, Vary,
+};
+use common::{read_body_string, send};
+
+const SUPPORTED: &[ResponseFormat] = &[ResponseFormat::Json, ResponseFormat::Xml];
+
+fn parts_with_accept(accept: Option<&str>) -> Parts {
+    let mut request = Request::builder().uri("/");
+    if let Some(accept) = accept {
+        request = request.header("accept", accept);
+    }
+    request.body(()).unwrap().into_parts().0
+}
+
+fn negotiate(accept: Option<&str>) -> Option<ResponseFormat> {
+    AcceptedType::negotiate(&parts_with_accept(accept), SUPPORTED)
+}
+
+// ============================================================================
+// NEGOTIATION TESTS
+// ============================================================================
+
+#[test]
+fn test_negotiate_exact_match() {
+    assert_eq!(
+        negotiate(Some("application/xml")),
+        Some(ResponseFormat::Xml)
+    );
+    assert_eq!(
+        negotiate(Some("application/json")),
+        Some(ResponseFormat::Json)
+    );
+}
+
+#[test]
+fn test_negotiate_highest_quality_wins() {
+    assert_eq!(
+        negotiate(Some("application/json;q=0.5, application/xml;q=0.9")),
+        Some(ResponseFormat::Xml)
+    );
+}
+
+#[test]
+fn test_negotiate_quality_tie_prefers_supported_order() {
+    assert_eq!(
+        negotiate(Some("application/xml;q=0.8, application/json;q=0.8")),
+        Some(ResponseFormat::Json)
+    );
+}
+
+#[test]
+fn test_negotiate_wildcard() {
+    assert_eq!(negotiate(Some("*/*")), Some(ResponseFormat::Json));
+    assert_eq!(negotiate(Some("application/*")), Some(ResponseFormat::Json));
+}
+
+#[test]
+fn test_negotiate_specific_range_overrides_wildcard() {
+    // JSON is explicitly excluded, everything else is acceptable
+    assert_eq!(
+        negotiate(Some("application/json;q=0, */*;q=0.1")),
+        Some(ResponseFormat::Xml)
+    );
+}
+
+#[test]
+fn test_negotiate_no_acceptable_format() {
+    assert_eq!(negotiate(Some("text/html")), None);
+    assert_eq!(negotiate(Some("*/*;q=0")), None);
+}
+
+#[test]
+fn test_negotiate_missing_accept_is_wildcard() {
+    assert_eq!(negotiate(None), Some(ResponseFormat::Json));
+}
+
+// ============================================================================
+// PARSING TESTS
+// ============================================================================
+
+#[test]
+fn test_parse_accept() {
+    let accepted: AcceptedType = "text/html, application/json;q=0.9;charset=utf-8"
+        .parse()
+        .unwrap();
+
+    assert_eq!(accepted.0.len(), 2);
+    assert_eq!(accepted.0[0].type_, "text");
+    assert_eq!(accepted.0[0].subtype, "html");
+    assert_eq!(accepted.0[0].quality, 1.0);
+    assert_eq!(accepted.0[1].quality, 0.9);
+}
+
+#[test]
+fn test_parse_accept_invalid() {
+    assert!("text".parse::<AcceptedType>().is_err());
+    assert!("*/json".parse::<AcceptedType>().is_err());
+    assert!("text/html;q=2".parse::<AcceptedType>().is_err());
+    assert!("text/html;q=abc".parse::<AcceptedType>().is_err());
+}
+
+// ============================================================================
+// EXTRACTOR TESTS
+// ============================================================================
+
+async fn accepted_handler(Optional(accepted): Optional<AcceptedType>) -> String {
+    match accepted.and_then(|accepted| accepted.best(SUPPORTED)) {
+        Some(format) => format.media_type().to_string(),
+        None => "none".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_accepted_type_extractor() {
+    let app = Router::new().route("/", get(accepted_handler));
+
+    let response = send(app, &[("accept", "application/xml")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "application/xml");
+}
+
+// ============================================================================
+// VARY TESTS
+// ============================================================================
+
+async fn vary_handler(parts: Parts) -> (Vary, &'static str) {
+    let body = match AcceptedType::negotiate(&parts, SUPPORTED) {
+        Some(ResponseFormat::Xml) => "<ok/>",
+        _ => "{\"ok\":true}",
+    };
+    (Vary::accept(), body)
+}
+
+async fn vary_merge_handler() -> ([(HeaderName, &'static str); 1], Vary, &'static str) {
+    (
+        [(header::VARY, "Origin, accept")],
+        Vary::accept().with(header::ACCEPT_LANGUAGE),
+        "ok",
+    )
+}
+
+fn vary_header(response: &axum::http::Response<axum::body::Body>) -> Vec<&str> {
+    response
+        .headers()
+        .get_all(header::VARY)
+        .iter()
+        .map(|value| value.to_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_vary_lists_negotiated_header() {
+    let app = Router::new().route("/", get(vary_handler));
+
+    let response = send(app, &[("accept", "application/xml")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(vary_header(&response), ["accept"]);
+}
+
+#[tokio::test]
+async fn test_vary_merges_existing_header_names() {
+    let app = Router::new().route("/", get(vary_merge_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(vary_header(&response), ["Origin, accept, accept-language"]);
+}
+
+#[test]
+fn test_vary_deduplicates_names() {
+    let vary = Vary::new()
+        .with(header::ACCEPT)
+        .with(header::ACCEPT_ENCODING)
+        .with(header::ACCEPT);
+
+    assert_eq!(
+        vary.header_names(),
+        [header::ACCEPT, header::ACCEPT_ENCODING]
+    );
+}
+
+// ============================================================================
+// ACCEPT CHARSET TESTS
+// ============================================================================
+
+const CHARSETS: &[&str] = &["utf-8", "iso-8859-1"];
+
+fn best_charset(accept: &str) -> Option<String> {
+    accept.parse::<AcceptCharset>().unwrap().best(CHARSETS)
+}
+
+#[test]
+fn test_charset_wildcard() {
+    assert_eq!(best_charset("*"), Some("utf-8".to_string()));
+    assert_eq!(
+        best_charset("utf-8;q=0.5, *;q=0.8"),
+        Some("iso-8859-1".to_string())
+    );
+}
+
+#[test]
+fn test_charset_explicit_preference() {
+    assert_eq!(
+        best_charset("ISO-8859-1, utf-8;q=0.7"),
+        Some("iso-8859-1".to_string())
+    );
+    // Unlisted charsets are unacceptable without `*`
+    assert_eq!(best_charset("utf-16"), None);
+}
+
+#[test]
+fn test_charset_zero_quality_excluded() {
+    assert_eq!(best_charset("utf-8;q=0, *"), Some("iso-8859-1".to_string()));
+    assert_eq!(best_charset("*;q=0"), None);
+}
+
+#[test]
+fn test_charset_absent_accepts_any() {
+    assert_eq!(
+        AcceptCharset::default().best(CHARSETS),
+        Some("utf-8".to_string())
+    );
+}
+
+#[test]
+fn test_parse_accept_charset_invalid() {
+    assert!("utf-8;q=2".parse::<AcceptCharset>().is_err());
+    assert!(";q=0.5".parse::<AcceptCharset>().is_err());
+}
+
+async fn charset_handler(Optional(accept): Optional<AcceptCharset>) -> String {
+    accept
+        .unwrap_or_default()
+        .best(CHARSETS)
+        .unwrap_or_else(|| "none".to_string())
+}
+
+#[tokio::test]
+async fn test_accept_charset_extractor() {
+    for (accept, expected) in [
+        (&[][..], "utf-8"),
+        (&[("accept-charset", "iso-8859-1")], "iso-8859-1"),
+        (&[("accept-charset", "utf-16")], "none"),
+    ] {
+        let app = Router::new().route("/", get(charset_handler));
+
+        let response = send(app, accept).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(read_body_string(response).await, expected, "{accept:?}");
+    }
+}
+
+// ============================================================================
+// QUALITY LIST TESTS
+// ============================================================================
+
+#[derive(Header, Debug, Clone, Copy, PartialEq)]
+#[header("accept-encoding")]
+enum Encoding {
+    Gzip,
+    Br,
+    Identity,
+    #[header_value("*")]
+    Any,
+}
+
+fn preferred<T: std::str::FromStr + Clone>(value: &str) -> Vec<T> {
+    let list: QualityList<T> = value.parse().unwrap();
+    list.preferred().cloned().collect()
+}
+
+#[test]
+fn test_quality_list_sorted_by_quality() {
+    assert_eq!(
+        preferred::<Encoding>("identity;q=0.1, gzip;q=0.8, br, *;q=0.5"),
+        [
+            Encoding::Br,
+            Encoding::Gzip,
+            Encoding::Any,
+            Encoding::Identity
+        ]
+    );
+}
+
+#[test]
+fn test_quality_list_ties_keep_order() {
+    assert_eq!(
+        preferred::<Encoding>("gzip;q=0.5, identity, br;q=0.5"),
+        [Encoding::Identity, Encoding::Gzip, Encoding::Br]
+    );
+}
+
+#[test]
+fn test_quality_list_zero_quality_not_preferred() {
+    let list: QualityList<Encoding> = "gzip, identity;q=0".parse().unwrap();
+    assert_eq!(list.items().len(), 2);
+    assert_eq!(list.items()[1].quality, 0.0);
+    assert_eq!(list.preferred().collect::<Vec<_>>(), [&Encoding::Gzip]);
+}
+
+#[test]
+fn test_quality_list_keeps_other_params() {
+    assert_eq!(
+        preferred::<String>("text/html;level=1;q=0.5, text/plain ; Q=0.9, ,"),
+        ["text/plain", "text/html;level=1"]
+    );
+    assert!("".parse::<QualityList<String>>().unwrap().is_empty());
+}
+
+#[test]
+fn test_quality_list_invalid() {
+    for value in ["gzip;q=1.5", "gzip;q=-0.1", "gzip;q=high", "gzip, zstd"] {
+        assert!(value.parse::<QualityList<Encoding>>().is_err(), "{value:?}");
+    }
+}
+
+async fn encoding_handler(Required(encodings): Required<QualityList<Encoding>>) -> String {
+    format!("{:?}", encodings.preferred().next())
+}
+
+#[tokio::test]
+async fn test_quality_list_extractor() {
+    for (accept, status, expected) in [
+        (
+            &[("accept-encoding", "gzip;q=0.5, br")][..],
+            StatusCode::OK,
+            "Some(Br)",
+        ),
+        (
+            &[("accept-encoding", "gzip;q=2")],
+            StatusCode::BAD_REQUEST,
+            "",
+        ),
+        (&[], StatusCode::BAD_REQUEST, ""),
+    ] {
+        let app = Router::new().route("/", get(encoding_handler));
+
+        let response = send(app, accept).await;
+        assert_eq!(response.status(), status, "{accept:?}");
+
+        if status == StatusCode::OK {
+            assert_eq!(read_body_string(response).await, expected);
+        }
+    }
+}
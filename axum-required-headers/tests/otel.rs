@@ -0,0 +1,81 @@
+#![cfg(feature = "otel")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::Headers;
+use common::send;
+use opentelemetry::{
+    Context, KeyValue,
+    context::FutureExt,
+    trace::{TraceContextExt, Tracer, TracerProvider},
+};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+#[derive(Headers)]
+pub struct TracedHeaders {
+    #[header("x-tenant-id", otel_attr = "tenant.id")]
+    pub tenant_id: String,
+
+    #[header("x-plan", otel_attr = "tenant.plan")]
+    pub plan: Option<String>,
+
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+async fn handler(_headers: TracedHeaders) {}
+
+/// Sends the request within an active span, returning the attributes it ended with
+async fn span_attributes(pairs: &[(&str, &str)]) -> (StatusCode, Vec<KeyValue>) {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let span = provider.tracer("test").start("request");
+    let cx = Context::current_with_span(span);
+
+    let app = Router::new().route("/", get(handler));
+    let response = send(app, pairs).with_context(cx.clone()).await;
+
+    cx.span().end();
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    (response.status(), spans[0].attributes.clone())
+}
+
+#[tokio::test]
+async fn test_otel_attributes_set() {
+    let (status, attributes) = span_attributes(&[
+        ("x-tenant-id", "acme"),
+        ("x-plan", "enterprise"),
+        ("x-user-id", "user123"),
+    ])
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        attributes,
+        [
+            KeyValue::new("tenant.id", "acme"),
+            KeyValue::new("tenant.plan", "enterprise"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_otel_absent_optional_not_set() {
+    let (status, attributes) =
+        span_attributes(&[("x-tenant-id", "acme"), ("x-user-id", "user123")]).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(attributes, [KeyValue::new("tenant.id", "acme")]);
+}
+
+#[tokio::test]
+async fn test_otel_rejected_request_not_recorded() {
+    let (status, attributes) = span_attributes(&[("x-tenant-id", "acme")]).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(attributes.is_empty());
+}
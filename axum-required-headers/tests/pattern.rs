@@ -0,0 +1,76 @@
+#![cfg(feature = "regex")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::Headers;
+use common::{read_body_json, read_body_string, send};
+
+#[derive(Headers)]
+pub struct TraceHeaders {
+    #[header("x-trace-id", pattern = "^[0-9a-f-]{36}$")]
+    pub trace_id: String,
+
+    #[header("x-region", pattern = "^[a-z]{2}-[a-z]+-[0-9]$", trim)]
+    pub region: Option<String>,
+}
+
+async fn handler(headers: TraceHeaders) -> String {
+    format!("trace: {}, region: {:?}", headers.trace_id, headers.region)
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler))
+}
+
+const TRACE_ID: &str = "0af76519-16cd-43dd-8448-eb211c80319c";
+
+#[tokio::test]
+async fn test_pattern_match() {
+    let response = send(
+        app(),
+        &[("x-trace-id", TRACE_ID), ("x-region", " eu-west-1 ")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        format!("trace: {TRACE_ID}, region: Some(\"eu-west-1\")")
+    );
+}
+
+#[tokio::test]
+async fn test_pattern_mismatch() {
+    for value in ["not-a-trace-id", "0AF76519-16CD-43DD-8448-EB211C80319C"] {
+        let response = send(app(), &[("x-trace-id", value)]).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{value}");
+
+        let body = read_body_json(response).await;
+        assert_eq!(body["error"], "header_validation_error");
+        let message = body["message"].as_str().unwrap();
+        assert!(message.contains("x-trace-id"));
+        assert!(message.contains("^[0-9a-f-]{36}$"));
+    }
+}
+
+#[tokio::test]
+async fn test_pattern_missing() {
+    let response = send(app(), &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_pattern_optional_mismatch() {
+    // Optional values that don't match are treated like parse failures and become `None`
+    let response = send(app(), &[("x-trace-id", TRACE_ID), ("x-region", "europe")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        format!("trace: {TRACE_ID}, region: None")
+    );
+}
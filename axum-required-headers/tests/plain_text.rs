@@ -0,0 +1,50 @@
+//! Error bodies without the default `json` feature, run with
+//! `cargo test --no-default-features --test plain_text`.
+#![cfg(not(feature = "json"))]
+
+use axum::{
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use axum_required_headers::{HeaderError, HeaderRejection, ResponseFormat};
+use http_body_util::BodyExt;
+
+async fn read_body_string(response: axum::http::Response<axum::body::Body>) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+// ============================================================================
+// PLAIN TEXT ERROR TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_error_renders_as_plain_text() {
+    let response = HeaderError::Missing("x-user-id").into_response();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(
+        read_body_string(response).await,
+        "Missing required header: `x-user-id`"
+    );
+}
+
+#[tokio::test]
+async fn test_json_rejection_falls_back_to_plain_text() {
+    let response = HeaderRejection::new(HeaderError::Parse("x-page"))
+        .with_format(ResponseFormat::Json)
+        .into_response();
+
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(
+        read_body_string(response).await,
+        "Failed to parse header value: `x-page`"
+    );
+}
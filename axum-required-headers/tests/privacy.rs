@@ -0,0 +1,39 @@
+use axum_required_headers::{DoNotTrack, GlobalPrivacyControl, OptionalHeader};
+
+// ============================================================================
+// PRIVACY SIGNAL TESTS
+// ============================================================================
+
+#[test]
+fn test_privacy_signals_enabled() {
+    assert_eq!("1".parse(), Ok(DoNotTrack(Some(true))));
+    assert_eq!("1".parse(), Ok(GlobalPrivacyControl(true)));
+}
+
+#[test]
+fn test_privacy_signals_disabled() {
+    assert_eq!("0".parse(), Ok(DoNotTrack(Some(false))));
+    assert_eq!("0".parse(), Ok(GlobalPrivacyControl(false)));
+}
+
+#[test]
+fn test_privacy_signals_garbage_is_not_rejected() {
+    assert_eq!("yes".parse(), Ok(DoNotTrack(None)));
+    assert_eq!("".parse(), Ok(DoNotTrack(None)));
+    assert_eq!("true".parse(), Ok(GlobalPrivacyControl(false)));
+    assert_eq!("2".parse(), Ok(GlobalPrivacyControl(false)));
+}
+
+#[test]
+fn test_privacy_signals_trim_whitespace() {
+    assert_eq!(" 1 ".parse(), Ok(DoNotTrack(Some(true))));
+}
+
+#[test]
+fn test_privacy_signal_header_names() {
+    assert_eq!(<DoNotTrack as OptionalHeader>::HEADER_NAME, "dnt");
+    assert_eq!(
+        <GlobalPrivacyControl as OptionalHeader>::HEADER_NAME,
+        "sec-gpc"
+    );
+}
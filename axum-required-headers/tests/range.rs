@@ -0,0 +1,119 @@
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::{
+    ByteRanges, Optional, ParseRangeError, Range, RangeSpec, RangeUnit, Required,
+};
+use common::{read_body_json, read_body_string, send};
+
+struct Items;
+
+impl RangeUnit for Items {
+    const UNIT: &'static str = "items";
+}
+
+async fn bytes_handler(Optional(range): Optional<ByteRanges>) -> String {
+    match range {
+        Some(range) => format!("parts: {:?}", range.satisfiable(1000)),
+        None => "full".to_string(),
+    }
+}
+
+async fn items_handler(Required(range): Required<Range<Items>>) -> String {
+    format!("{}: {:?}", range.unit(), range.ranges())
+}
+
+// ============================================================================
+// BYTES TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_byte_ranges_satisfiable() {
+    let app = Router::new().route("/", get(bytes_handler));
+
+    let response = send(app, &[("range", "bytes=0-99, 500-, -100, 2000-3000")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "parts: [0..100, 500..1000, 900..1000]"
+    );
+}
+
+#[tokio::test]
+async fn test_byte_ranges_absent() {
+    let app = Router::new().route("/", get(bytes_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(read_body_string(response).await, "full");
+}
+
+#[test]
+fn test_byte_ranges_parse() {
+    let range: ByteRanges = "Bytes=0-0,,-5".parse().unwrap();
+    assert_eq!(range.unit(), "bytes");
+    assert_eq!(
+        range.ranges(),
+        [RangeSpec::FromTo(0, 0), RangeSpec::Suffix(5)]
+    );
+
+    for value in [
+        "bytes=",
+        "bytes=5-1",
+        "bytes=a-b",
+        "bytes=-",
+        "bytes=+1-2",
+        "0-10",
+    ] {
+        assert!(
+            matches!(
+                value.parse::<ByteRanges>(),
+                Err(ParseRangeError::Invalid(_))
+            ),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn test_range_spec_resolve() {
+    assert_eq!(RangeSpec::FromTo(10, 2000).resolve(100), Some(10..100));
+    assert_eq!(RangeSpec::From(100).resolve(100), None);
+    assert_eq!(RangeSpec::Suffix(500).resolve(100), Some(0..100));
+    assert_eq!(RangeSpec::Suffix(0).resolve(100), None);
+}
+
+// ============================================================================
+// CUSTOM UNIT TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_custom_unit() {
+    let app = Router::new().route("/", get(items_handler));
+
+    let response = send(app, &[("range", "items=0-24")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "items: [FromTo(0, 24)]");
+}
+
+#[tokio::test]
+async fn test_mismatched_unit_rejected() {
+    let app = Router::new().route("/", get(items_handler));
+
+    let response = send(app, &[("range", "bytes=0-24")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    assert!(body["message"].as_str().unwrap().contains("range"));
+
+    assert_eq!(
+        "bytes=0-24".parse::<Range<Items>>().unwrap_err(),
+        ParseRangeError::UnitMismatch {
+            expected: "items",
+            found: "bytes".to_string(),
+        }
+    );
+}
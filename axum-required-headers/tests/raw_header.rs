@@ -0,0 +1,143 @@
+mod common;
+
+use axum::{
+    Router,
+    extract::FromRequestParts,
+    http::{HeaderValue, Request, StatusCode, request::Parts},
+    routing::get,
+};
+use axum_required_headers::{Header, RawHeader, Required};
+use common::{read_body_string, send};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts the allocations of the current thread, to compare extractors.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[derive(Header)]
+#[header("x-api-key", infallible)]
+struct ApiKey(String);
+
+async fn handler(api_key: RawHeader<ApiKey>) -> String {
+    format!("key: {}", api_key.as_str())
+}
+
+async fn optional_handler(api_key: Option<RawHeader<ApiKey>>) -> String {
+    format!("key: {:?}", api_key.as_ref().map(RawHeader::as_str))
+}
+
+fn parts_with_key(value: HeaderValue) -> Parts {
+    let mut parts = Request::builder().uri("/").body(()).unwrap().into_parts().0;
+    parts.headers.insert("x-api-key", value);
+    parts
+}
+
+// ============================================================================
+// RAW HEADER TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_raw_header_extraction() {
+    let app = Router::new().route("/", get(handler));
+
+    let response = send(app, &[("x-api-key", "live_123")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "key: live_123");
+}
+
+#[tokio::test]
+async fn test_raw_header_missing_and_optional() {
+    let app = Router::new().route("/", get(handler));
+    let response = send(app, &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let app = Router::new().route("/", get(optional_handler));
+    let response = send(app, &[]).await;
+    assert_eq!(read_body_string(response).await, "key: None");
+}
+
+#[tokio::test]
+async fn test_raw_header_rejects_non_ascii() {
+    let mut parts = parts_with_key(HeaderValue::from_bytes(&[0xc3]).unwrap());
+
+    let error = RawHeader::<ApiKey>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid header value (not valid ASCII): `x-api-key`"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_raw_header_does_not_allocate() {
+    let mut parts = parts_with_key(HeaderValue::from_static("live_0123456789abcdef"));
+
+    let before = allocations();
+    let raw = RawHeader::<ApiKey>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    let raw_allocations = allocations() - before;
+    assert_eq!(raw.as_str(), "live_0123456789abcdef");
+
+    let before = allocations();
+    let Required(owned) = Required::<ApiKey>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    let owned_allocations = allocations() - before;
+    assert_eq!(owned.0, "live_0123456789abcdef");
+
+    assert_eq!(raw_allocations, 0);
+    assert!(owned_allocations >= 1);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_raw_header_as_str_does_not_allocate() {
+    // values parsed at runtime are uniquely owned, the first clone moves them to shared storage
+    let mut parts = parts_with_key(HeaderValue::from_str("live_0123456789abcdef").unwrap());
+
+    let before = allocations();
+    let raw = RawHeader::<ApiKey>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    let extract_allocations = allocations() - before;
+
+    let before = allocations();
+    let value = raw.as_str();
+    let as_str_allocations = allocations() - before;
+    assert_eq!(value, "live_0123456789abcdef");
+
+    let before = allocations();
+    let again = RawHeader::<ApiKey>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    let shared_allocations = allocations() - before;
+    assert_eq!(again.as_str(), value);
+
+    assert!(extract_allocations <= 1);
+    assert_eq!(shared_allocations, 0);
+    assert_eq!(as_str_allocations, 0);
+}
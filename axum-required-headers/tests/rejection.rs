@@ -0,0 +1,447 @@
+mod common;
+
+use axum::{
+    Router,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use axum_required_headers::{HeaderError, HeaderRejection, Headers, ResponseFormat};
+use common::{read_body_string, send};
+
+#[derive(Headers)]
+#[headers(negotiate_error)]
+pub struct NegotiatedHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+async fn negotiated_handler(headers: NegotiatedHeaders) -> impl IntoResponse {
+    headers.user_id
+}
+
+fn negotiated_app() -> Router {
+    Router::new().route("/", get(negotiated_handler))
+}
+
+fn content_type(response: &axum::http::Response<axum::body::Body>) -> &str {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .unwrap()
+        .to_str()
+        .unwrap()
+}
+
+// ============================================================================
+// NEGOTIATED ERROR FORMAT TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_negotiated_error_json() {
+    let response = send(negotiated_app(), &[("accept", "application/json")]).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(content_type(&response), "application/json");
+
+    let body: serde_json::Value = serde_json::from_str(&read_body_string(response).await).unwrap();
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_negotiated_error_plain_text() {
+    let response = send(negotiated_app(), &[("accept", "text/plain")]).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(content_type(&response).starts_with("text/plain"));
+    assert_eq!(
+        read_body_string(response).await,
+        "Missing required header: `x-user-id`"
+    );
+}
+
+#[tokio::test]
+async fn test_negotiated_error_html() {
+    let response = send(
+        negotiated_app(),
+        &[("accept", "text/html,application/xhtml+xml;q=0.9,*/*;q=0.8")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(content_type(&response).starts_with("text/html"));
+
+    let body = read_body_string(response).await;
+    assert!(body.contains("<h1>Bad Request</h1>"));
+    assert!(body.contains("x-user-id"));
+}
+
+#[tokio::test]
+async fn test_negotiated_error_defaults_to_json() {
+    for accept in [&[][..], &[("accept", "*/*")], &[("accept", "image/png")]] {
+        let response = send(negotiated_app(), accept).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(content_type(&response), "application/json");
+    }
+}
+
+#[tokio::test]
+async fn test_negotiated_success() {
+    let response = send(
+        negotiated_app(),
+        &[("accept", "text/plain"), ("x-user-id", "user123")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+// ============================================================================
+// HEADER REJECTION TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_rejection_default_matches_header_error() {
+    let rejection = HeaderRejection::from(HeaderError::Parse("x-count")).into_response();
+    let error = HeaderError::Parse("x-count").into_response();
+
+    assert_eq!(rejection.status(), error.status());
+    assert_eq!(
+        read_body_string(rejection).await,
+        read_body_string(error).await
+    );
+}
+
+#[tokio::test]
+async fn test_rejection_html_escapes_message() {
+    let response = HeaderRejection::new(HeaderError::Missing("x-<script>"))
+        .with_format(ResponseFormat::Html)
+        .into_response();
+
+    let body = read_body_string(response).await;
+    assert!(body.contains("x-&lt;script&gt;"));
+    assert!(!body.contains("<script>"));
+}
+
+// ============================================================================
+// ADVERTISED REQUIRED HEADERS TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(advertise_required)]
+pub struct AdvertisedHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-tenant-id")]
+    pub tenant_id: String,
+
+    #[header("x-count")]
+    pub count: Option<u32>,
+}
+
+async fn advertised_handler(headers: AdvertisedHeaders) -> impl IntoResponse {
+    format!("{}, {}", headers.user_id, headers.tenant_id)
+}
+
+#[test]
+fn test_header_names_constants() {
+    assert_eq!(
+        AdvertisedHeaders::HEADER_NAMES,
+        ["x-user-id", "x-tenant-id", "x-count"]
+    );
+    assert_eq!(
+        AdvertisedHeaders::REQUIRED_HEADER_NAMES,
+        ["x-user-id", "x-tenant-id"]
+    );
+}
+
+#[tokio::test]
+async fn test_missing_header_lists_required_headers() {
+    let app = Router::new().route("/", get(advertised_handler));
+
+    let response = send(app, &[("x-user-id", "user123")]).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers().get("x-required-headers").unwrap(),
+        "x-user-id, x-tenant-id"
+    );
+}
+
+#[tokio::test]
+async fn test_parse_error_does_not_list_required_headers() {
+    let app = Router::new().route("/", get(advertised_handler));
+
+    let response = send(app, &[("x-user-id", "user123"), ("x-tenant-id", "日本語")]).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get("x-required-headers").is_none());
+}
+
+#[tokio::test]
+async fn test_default_rejection_does_not_list_required_headers() {
+    let response = send(negotiated_app(), &[]).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get("x-required-headers").is_none());
+}
+
+// ============================================================================
+// STATUS CODE TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(missing_status = 401)]
+pub struct AuthHeaders {
+    #[header("authorization")]
+    pub authorization: String,
+
+    #[header("x-attempt")]
+    pub attempt: u32,
+}
+
+#[derive(Headers)]
+#[headers(status = 422, missing_status = 401)]
+pub struct StrictAuthHeaders {
+    #[header("authorization")]
+    pub authorization: String,
+}
+
+async fn auth_handler(headers: AuthHeaders) -> impl IntoResponse {
+    format!("{} ({})", headers.authorization, headers.attempt)
+}
+
+async fn strict_auth_handler(headers: StrictAuthHeaders) -> impl IntoResponse {
+    headers.authorization
+}
+
+#[tokio::test]
+async fn test_missing_status() {
+    let app = Router::new().route("/", get(auth_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = serde_json::from_str(&read_body_string(response).await).unwrap();
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_missing_status_keeps_default_for_malformed() {
+    let app = Router::new().route("/", get(auth_handler));
+
+    let response = send(
+        app,
+        &[("authorization", "Bearer token"), ("x-attempt", "first")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_status_and_missing_status() {
+    let app = Router::new().route("/", get(strict_auth_handler));
+
+    let response = send(app.clone(), &[]).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = send(app, &[("authorization", "日本語")]).await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[derive(Headers)]
+#[headers(error_status = 200)]
+pub struct LegacyHeaders {
+    #[header("x-client-id")]
+    pub client_id: String,
+
+    #[header("x-attempt")]
+    pub attempt: u32,
+}
+
+async fn legacy_handler(headers: LegacyHeaders) -> impl IntoResponse {
+    format!("{} ({})", headers.client_id, headers.attempt)
+}
+
+#[tokio::test]
+async fn test_error_status_missing_header() {
+    let app = Router::new().route("/", get(legacy_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&read_body_string(response).await).unwrap();
+    assert_eq!(body["error"], "missing_header");
+    assert!(body["message"].as_str().unwrap().contains("x-client-id"));
+}
+
+#[tokio::test]
+async fn test_error_status_malformed_header() {
+    let app = Router::new().route("/", get(legacy_handler));
+
+    let response = send(app, &[("x-client-id", "legacy"), ("x-attempt", "first")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&read_body_string(response).await).unwrap();
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[derive(Headers)]
+#[headers(on_missing_redirect = "/login?next=%2F")]
+pub struct SessionHeaders {
+    #[header("x-session-id")]
+    pub session_id: String,
+
+    #[header("x-attempt")]
+    pub attempt: Option<u32>,
+}
+
+async fn session_handler(headers: SessionHeaders) -> impl IntoResponse {
+    headers.session_id
+}
+
+#[tokio::test]
+async fn test_on_missing_redirect() {
+    let app = Router::new().route("/", get(session_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()["location"], "/login?next=%2F");
+    assert_eq!(read_body_string(response).await, "");
+}
+
+#[tokio::test]
+async fn test_on_missing_redirect_keeps_other_errors() {
+    let app = Router::new().route("/", get(session_handler));
+
+    let response = send(app, &[("x-session-id", "日本語")]).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get("location").is_none());
+}
+
+#[test]
+fn test_header_error_with_status() {
+    let response = HeaderError::Parse("x-count")
+        .with_status(StatusCode::UNPROCESSABLE_ENTITY)
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+// ============================================================================
+// ERROR DTO TESTS
+// ============================================================================
+
+#[derive(serde::Serialize)]
+pub struct ErrorResponse {
+    code: &'static str,
+    detail: String,
+}
+
+impl From<&HeaderError> for ErrorResponse {
+    fn from(error: &HeaderError) -> Self {
+        ErrorResponse {
+            code: "INVALID_HEADERS",
+            detail: error.to_string(),
+        }
+    }
+}
+
+#[derive(Headers)]
+#[headers(error_dto = "ErrorResponse", missing_status = 401)]
+pub struct DtoHeaders {
+    #[header("authorization")]
+    pub authorization: String,
+}
+
+async fn dto_handler(headers: DtoHeaders) -> impl IntoResponse {
+    headers.authorization
+}
+
+#[tokio::test]
+async fn test_error_dto_body() {
+    let app = Router::new().route("/", get(dto_handler));
+
+    let response = send(app, &[]).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = serde_json::from_str(&read_body_string(response).await).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "code": "INVALID_HEADERS",
+            "detail": "Missing required header: `authorization`",
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_error_dto_only_changes_json_bodies() {
+    let response = HeaderRejection::new(HeaderError::Parse("x-page"))
+        .with_format(ResponseFormat::PlainText)
+        .with_error_dto::<ErrorResponse>()
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        read_body_string(response).await,
+        "Failed to parse header value: `x-page`"
+    );
+}
+
+// ============================================================================
+// EXTRA RESPONSE HEADER TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_with_header_adds_response_headers() {
+    let response = HeaderError::Missing("idempotency-key")
+        .with_header(
+            header::LINK,
+            header::HeaderValue::from_static(
+                "<https://example.com/docs/idempotency>; rel=\"help\"",
+            ),
+        )
+        .with_header(
+            header::LINK,
+            header::HeaderValue::from_static("<https://example.com/docs>; rel=\"index\""),
+        )
+        .into_response();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let links: Vec<_> = response.headers().get_all(header::LINK).iter().collect();
+    assert_eq!(
+        links,
+        [
+            "<https://example.com/docs/idempotency>; rel=\"help\"",
+            "<https://example.com/docs>; rel=\"index\"",
+        ]
+    );
+    assert!(content_type(&response).starts_with("application/json"));
+}
+
+#[tokio::test]
+async fn test_with_header_replaces_rendered_header() {
+    let response = HeaderRejection::new(HeaderError::Parse("x-page"))
+        .with_format(ResponseFormat::PlainText)
+        .with_header(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("text/x-error"),
+        )
+        .into_response();
+
+    assert_eq!(content_type(&response), "text/x-error");
+}
+
+#[tokio::test]
+async fn test_without_extra_headers_response_is_unchanged() {
+    let plain = HeaderError::Missing("idempotency-key").into_response();
+    let rejection = HeaderRejection::new(HeaderError::Missing("idempotency-key")).into_response();
+
+    assert_eq!(plain.headers(), rejection.headers());
+}
@@ -0,0 +1,73 @@
+use axum_required_headers::RetryAfter;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// `Fri, 31 Dec 1999 23:59:59 GMT`
+const DATE_SECS: u64 = 946_684_799;
+
+/// When to retry according to `value`, received at `UNIX_EPOCH + 1000s`, in seconds since the epoch
+fn retry_at(value: &str) -> u64 {
+    let now = UNIX_EPOCH + Duration::from_secs(1_000);
+    let retry_after: RetryAfter = value.parse().unwrap();
+    retry_after
+        .retry_at(now)
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+// ============================================================================
+// RETRY AFTER TESTS
+// ============================================================================
+
+#[test]
+fn test_retry_after_delay() {
+    assert_eq!(retry_at("120"), 1120);
+}
+
+#[test]
+fn test_retry_after_date() {
+    assert_eq!(retry_at("Fri, 31 Dec 1999 23:59:59 GMT"), DATE_SECS);
+}
+
+#[test]
+fn test_retry_after_invalid() {
+    assert!("soon".parse::<RetryAfter>().is_err());
+}
+
+// ============================================================================
+// FROM STR TESTS
+// ============================================================================
+
+#[test]
+fn test_retry_after_from_str() {
+    assert_eq!(
+        "0".parse::<RetryAfter>().unwrap(),
+        RetryAfter::Delay(Duration::ZERO)
+    );
+    assert_eq!(
+        "Friday, 31-Dec-99 23:59:59 GMT"
+            .parse::<RetryAfter>()
+            .unwrap(),
+        RetryAfter::Date(UNIX_EPOCH + Duration::from_secs(DATE_SECS))
+    );
+    assert_eq!(
+        "Fri Dec 31 23:59:59 1999".parse::<RetryAfter>().unwrap(),
+        RetryAfter::Date(UNIX_EPOCH + Duration::from_secs(DATE_SECS))
+    );
+
+    for invalid in ["", "-5", "1.5", "tomorrow"] {
+        let error = invalid.parse::<RetryAfter>().unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("expected a number of seconds or an HTTP-date")
+        );
+    }
+}
+
+#[test]
+fn test_retry_after_date_ignores_now() {
+    let date = UNIX_EPOCH + Duration::from_secs(DATE_SECS);
+
+    assert_eq!(RetryAfter::Date(date).retry_at(SystemTime::now()), date);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use axum::{
+    Router,
+    http::{HeaderMap, HeaderValue, StatusCode},
+    routing::get,
+};
+use axum_required_headers::{HeaderError, HeaderSchema, SchemaHeaders};
+use common::{read_body_json, read_body_string, send};
+
+fn schema() -> HeaderSchema {
+    HeaderSchema::builder()
+        .require("X-Id")
+        .optional("x-hint")
+        .build()
+        .unwrap()
+}
+
+async fn handler(SchemaHeaders(headers): SchemaHeaders) -> String {
+    format!("id: {}, hint: {:?}", headers["x-id"], headers.get("x-hint"))
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler)).with_state(schema())
+}
+
+// ============================================================================
+// SCHEMA EXTRACTION TESTS
+// ============================================================================
+
+#[test]
+fn test_schema_extract() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-id", HeaderValue::from_static("42"));
+    headers.insert("x-other", HeaderValue::from_static("ignored"));
+
+    let values = schema().extract(&headers).unwrap();
+    assert_eq!(values.len(), 1);
+    assert_eq!(values["x-id"], "42");
+}
+
+#[test]
+fn test_schema_extract_errors() {
+    assert_eq!(
+        schema().extract(&HeaderMap::new()),
+        Err(HeaderError::Missing("x-id"))
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-id", HeaderValue::from_static("42"));
+    headers.insert("x-hint", HeaderValue::from_bytes(&[0xc3]).unwrap());
+    assert_eq!(
+        schema().extract(&headers),
+        Err(HeaderError::InvalidValue("x-hint"))
+    );
+}
+
+#[test]
+fn test_schema_build() {
+    assert!(HeaderSchema::builder().require("bad name").build().is_err());
+
+    let schema = HeaderSchema::builder()
+        .optional("x-a")
+        .require("X-A")
+        .require("x-b")
+        .build()
+        .unwrap();
+    assert_eq!(
+        schema.required_header_names().collect::<Vec<_>>(),
+        ["x-a", "x-b"]
+    );
+}
+
+#[tokio::test]
+async fn test_schema_headers_extractor() {
+    let response = send(app(), &[("x-id", "42"), ("x-hint", "fast")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "id: 42, hint: Some(\"fast\")"
+    );
+}
+
+#[tokio::test]
+async fn test_schema_headers_extractor_missing() {
+    let response = send(app(), &[("x-hint", "fast")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
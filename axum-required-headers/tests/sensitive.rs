@@ -0,0 +1,110 @@
+mod common;
+
+use axum::{extract::FromRequestParts, http::HeaderValue, response::IntoResponse};
+use axum_required_headers::{Header, HeaderError, Headers, Optional, Required, Sensitive};
+use common::request;
+use http_body_util::BodyExt;
+
+#[derive(Debug, Clone, PartialEq, Header)]
+#[header("x-api-key", infallible)]
+pub struct ApiKey(String);
+
+#[derive(Debug, Headers)]
+pub struct SensitiveHeaders {
+    #[header("authorization", sensitive)]
+    pub authorization: HeaderValue,
+
+    #[header("x-signatures", sensitive)]
+    pub signatures: Vec<HeaderValue>,
+
+    #[header("x-secret-pin", sensitive)]
+    pub pin: Option<Sensitive<u32>>,
+
+    #[header("x-trace-id")]
+    pub trace_id: HeaderValue,
+}
+
+fn parts(pairs: &[(&str, &str)]) -> axum::http::request::Parts {
+    request(pairs).body(()).unwrap().into_parts().0
+}
+
+// ============================================================================
+// SENSITIVE FIELD TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_sensitive_header_values_are_marked() {
+    let mut parts = parts(&[
+        ("authorization", "Bearer secret-token"),
+        ("x-signatures", "sig-a"),
+        ("x-signatures", "sig-b"),
+        ("x-secret-pin", "1234"),
+        ("x-trace-id", "abc"),
+    ]);
+
+    let headers = SensitiveHeaders::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert!(headers.authorization.is_sensitive());
+    assert!(headers.signatures.iter().all(HeaderValue::is_sensitive));
+    assert!(!headers.trace_id.is_sensitive());
+    assert_eq!(headers.pin.as_deref(), Some(&1234));
+
+    let debug = format!("{headers:?}");
+    assert!(!debug.contains("secret-token"), "{debug}");
+    assert!(!debug.contains("sig-a"), "{debug}");
+    assert!(!debug.contains("1234"), "{debug}");
+    assert!(debug.contains("abc"), "{debug}");
+}
+
+#[tokio::test]
+async fn test_errors_never_include_values() {
+    #[derive(Debug, Headers)]
+    pub struct PinHeaders {
+        #[header("x-secret-pin", sensitive)]
+        pub pin: Sensitive<u32>,
+    }
+
+    let mut valid = parts(&[("x-secret-pin", "42")]);
+    let headers = PinHeaders::from_request_parts(&mut valid, &())
+        .await
+        .unwrap();
+    assert_eq!(*headers.pin, 42);
+
+    let mut parts = parts(&[("x-secret-pin", "not-a-pin-9876")]);
+    let error = PinHeaders::from_request_parts(&mut parts, &())
+        .await
+        .unwrap_err();
+    assert_eq!(error, HeaderError::Parse("x-secret-pin"));
+    assert!(!error.to_string().contains("9876"));
+
+    let body = error.into_response().into_body().collect().await.unwrap();
+    let body = String::from_utf8(body.to_bytes().to_vec()).unwrap();
+    assert!(body.contains("x-secret-pin"), "{body}");
+    assert!(!body.contains("9876"), "{body}");
+}
+
+// ============================================================================
+// SENSITIVE ADAPTER TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_sensitive_adapter_redacts_debug() {
+    let mut parts = parts(&[("x-api-key", "key-123")]);
+
+    let api_key = Required::<Sensitive<ApiKey>>::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert_eq!(format!("{api_key:?}"), "Required(<redacted>)");
+    assert_eq!(api_key.0.0, ApiKey("key-123".to_owned()));
+
+    let optional =
+        <Optional<Sensitive<ApiKey>> as FromRequestParts<()>>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+    assert_eq!(format!("{optional:?}"), "Optional(Some(<redacted>))");
+    assert_eq!(
+        optional.into_inner().map(Sensitive::into_inner),
+        Some(ApiKey("key-123".to_owned()))
+    );
+}
@@ -603,3 +603,2910 @@ async fn test_headers_mixed_case() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+// ============================================================================
+// RENAME TESTS
+// ============================================================================
+
+async fn read_body_string(response: axum::http::Response<axum::body::Body>) -> String {
+    let body = response.into_body();
+    let bytes = body.collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[derive(Headers)]
+#[headers(rename_all = "kebab-case", prefix = "x-")]
+pub struct RenamedHeaders {
+    // derived from the rule: `x-user-id`
+    pub user_id: String,
+
+    // `rename` wins over the rule but still inherits the prefix: `x-ETag`
+    #[header(rename = "ETag")]
+    pub entity_tag: Option<String>,
+
+    // an explicit literal wins over everything
+    #[header("if-match")]
+    pub if_match: Option<String>,
+}
+
+async fn renamed_handler(headers: RenamedHeaders) -> impl IntoResponse {
+    format!(
+        "user: {}, etag: {}, if-match: {}",
+        headers.user_id,
+        headers.entity_tag.as_deref().unwrap_or("none"),
+        headers.if_match.as_deref().unwrap_or("none"),
+    )
+}
+
+#[tokio::test]
+async fn test_rename_all_derives_header_names() {
+    let app = Router::new().route("/", get(renamed_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .header("x-etag", "\"abc\"")
+        .header("if-match", "\"def\"")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, etag: \"abc\", if-match: \"def\""
+    );
+}
+
+#[tokio::test]
+async fn test_rename_overrides_rule() {
+    let app = Router::new().route("/", get(renamed_handler));
+
+    // The rule would produce `x-entity-tag`, but the rename wins
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .header("x-entity-tag", "\"abc\"")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, etag: none, if-match: none"
+    );
+}
+
+#[tokio::test]
+async fn test_rename_all_missing_error_uses_derived_name() {
+    let app = Router::new().route("/", get(renamed_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert!(body["message"].as_str().unwrap().contains("x-user-id"));
+}
+
+// ============================================================================
+// LENIENT EXTRACTION TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(lenient)]
+pub struct LenientHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-count")]
+    pub count: u32,
+
+    #[header("x-limit")]
+    pub limit: u32,
+
+    #[header("x-page")]
+    pub page: Option<u32>,
+
+    #[header("x-tenant")]
+    pub tenant: Option<String>,
+}
+
+#[test]
+fn test_lenient_extraction_collects_warnings() {
+    let (parts, _) = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .header("x-limit", "not-a-number")
+        .header("x-page", "-1")
+        .header("x-tenant", "tenant-1")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let (headers, warnings) = LenientHeaders::from_request_parts_lenient(&parts);
+
+    // Good headers are extracted as usual
+    assert_eq!(headers.user_id, "user123");
+    assert_eq!(headers.tenant.as_deref(), Some("tenant-1"));
+
+    // Malformed or missing headers fall back to defaults
+    assert_eq!(headers.count, 0);
+    assert_eq!(headers.limit, 0);
+    assert_eq!(headers.page, None);
+
+    let summary: Vec<_> = warnings
+        .iter()
+        .map(|warning| (warning.name, warning.error.to_string()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("x-count", "Missing required header: `x-count`".to_string()),
+            (
+                "x-limit",
+                "Failed to parse header value: `x-limit`".to_string()
+            ),
+            (
+                "x-page",
+                "Failed to parse header value: `x-page`".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_lenient_extraction_without_problems() {
+    let (parts, _) = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .header("x-count", "1")
+        .header("x-limit", "10")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let (headers, warnings) = LenientHeaders::from_request_parts_lenient(&parts);
+
+    assert!(warnings.is_empty());
+    assert_eq!(headers.count, 1);
+    assert_eq!(headers.limit, 10);
+    assert_eq!(headers.page, None);
+}
+
+#[tokio::test]
+async fn test_lenient_struct_still_strict_as_extractor() {
+    async fn handler(headers: LenientHeaders) -> impl IntoResponse {
+        headers.user_id
+    }
+
+    let app = Router::new().route("/", get(handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// OPTIONAL STRUCT EXTRACTION TESTS
+// ============================================================================
+
+async fn optional_struct_handler(headers: Option<ParseableHeaders>) -> impl IntoResponse {
+    match headers {
+        Some(headers) => format!("count: {}", headers.count.0),
+        None => "no headers".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_optional_struct_present() {
+    let app = Router::new().route("/", get(optional_struct_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-count", "42")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "count: 42");
+}
+
+#[tokio::test]
+async fn test_optional_struct_required_absent() {
+    let app = Router::new().route("/", get(optional_struct_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-optional-count", "1")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "no headers");
+}
+
+#[tokio::test]
+async fn test_optional_struct_malformed_still_errors() {
+    let app = Router::new().route("/", get(optional_struct_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-count", "not-a-number")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+async fn optional_multiple_handler(headers: Option<MultipleRequiredHeaders>) -> impl IntoResponse {
+    match headers {
+        Some(headers) => format!("{}, {}, {}", headers.first, headers.second, headers.third),
+        None => "no headers".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_optional_struct_partially_present_is_none() {
+    let app = Router::new().route("/", get(optional_multiple_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-first", "one")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "no headers");
+}
+
+#[derive(Headers)]
+#[headers(none_if_all_absent)]
+pub struct AllOrNothingHeaders {
+    #[header("x-first")]
+    pub first: String,
+
+    #[header("x-second")]
+    pub second: String,
+}
+
+async fn all_or_nothing_handler(headers: Option<AllOrNothingHeaders>) -> impl IntoResponse {
+    match headers {
+        Some(headers) => format!("{}, {}", headers.first, headers.second),
+        None => "no headers".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_none_if_all_absent_all_absent() {
+    let app = Router::new().route("/", get(all_or_nothing_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "no headers");
+}
+
+#[tokio::test]
+async fn test_none_if_all_absent_partially_present_errors() {
+    let app = Router::new().route("/", get(all_or_nothing_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-first", "one")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+    assert!(body["message"].as_str().unwrap().contains("x-second"));
+}
+
+// ============================================================================
+// RANGE VALIDATION TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct RangeHeaders {
+    #[header("x-offset", range(0..=10000))]
+    pub offset: i64,
+
+    #[header("x-delta", range(-5..5))]
+    pub delta: Option<i32>,
+}
+
+async fn range_handler(headers: RangeHeaders) -> impl IntoResponse {
+    format!("offset: {}, delta: {:?}", headers.offset, headers.delta)
+}
+
+async fn send_range(offset: &str, delta: Option<&str>) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(range_handler));
+
+    let mut request = Request::builder().uri("/").header("x-offset", offset);
+    if let Some(delta) = delta {
+        request = request.header("x-delta", delta);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_range_within() {
+    for offset in ["0", "5000", "10000"] {
+        let response = send_range(offset, None).await;
+        assert_eq!(response.status(), StatusCode::OK, "offset {offset}");
+    }
+}
+
+#[tokio::test]
+async fn test_range_below() {
+    let response = send_range("-1", None).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_validation_error");
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("x-offset"));
+    assert!(message.contains("0..=10000"));
+}
+
+#[tokio::test]
+async fn test_range_above() {
+    let response = send_range("10001", None).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_validation_error");
+}
+
+#[tokio::test]
+async fn test_exclusive_range_optional_field() {
+    let response = send_range("1", Some("-5")).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "offset: 1, delta: Some(-5)"
+    );
+
+    // Out of range optional values are treated like parse failures and become `None`
+    let response = send_range("1", Some("5")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "offset: 1, delta: None");
+}
+
+// ============================================================================
+// LENGTH VALIDATION TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct LengthHeaders {
+    #[header("x-api-key", min_len = 32, max_len = 64)]
+    pub api_key: String,
+
+    #[header("x-label", max_len = 4, trim)]
+    pub label: Option<String>,
+}
+
+async fn length_handler(headers: LengthHeaders) -> impl IntoResponse {
+    format!("key: {}, label: {:?}", headers.api_key.len(), headers.label)
+}
+
+async fn send_length(api_key: &str, label: Option<&str>) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(length_handler));
+
+    let mut request = Request::builder().uri("/").header("x-api-key", api_key);
+    if let Some(label) = label {
+        request = request.header("x-label", label);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_length_within_bounds() {
+    for len in [32, 48, 64] {
+        let response = send_length(&"k".repeat(len), None).await;
+        assert_eq!(response.status(), StatusCode::OK, "length {len}");
+        assert_eq!(
+            read_body_string(response).await,
+            format!("key: {len}, label: None")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_length_below_min() {
+    let response = send_length(&"k".repeat(31), None).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_validation_error");
+    let message = body["message"].as_str().unwrap();
+    assert!(message.contains("x-api-key"));
+    assert!(message.contains("between 32 and 64 bytes"));
+}
+
+#[tokio::test]
+async fn test_length_above_max() {
+    let response = send_length(&"k".repeat(65), None).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_validation_error");
+}
+
+#[tokio::test]
+async fn test_length_optional_field() {
+    let key = "k".repeat(32);
+
+    // the length is checked after trimming
+    let response = send_length(&key, Some("  abcd  ")).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "key: 32, label: Some(\"abcd\")"
+    );
+
+    // Too long optional values are treated like parse failures and become `None`
+    let response = send_length(&key, Some("abcde")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "key: 32, label: None");
+}
+
+// ============================================================================
+// SOURCE EXTENSION TESTS
+// ============================================================================
+
+/// Headers canonicalized by a preprocessing layer
+#[derive(Clone)]
+pub struct CanonicalHeaders(axum::http::HeaderMap);
+
+impl AsRef<axum::http::HeaderMap> for CanonicalHeaders {
+    fn as_ref(&self) -> &axum::http::HeaderMap {
+        &self.0
+    }
+}
+
+#[derive(Headers)]
+#[headers(source_extension = "CanonicalHeaders")]
+pub struct CanonicalUserHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-locale")]
+    pub locale: Option<String>,
+}
+
+async fn canonical_handler(headers: CanonicalUserHeaders) -> impl IntoResponse {
+    format!("user: {}, locale: {:?}", headers.user_id, headers.locale)
+}
+
+async fn optional_canonical_handler(headers: Option<CanonicalUserHeaders>) -> impl IntoResponse {
+    match headers {
+        Some(headers) => format!("user: {}", headers.user_id),
+        None => "no headers".to_string(),
+    }
+}
+
+fn canonical(pairs: &[(&'static str, &'static str)]) -> CanonicalHeaders {
+    let mut map = axum::http::HeaderMap::new();
+    for (name, value) in pairs {
+        map.insert(*name, axum::http::HeaderValue::from_static(value));
+    }
+    CanonicalHeaders(map)
+}
+
+#[tokio::test]
+async fn test_source_extension_reads_extension_map() {
+    let app = Router::new().route("/", get(canonical_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "raw")
+        .extension(canonical(&[("x-user-id", "canonical"), ("x-locale", "en")]))
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: canonical, locale: Some(\"en\")"
+    );
+}
+
+#[tokio::test]
+async fn test_source_extension_ignores_raw_headers() {
+    let app = Router::new().route("/", get(canonical_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "raw")
+        .extension(canonical(&[]))
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_source_extension_absent() {
+    let app = Router::new().route("/", get(canonical_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "raw")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_source_extension_optional_struct() {
+    let app = Router::new().route("/", get(optional_canonical_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "raw")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "no headers");
+}
+
+// ============================================================================
+// DEFAULT VALUE TESTS
+// ============================================================================
+
+fn default_retries() -> u32 {
+    3
+}
+
+#[derive(Headers)]
+#[headers(lenient)]
+pub struct DefaultHeaders {
+    #[header("x-region", default = "us-east-1")]
+    pub region: String,
+
+    #[header("x-retries", default = default_retries())]
+    pub retries: u32,
+
+    #[header("x-suffix", default = "")]
+    pub suffix: String,
+
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+async fn default_handler(headers: DefaultHeaders) -> impl IntoResponse {
+    format!(
+        "region: {}, retries: {}, suffix: {:?}",
+        headers.region, headers.retries, headers.suffix
+    )
+}
+
+async fn send_defaults(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(default_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_defaults_used_when_absent() {
+    let response = send_defaults(&[("x-user-id", "user123")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "region: us-east-1, retries: 3, suffix: \"\""
+    );
+}
+
+#[tokio::test]
+async fn test_present_header_overrides_default() {
+    let response = send_defaults(&[
+        ("x-user-id", "user123"),
+        ("x-region", "eu-west-1"),
+        ("x-retries", "5"),
+        ("x-suffix", "beta"),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "region: eu-west-1, retries: 5, suffix: \"beta\""
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_header_with_default_rejected() {
+    let response = send_defaults(&[("x-user-id", "user123"), ("x-retries", "many")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[tokio::test]
+async fn test_required_header_without_default_still_missing() {
+    let response = send_defaults(&[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[test]
+fn test_defaulted_headers_not_required() {
+    assert_eq!(DefaultHeaders::REQUIRED_HEADER_NAMES, ["x-user-id"]);
+}
+
+#[test]
+fn test_lenient_defaults_without_warnings() {
+    let (parts, _) = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    let (headers, warnings) = DefaultHeaders::from_request_parts_lenient(&parts);
+
+    assert!(warnings.is_empty());
+    assert_eq!(headers.region, "us-east-1");
+    assert_eq!(headers.retries, 3);
+}
+
+// ============================================================================
+// COLLECT ERRORS TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(collect_errors)]
+pub struct CollectedHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-tenant-id")]
+    pub tenant_id: String,
+
+    #[header("x-count")]
+    pub count: u32,
+
+    #[header("x-page")]
+    pub page: Option<u32>,
+}
+
+async fn collected_handler(headers: CollectedHeaders) -> impl IntoResponse {
+    format!(
+        "user: {}, tenant: {}, count: {}, page: {:?}",
+        headers.user_id, headers.tenant_id, headers.count, headers.page
+    )
+}
+
+async fn send_collected(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(collected_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_collect_errors_all_present() {
+    let response = send_collected(&[
+        ("x-user-id", "user123"),
+        ("x-tenant-id", "tenant-1"),
+        ("x-count", "2"),
+        ("x-page", "invalid"),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, tenant: tenant-1, count: 2, page: None"
+    );
+}
+
+#[tokio::test]
+async fn test_collect_errors_reports_every_problem() {
+    let response = send_collected(&[("x-count", "many")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "multiple_header_errors");
+
+    let errors: Vec<_> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["error"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        errors,
+        ["missing_header", "missing_header", "header_parse_error"]
+    );
+    assert!(
+        body["errors"][1]["message"]
+            .as_str()
+            .unwrap()
+            .contains("x-tenant-id")
+    );
+}
+
+#[tokio::test]
+async fn test_collect_errors_single_error_unchanged() {
+    let response = send_collected(&[("x-user-id", "user123"), ("x-tenant-id", "tenant-1")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "error": "missing_header",
+            "message": "Missing required header: `x-count`",
+        })
+    );
+}
+
+// ============================================================================
+// ALIAS TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct AliasHeaders {
+    #[header("x-request-id", alias = "x-correlation-id")]
+    pub request_id: String,
+
+    #[header("x-locale", alias = "accept-language")]
+    pub locale: Option<String>,
+}
+
+async fn alias_handler(headers: AliasHeaders) -> impl IntoResponse {
+    format!(
+        "request: {}, locale: {:?}",
+        headers.request_id, headers.locale
+    )
+}
+
+async fn send_alias(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(alias_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_alias_fallback() {
+    let response = send_alias(&[("X-Correlation-Id", "abc"), ("accept-language", "en")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "request: abc, locale: Some(\"en\")"
+    );
+}
+
+#[tokio::test]
+async fn test_alias_primary_preferred() {
+    let response = send_alias(&[("x-correlation-id", "abc"), ("x-request-id", "def")]).await;
+
+    assert_eq!(
+        read_body_string(response).await,
+        "request: def, locale: None"
+    );
+}
+
+#[tokio::test]
+async fn test_alias_missing_mentions_primary_name() {
+    let response = send_alias(&[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["message"], "Missing required header: `x-request-id`");
+}
+
+// ============================================================================
+// MULTIPLE OCCURRENCE (VEC) TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct VecHeaders {
+    #[header("x-forwarded-for")]
+    pub forwarded_for: Vec<String>,
+
+    #[header("x-weight", range(0..=100))]
+    pub weights: Option<Vec<u32>>,
+}
+
+async fn vec_handler(headers: VecHeaders) -> impl IntoResponse {
+    format!(
+        "forwarded: {:?}, weights: {:?}",
+        headers.forwarded_for, headers.weights
+    )
+}
+
+async fn send_vec(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(vec_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_vec_collects_every_occurrence() {
+    let response = send_vec(&[
+        ("x-forwarded-for", "10.0.0.1"),
+        ("x-forwarded-for", "10.0.0.2"),
+        ("x-weight", "1"),
+        ("x-weight", "99"),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "forwarded: [\"10.0.0.1\", \"10.0.0.2\"], weights: Some([1, 99])"
+    );
+}
+
+#[tokio::test]
+async fn test_vec_required_missing() {
+    let response = send_vec(&[("x-weight", "1")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_vec_optional_absent() {
+    let response = send_vec(&[("x-forwarded-for", "10.0.0.1")]).await;
+
+    assert_eq!(
+        read_body_string(response).await,
+        "forwarded: [\"10.0.0.1\"], weights: None"
+    );
+}
+
+#[tokio::test]
+async fn test_vec_element_failure() {
+    // Any invalid element makes an optional `Vec` `None`
+    let response = send_vec(&[
+        ("x-forwarded-for", "10.0.0.1"),
+        ("x-weight", "1"),
+        ("x-weight", "101"),
+    ])
+    .await;
+    assert_eq!(
+        read_body_string(response).await,
+        "forwarded: [\"10.0.0.1\"], weights: None"
+    );
+}
+
+#[derive(Headers)]
+pub struct RequiredVecHeaders {
+    #[header("x-id")]
+    pub ids: Vec<u64>,
+}
+
+#[tokio::test]
+async fn test_vec_required_parse_failure() {
+    async fn handler(headers: RequiredVecHeaders) -> impl IntoResponse {
+        format!("{:?}", headers.ids)
+    }
+
+    let app = Router::new().route("/", get(handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-id", "1")
+        .header("x-id", "two")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    assert_eq!(body["message"], "Failed to parse header value: `x-id`");
+}
+
+// ============================================================================
+// TRIM TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct TrimHeaders {
+    #[header("x-count", trim)]
+    pub count: u32,
+
+    #[header("x-name", trim)]
+    pub name: Option<String>,
+
+    #[header("x-raw")]
+    pub raw: Option<String>,
+}
+
+async fn trim_handler(headers: TrimHeaders) -> impl IntoResponse {
+    format!(
+        "count: {}, name: {:?}, raw: {:?}",
+        headers.count, headers.name, headers.raw
+    )
+}
+
+async fn send_trim(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(trim_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_trim_required_and_optional() {
+    let response = send_trim(&[
+        ("x-count", "  42\t"),
+        ("x-name", " alice "),
+        ("x-raw", " bob "),
+    ])
+    .await;
+
+    // fields without `trim` keep their value unless the `default-trim` feature is enabled
+    let raw = if cfg!(feature = "default-trim") {
+        "bob"
+    } else {
+        " bob "
+    };
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        format!("count: 42, name: Some(\"alice\"), raw: Some({raw:?})")
+    );
+}
+
+#[tokio::test]
+async fn test_trim_still_rejects_invalid_values() {
+    let response = send_trim(&[("x-count", " 4 2 ")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+// ============================================================================
+// RAW VALUE TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct RawHeaders {
+    #[header("x-blob")]
+    pub blob: Vec<u8>,
+
+    #[header("x-signature")]
+    pub signature: Option<axum::http::HeaderValue>,
+
+    #[header("x-chunk")]
+    pub chunks: Option<Vec<Vec<u8>>>,
+}
+
+async fn raw_handler(headers: RawHeaders) -> impl IntoResponse {
+    format!(
+        "blob: {:?}, signature: {:?}, chunks: {:?}",
+        headers.blob, headers.signature, headers.chunks
+    )
+}
+
+async fn send_raw(pairs: &[(&str, &[u8])]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(raw_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, axum::http::HeaderValue::from_bytes(value).unwrap());
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_raw_values_accept_non_ascii_bytes() {
+    let response = send_raw(&[
+        ("x-blob", &[0xff, b'a', 0x80]),
+        ("x-signature", &[b's', 0xe9]),
+        ("x-chunk", &[0xc3, b'x']),
+        ("x-chunk", &[0xfe]),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "blob: [255, 97, 128], signature: Some(\"s\\xe9\"), chunks: Some([[195, 120], [254]])"
+    );
+}
+
+#[tokio::test]
+async fn test_raw_value_required_missing() {
+    let response = send_raw(&[("x-signature", b"sig")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_raw_value_optional_absent() {
+    let response = send_raw(&[("x-blob", b"abc")]).await;
+
+    assert_eq!(
+        read_body_string(response).await,
+        "blob: [97, 98, 99], signature: None, chunks: None"
+    );
+}
+
+// ============================================================================
+// LIST TESTS
+// ============================================================================
+
+#[derive(Debug, PartialEq)]
+pub enum Capability {
+    Streaming,
+    Compression,
+    Batching,
+}
+
+impl FromStr for Capability {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "streaming" => Ok(Capability::Streaming),
+            "compression" => Ok(Capability::Compression),
+            "batching" => Ok(Capability::Batching),
+            other => Err(format!("unknown capability `{other}`")),
+        }
+    }
+}
+
+#[derive(Headers)]
+pub struct StrictListHeaders {
+    #[header("x-capabilities", list)]
+    pub capabilities: Vec<Capability>,
+}
+
+#[derive(Headers)]
+pub struct LenientListHeaders {
+    #[header("x-capabilities", list, skip_unknown)]
+    pub capabilities: Option<Vec<Capability>>,
+}
+
+async fn strict_list_handler(headers: StrictListHeaders) -> impl IntoResponse {
+    format!("{:?}", headers.capabilities)
+}
+
+async fn lenient_list_handler(headers: LenientListHeaders) -> impl IntoResponse {
+    format!("{:?}", headers.capabilities)
+}
+
+async fn send_list(app: Router, values: &[&str]) -> axum::http::Response<axum::body::Body> {
+    let mut request = Request::builder().uri("/");
+    for value in values {
+        request = request.header("x-capabilities", *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_list_known_tokens() {
+    let app = Router::new().route("/", get(strict_list_handler));
+
+    let response = send_list(app, &["streaming, compression", "batching,"]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "[Streaming, Compression, Batching]"
+    );
+}
+
+#[tokio::test]
+async fn test_list_strict_rejects_unknown_token() {
+    let app = Router::new().route("/", get(strict_list_handler));
+
+    let response = send_list(app, &["streaming,teleport,batching"]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[tokio::test]
+async fn test_list_skip_unknown_tokens() {
+    let app = Router::new().route("/", get(lenient_list_handler));
+
+    let response = send_list(app, &["streaming,teleport, batching"]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "Some([Streaming, Batching])"
+    );
+}
+
+#[tokio::test]
+async fn test_list_skip_unknown_absent() {
+    let app = Router::new().route("/", get(lenient_list_handler));
+
+    let response = send_list(app, &[]).await;
+
+    assert_eq!(read_body_string(response).await, "None");
+}
+
+// ============================================================================
+// ALL OPTIONAL TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(all_optional)]
+pub struct AllOptionalHeaders {
+    #[header("x-page")]
+    pub page: u32,
+
+    #[header("x-tag")]
+    pub tags: Vec<String>,
+
+    #[header("x-region", default = "us-east-1")]
+    pub region: String,
+
+    #[header("x-trace")]
+    pub trace: Option<String>,
+}
+
+async fn all_optional_handler(headers: AllOptionalHeaders) -> impl IntoResponse {
+    format!(
+        "page: {}, tags: {:?}, region: {}, trace: {:?}",
+        headers.page, headers.tags, headers.region, headers.trace
+    )
+}
+
+async fn send_all_optional(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(all_optional_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_all_optional_absent_uses_defaults() {
+    let response = send_all_optional(&[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "page: 0, tags: [], region: us-east-1, trace: None"
+    );
+    assert!(AllOptionalHeaders::REQUIRED_HEADER_NAMES.is_empty());
+}
+
+#[tokio::test]
+async fn test_all_optional_present_values() {
+    let response = send_all_optional(&[
+        ("x-page", "3"),
+        ("x-tag", "a"),
+        ("x-tag", "b"),
+        ("x-region", "eu-west-1"),
+        ("x-trace", "abc"),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "page: 3, tags: [\"a\", \"b\"], region: eu-west-1, trace: Some(\"abc\")"
+    );
+}
+
+#[tokio::test]
+async fn test_all_optional_still_rejects_malformed_values() {
+    let response = send_all_optional(&[("x-page", "three")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+// ============================================================================
+// NON-STATIC HEADER NAME TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct UppercaseNameHeaders {
+    #[header("X-Custom-Id")]
+    custom_id: String,
+    #[header("X-Custom-Tags")]
+    tags: Vec<String>,
+}
+
+async fn uppercase_name_handler(headers: UppercaseNameHeaders) -> String {
+    format!("id: {}, tags: {:?}", headers.custom_id, headers.tags)
+}
+
+#[tokio::test]
+async fn test_uppercase_header_name_falls_back_to_runtime_lookup() {
+    let app = Router::new().route("/", get(uppercase_name_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-custom-id", "abc")
+        .header("x-custom-tags", "a")
+        .header("X-Custom-Tags", "b")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "id: abc, tags: [\"a\", \"b\"]"
+    );
+}
+
+// ============================================================================
+// PREFIX TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct MetaHeaders {
+    #[header("x-user-id")]
+    user_id: String,
+    #[header(prefix = "X-Meta-")]
+    meta: std::collections::BTreeMap<String, String>,
+}
+
+async fn meta_handler(headers: MetaHeaders) -> String {
+    format!("user: {}, meta: {:?}", headers.user_id, headers.meta)
+}
+
+async fn send_meta(
+    headers: &[(&str, axum::http::HeaderValue)],
+) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(meta_handler));
+
+    let mut request = Request::builder().uri("/").header("x-user-id", "user123");
+    for (name, value) in headers {
+        request = request.header(*name, value.clone());
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_prefix_collects_matching_headers() {
+    use axum::http::HeaderValue;
+
+    let response = send_meta(&[
+        ("x-meta-region", HeaderValue::from_static("eu")),
+        ("X-Meta-Owner", HeaderValue::from_static("team-a")),
+        ("x-meta-owner", HeaderValue::from_static("team-b")),
+        ("x-metadata", HeaderValue::from_static("ignored")),
+        ("x-meta-", HeaderValue::from_static("ignored")),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, meta: {\"owner\": \"team-a\", \"region\": \"eu\"}"
+    );
+}
+
+#[tokio::test]
+async fn test_prefix_skips_non_ascii_values() {
+    use axum::http::HeaderValue;
+
+    let response = send_meta(&[
+        ("x-meta-region", HeaderValue::from_static("eu")),
+        (
+            "x-meta-name",
+            HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap(),
+        ),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, meta: {\"region\": \"eu\"}"
+    );
+}
+
+#[tokio::test]
+async fn test_prefix_without_matches_is_empty() {
+    let response = send_meta(&[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "user: user123, meta: {}");
+}
+
+#[test]
+fn test_prefix_is_not_a_header_name() {
+    assert_eq!(MetaHeaders::HEADER_NAMES, &["x-user-id"]);
+    assert_eq!(MetaHeaders::REQUIRED_HEADER_NAMES, &["x-user-id"]);
+}
+
+// ============================================================================
+// DURATION SUFFIX TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct TimeoutHeaders {
+    #[header("x-timeout", duration_suffix)]
+    timeout: std::time::Duration,
+    #[header("x-grace", duration_suffix)]
+    grace: Option<std::time::Duration>,
+}
+
+async fn timeout_handler(headers: TimeoutHeaders) -> String {
+    format!(
+        "timeout: {}ms, grace: {:?}",
+        headers.timeout.as_millis(),
+        headers.grace.map(|grace| grace.as_millis())
+    )
+}
+
+async fn send_timeout(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(timeout_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_duration_suffix_units() {
+    for (value, millis) in [
+        ("500ms", 500),
+        ("30s", 30_000),
+        ("2m", 120_000),
+        ("1h", 3_600_000),
+        ("45", 45_000),
+    ] {
+        let response = send_timeout(&[("x-timeout", value)]).await;
+
+        assert_eq!(response.status(), StatusCode::OK, "{value}");
+        assert_eq!(
+            read_body_string(response).await,
+            format!("timeout: {millis}ms, grace: None")
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_duration_suffix_unknown_unit() {
+    for value in ["30d", "1.5s", "s", "30 s", "-5s"] {
+        let response = send_timeout(&[("x-timeout", value)]).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{value}");
+
+        let body = read_body_json(response).await;
+        assert_eq!(body["error"], "header_parse_error");
+    }
+}
+
+#[tokio::test]
+async fn test_duration_suffix_optional() {
+    let response = send_timeout(&[("x-timeout", "1s"), ("x-grace", "250ms")]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "timeout: 1000ms, grace: Some(250)"
+    );
+
+    let response = send_timeout(&[("x-timeout", "1s"), ("x-grace", "1w")]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "timeout: 1000ms, grace: None"
+    );
+}
+
+// ============================================================================
+// INFALLIBLE EXTRACTION TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(infallible)]
+pub struct InspectedHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-count")]
+    pub count: u32,
+
+    #[header("x-limit", default = "50")]
+    pub limit: u32,
+
+    #[header("x-page")]
+    pub page: Option<u32>,
+
+    #[header(problems)]
+    pub problems: Vec<axum_required_headers::HeaderError>,
+}
+
+async fn inspect_handler(headers: InspectedHeaders) -> String {
+    let problems: Vec<_> = headers
+        .problems
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect();
+    format!(
+        "user: {:?}, count: {}, limit: {}, page: {:?}, problems: {problems:?}",
+        headers.user_id, headers.count, headers.limit, headers.page
+    )
+}
+
+async fn send_inspect(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(inspect_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_infallible_records_problems() {
+    let response = send_inspect(&[
+        ("x-user-id", "user123"),
+        ("x-limit", "many"),
+        ("x-page", "-1"),
+    ])
+    .await;
+
+    // Missing and malformed headers never reject the request
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: \"user123\", count: 0, limit: 0, page: None, problems: [\
+         \"Missing required header: `x-count`\", \
+         \"Failed to parse header value: `x-limit`\", \
+         \"Failed to parse header value: `x-page`\"]"
+    );
+}
+
+#[tokio::test]
+async fn test_infallible_without_problems() {
+    let response =
+        send_inspect(&[("x-user-id", "user123"), ("x-count", "3"), ("x-page", "2")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: \"user123\", count: 3, limit: 50, page: Some(2), problems: []"
+    );
+}
+
+#[tokio::test]
+async fn test_infallible_optional_extractor_always_some() {
+    async fn handler(headers: Option<InspectedHeaders>) -> String {
+        match headers {
+            Some(headers) => format!("problems: {}", headers.problems.len()),
+            None => "none".to_string(),
+        }
+    }
+
+    let app = Router::new().route("/", get(handler));
+    let request = Request::builder()
+        .uri("/")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(read_body_string(response).await, "problems: 2");
+}
+
+// ============================================================================
+// SKIPPED FIELD TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(rename_all = "kebab-case", prefix = "x-")]
+pub struct EnrichedHeaders {
+    pub user_id: String,
+
+    #[header(skip)]
+    pub display_name: Option<String>,
+
+    #[header(skip)]
+    pub roles: Vec<String>,
+}
+
+#[test]
+fn test_skipped_fields_not_header_names() {
+    assert_eq!(EnrichedHeaders::HEADER_NAMES, ["x-user-id"]);
+    assert_eq!(EnrichedHeaders::REQUIRED_HEADER_NAMES, ["x-user-id"]);
+}
+
+#[tokio::test]
+async fn test_skipped_fields_default_initialized() {
+    async fn handler(mut headers: EnrichedHeaders) -> String {
+        assert_eq!(headers.display_name, None);
+        assert!(headers.roles.is_empty());
+
+        // downstream enrichment
+        headers.roles.push("admin".to_string());
+        format!("user: {}, roles: {:?}", headers.user_id, headers.roles)
+    }
+
+    let app = Router::new().route("/", get(handler));
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        // headers named like skipped fields are ignored
+        .header("x-display-name", "Jane")
+        .header("x-roles", "owner")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, roles: [\"admin\"]"
+    );
+}
+
+// ============================================================================
+// STATEFUL VALIDATION TESTS
+// ============================================================================
+
+#[derive(Clone)]
+pub struct AppConfig {
+    pub api_keys: Vec<&'static str>,
+}
+
+#[derive(Clone)]
+pub struct AppState {
+    pub config: AppConfig,
+}
+
+impl axum::extract::FromRef<AppState> for AppConfig {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+fn check_key(key: &str, config: &AppConfig) -> Result<(), &'static str> {
+    if config.api_keys.contains(&key) {
+        Ok(())
+    } else {
+        Err("unknown API key")
+    }
+}
+
+fn check_scope(scope: &str, _config: &AppConfig) -> Result<(), String> {
+    match scope {
+        "read" | "write" => Ok(()),
+        other => Err(format!("unsupported scope `{other}`")),
+    }
+}
+
+#[derive(Headers)]
+#[headers(state = "AppConfig")]
+pub struct KeyedHeaders {
+    #[header("x-api-key", validate_with = check_key, trim)]
+    pub api_key: String,
+
+    #[header("x-scope", validate_with = check_scope)]
+    pub scope: Option<String>,
+}
+
+async fn keyed_handler(headers: KeyedHeaders) -> String {
+    format!("key: {}, scope: {:?}", headers.api_key, headers.scope)
+}
+
+async fn send_keyed(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let state = AppState {
+        config: AppConfig {
+            api_keys: vec!["secret-1", "secret-2"],
+        },
+    };
+    let app = Router::new()
+        .route("/", get(keyed_handler))
+        .with_state(state);
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_validate_with_state_accepts() {
+    let response = send_keyed(&[("x-api-key", " secret-2 "), ("x-scope", "read")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "key: secret-2, scope: Some(\"read\")"
+    );
+}
+
+#[tokio::test]
+async fn test_validate_with_state_rejects() {
+    let response = send_keyed(&[("x-api-key", "secret-3")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_validation_error");
+    assert_eq!(
+        body["message"],
+        "Invalid header value for `x-api-key`: unknown API key"
+    );
+}
+
+#[tokio::test]
+async fn test_validate_with_state_optional_field() {
+    // Optional values failing the check become `None`
+    let response = send_keyed(&[("x-api-key", "secret-1"), ("x-scope", "admin")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "key: secret-1, scope: None"
+    );
+}
+
+// ============================================================================
+// REQUIRED IF TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct SignedRequestHeaders {
+    #[header("x-signature", required_if = "X-Signature-Alg")]
+    pub signature: Option<u64>,
+
+    #[header("x-signature-alg")]
+    pub algorithm: Option<String>,
+}
+
+async fn signed_handler(headers: SignedRequestHeaders) -> String {
+    format!(
+        "signature: {:?}, algorithm: {:?}",
+        headers.signature, headers.algorithm
+    )
+}
+
+async fn send_signed(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(signed_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_required_if_without_companion() {
+    let response = send_signed(&[]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: None, algorithm: None"
+    );
+
+    let response = send_signed(&[("x-signature", "42")]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: Some(42), algorithm: None"
+    );
+
+    // Without the companion, malformed values become `None` like for any `Option` field
+    let response = send_signed(&[("x-signature", "abc")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: None, algorithm: None"
+    );
+}
+
+#[tokio::test]
+async fn test_required_if_with_companion() {
+    let response = send_signed(&[("x-signature-alg", "hmac"), ("x-signature", "42")]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: Some(42), algorithm: Some(\"hmac\")"
+    );
+
+    let response = send_signed(&[("x-signature-alg", "hmac")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+    assert!(body["message"].as_str().unwrap().contains("x-signature"));
+
+    let response = send_signed(&[("x-signature-alg", "hmac"), ("x-signature", "abc")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[derive(Headers)]
+#[headers(collect_errors)]
+pub struct CollectedSignedHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-signature", required_if = "x-signature-alg")]
+    pub signature: Option<u64>,
+}
+
+#[tokio::test]
+async fn test_required_if_collect_errors() {
+    async fn handler(headers: CollectedSignedHeaders) -> String {
+        format!(
+            "user: {}, signature: {:?}",
+            headers.user_id, headers.signature
+        )
+    }
+
+    let app = Router::new().route("/", get(handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-signature-alg", "hmac")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "multiple_header_errors");
+    assert_eq!(body["errors"].as_array().unwrap().len(), 2);
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, signature: None"
+    );
+}
+
+#[derive(Headers)]
+#[headers(infallible)]
+pub struct InspectedSignedHeaders {
+    #[header("x-signature", required_if = "x-signature-alg")]
+    pub signature: Option<u64>,
+
+    #[header(problems)]
+    pub problems: Vec<axum_required_headers::HeaderError>,
+}
+
+#[tokio::test]
+async fn test_required_if_infallible() {
+    async fn handler(headers: InspectedSignedHeaders) -> String {
+        format!("{:?}", headers.problems)
+    }
+
+    let app = Router::new().route("/", get(handler));
+    let request = Request::builder()
+        .uri("/")
+        .header("x-signature-alg", "hmac")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    assert_eq!(
+        read_body_string(response).await,
+        "[Missing(\"x-signature\")]"
+    );
+}
+
+#[derive(Headers)]
+pub struct CsrfHeaders {
+    #[header("x-csrf-token", required_if_present = "authorization")]
+    pub csrf_token: Option<String>,
+}
+
+#[tokio::test]
+async fn test_required_if_present_combinations() {
+    async fn handler(headers: CsrfHeaders) -> String {
+        format!("csrf: {:?}", headers.csrf_token)
+    }
+
+    async fn send(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+        let app = Router::new().route("/", get(handler));
+        let mut request = Request::builder().uri("/");
+        for (name, value) in pairs {
+            request = request.header(*name, *value);
+        }
+        app.oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    // Anonymous request without token
+    let response = send(&[]).await;
+    assert_eq!(read_body_string(response).await, "csrf: None");
+
+    // Anonymous request with token
+    let response = send(&[("x-csrf-token", "tok")]).await;
+    assert_eq!(read_body_string(response).await, "csrf: Some(\"tok\")");
+
+    // Authenticated request with token
+    let response = send(&[("authorization", "Bearer abc"), ("x-csrf-token", "tok")]).await;
+    assert_eq!(read_body_string(response).await, "csrf: Some(\"tok\")");
+
+    // Authenticated request without token
+    let response = send(&[("authorization", "Bearer abc")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+    assert_eq!(body["message"], "Missing required header: `x-csrf-token`");
+}
+
+// ============================================================================
+// ALL OR NONE TESTS
+// ============================================================================
+
+#[derive(Headers)]
+#[headers(all_or_none("x-signature", "X-Signature-Timestamp"))]
+pub struct WebhookHeaders {
+    #[header("x-signature")]
+    pub signature: Option<String>,
+
+    #[header("x-signature-timestamp")]
+    pub timestamp: Option<u64>,
+}
+
+async fn webhook_handler(headers: WebhookHeaders) -> String {
+    format!(
+        "signature: {:?}, timestamp: {:?}",
+        headers.signature, headers.timestamp
+    )
+}
+
+async fn send_webhook(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(webhook_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_all_or_none_all_present() {
+    let response = send_webhook(&[
+        ("x-signature", "abc"),
+        ("x-signature-timestamp", "1700000000"),
+    ])
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: Some(\"abc\"), timestamp: Some(1700000000)"
+    );
+}
+
+#[tokio::test]
+async fn test_all_or_none_none_present() {
+    let response = send_webhook(&[]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "signature: None, timestamp: None"
+    );
+}
+
+#[tokio::test]
+async fn test_all_or_none_partial() {
+    let response = send_webhook(&[("x-signature", "abc")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "incomplete_header_group");
+    assert_eq!(
+        body["message"],
+        "Incomplete header group (x-signature, x-signature-timestamp): missing `x-signature-timestamp`"
+    );
+
+    let response = send_webhook(&[("x-signature-timestamp", "1700000000")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "incomplete_header_group");
+}
+
+#[derive(Headers)]
+#[headers(
+    collect_errors,
+    all_or_none("x-signature", "x-signature-timestamp"),
+    all_or_none("x-page", "x-per-page")
+)]
+pub struct CollectedGroupHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+#[tokio::test]
+async fn test_all_or_none_collect_errors() {
+    async fn handler(headers: CollectedGroupHeaders) -> String {
+        headers.user_id
+    }
+
+    let app = Router::new().route("/", get(handler));
+    let request = Request::builder()
+        .uri("/")
+        .header("x-signature", "abc")
+        .header("x-per-page", "10")
+        .body(axum::body::Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "multiple_header_errors");
+    let codes: Vec<_> = body["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error| error["error"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        codes,
+        [
+            "incomplete_header_group",
+            "incomplete_header_group",
+            "missing_header"
+        ]
+    );
+}
+
+// ============================================================================
+// BOX<STR> AND COW<STR> TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct LightweightStrHeaders {
+    #[header("x-tenant-id")]
+    pub tenant_id: Box<str>,
+
+    #[header("x-region")]
+    pub region: std::borrow::Cow<'static, str>,
+
+    #[header("x-label", trim, max_len = 8)]
+    pub label: Option<Box<str>>,
+
+    #[header("x-tag")]
+    pub tags: Option<Vec<std::borrow::Cow<'static, str>>>,
+}
+
+async fn lightweight_handler(headers: LightweightStrHeaders) -> String {
+    assert!(matches!(headers.region, std::borrow::Cow::Owned(_)));
+    format!(
+        "tenant: {}, region: {}, label: {:?}, tags: {:?}",
+        headers.tenant_id, headers.region, headers.label, headers.tags
+    )
+}
+
+async fn send_lightweight(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(lightweight_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_box_and_cow_str_fields() {
+    let response = send_lightweight(&[
+        ("x-tenant-id", "acme"),
+        ("x-region", "eu-west-1"),
+        ("x-label", " beta "),
+        ("x-tag", "a"),
+        ("x-tag", "b"),
+    ])
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "tenant: acme, region: eu-west-1, label: Some(\"beta\"), tags: Some([\"a\", \"b\"])"
+    );
+}
+
+#[tokio::test]
+async fn test_box_and_cow_str_required_and_optional() {
+    let response = send_lightweight(&[("x-region", "eu-west-1")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+
+    // A value failing validation makes the optional field `None`
+    let response = send_lightweight(&[
+        ("x-tenant-id", "acme"),
+        ("x-region", "eu-west-1"),
+        ("x-label", "much too long"),
+    ])
+    .await;
+    assert_eq!(
+        read_body_string(response).await,
+        "tenant: acme, region: eu-west-1, label: None, tags: None"
+    );
+}
+
+// ============================================================================
+// MEDIA PARAM TESTS
+// ============================================================================
+
+#[derive(Debug, axum_required_headers::Header)]
+#[header("x-api-version")]
+pub enum ApiVersion {
+    #[header_value("1")]
+    V1,
+    #[header_value("2")]
+    V2,
+    #[header_value("3")]
+    V3,
+}
+
+#[derive(Headers)]
+pub struct VersionedHeaders {
+    #[header(
+        "x-api-version",
+        alias = "api-version",
+        media_param("Accept", "version"),
+        default = "1"
+    )]
+    pub version: ApiVersion,
+}
+
+async fn versioned_handler(headers: VersionedHeaders) -> String {
+    format!("{:?}", headers.version)
+}
+
+async fn send_versioned(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(versioned_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_media_param_header_wins() {
+    let response = send_versioned(&[
+        ("x-api-version", "3"),
+        ("api-version", "2"),
+        ("accept", "application/json; version=2"),
+    ])
+    .await;
+    assert_eq!(read_body_string(response).await, "V3");
+}
+
+#[tokio::test]
+async fn test_media_param_alias_before_param() {
+    let response = send_versioned(&[
+        ("api-version", "2"),
+        ("accept", "application/json; version=3"),
+    ])
+    .await;
+    assert_eq!(read_body_string(response).await, "V2");
+}
+
+#[tokio::test]
+async fn test_media_param_from_accept() {
+    let response = send_versioned(&[("accept", "application/json;version=2")]).await;
+    assert_eq!(read_body_string(response).await, "V2");
+
+    // The first media type carrying the parameter wins, quotes are removed
+    let response = send_versioned(&[(
+        "accept",
+        "text/html, application/json; q=0.9; Version=\"3\", application/xml; version=2",
+    )])
+    .await;
+    assert_eq!(read_body_string(response).await, "V3");
+}
+
+#[tokio::test]
+async fn test_media_param_default_fallback() {
+    let response = send_versioned(&[]).await;
+    assert_eq!(read_body_string(response).await, "V1");
+
+    let response = send_versioned(&[("accept", "application/json")]).await;
+    assert_eq!(read_body_string(response).await, "V1");
+}
+
+#[tokio::test]
+async fn test_media_param_invalid_value() {
+    let response = send_versioned(&[("accept", "application/json; version=9")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    assert_eq!(
+        body["message"],
+        "Failed to parse header value: `x-api-version`"
+    );
+}
+
+// ============================================================================
+// INTO EXTENSION TESTS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TenantId(String);
+
+impl FromStr for TenantId {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(TenantId(s.to_owned()))
+    }
+}
+
+#[derive(Headers)]
+pub struct TenantHeaders {
+    #[header("x-tenant-id", into_extension)]
+    pub tenant_id: TenantId,
+
+    #[header("x-region", into_extension)]
+    pub region: Option<String>,
+
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+async fn tenant_handler(
+    _headers: TenantHeaders,
+    axum::Extension(tenant_id): axum::Extension<TenantId>,
+    region: Option<axum::Extension<String>>,
+) -> String {
+    format!(
+        "tenant: {}, region: {:?}",
+        tenant_id.0,
+        region.map(|axum::Extension(region)| region)
+    )
+}
+
+async fn send_tenant(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(tenant_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_into_extension_inserts_values() {
+    let response = send_tenant(&[
+        ("x-tenant-id", "acme"),
+        ("x-region", "eu-west-1"),
+        ("x-user-id", "user123"),
+    ])
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "tenant: acme, region: Some(\"eu-west-1\")"
+    );
+}
+
+#[tokio::test]
+async fn test_into_extension_skips_absent_option() {
+    let response = send_tenant(&[("x-tenant-id", "acme"), ("x-user-id", "user123")]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "tenant: acme, region: None"
+    );
+}
+
+#[tokio::test]
+async fn test_into_extension_not_inserted_on_rejection() {
+    use axum::extract::FromRequestParts;
+
+    let (mut parts, ()) = Request::builder()
+        .uri("/")
+        .header("x-tenant-id", "acme")
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    assert!(
+        TenantHeaders::from_request_parts(&mut parts, &())
+            .await
+            .is_err()
+    );
+    assert!(parts.extensions.get::<TenantId>().is_none());
+}
+
+// ============================================================================
+// OR EXTENSION TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct FallbackTenantHeaders {
+    #[header("x-tenant-id", or_extension)]
+    pub tenant_id: TenantId,
+
+    #[header("x-region", or_extension)]
+    pub region: Option<String>,
+}
+
+fn tenant_parts(pairs: &[(&str, &str)]) -> axum::http::request::Parts {
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    request.body(()).unwrap().into_parts().0
+}
+
+#[tokio::test]
+async fn test_or_extension_reads_extension_when_header_absent() {
+    use axum::extract::FromRequestParts;
+
+    let mut parts = tenant_parts(&[]);
+    parts.extensions.insert(TenantId("acme".to_owned()));
+    parts.extensions.insert("eu-west-1".to_owned());
+
+    let headers = FallbackTenantHeaders::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert_eq!(headers.tenant_id, TenantId("acme".to_owned()));
+    assert_eq!(headers.region.as_deref(), Some("eu-west-1"));
+}
+
+#[tokio::test]
+async fn test_or_extension_prefers_header() {
+    use axum::extract::FromRequestParts;
+
+    let mut parts = tenant_parts(&[("x-tenant-id", "from-header")]);
+    parts
+        .extensions
+        .insert(TenantId("from-extension".to_owned()));
+
+    let headers = FallbackTenantHeaders::from_request_parts(&mut parts, &())
+        .await
+        .unwrap();
+    assert_eq!(headers.tenant_id, TenantId("from-header".to_owned()));
+    assert_eq!(headers.region, None);
+}
+
+#[tokio::test]
+async fn test_or_extension_missing_from_both() {
+    use axum::extract::FromRequestParts;
+
+    let mut parts = tenant_parts(&[("x-region", "eu-west-1")]);
+
+    let error = FallbackTenantHeaders::from_request_parts(&mut parts, &())
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        error,
+        axum_required_headers::HeaderError::Missing("x-tenant-id")
+    );
+}
+
+// ============================================================================
+// CONST HEADER NAME TESTS
+// ============================================================================
+
+const X_REQUEST_ID: &str = "x-request-id";
+
+mod names {
+    pub const X_FORWARDED_USER: &str = "X-Forwarded-User";
+}
+
+#[derive(Headers)]
+#[headers(rename_all = "kebab-case", prefix = "x-")]
+pub struct ConstNameHeaders {
+    #[header(name = X_REQUEST_ID)]
+    request_id: String,
+    #[header(name = names::X_FORWARDED_USER)]
+    forwarded_user: Option<String>,
+    trace_id: Option<String>,
+}
+
+async fn const_name_handler(headers: ConstNameHeaders) -> String {
+    format!(
+        "request: {}, user: {:?}, trace: {:?}",
+        headers.request_id, headers.forwarded_user, headers.trace_id
+    )
+}
+
+#[tokio::test]
+async fn test_const_header_name() {
+    let app = Router::new().route("/", get(const_name_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-request-id", "req-1")
+        .header("x-forwarded-user", "alice")
+        .header("x-trace-id", "t-1")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "request: req-1, user: Some(\"alice\"), trace: Some(\"t-1\")"
+    );
+
+    assert_eq!(
+        ConstNameHeaders::HEADER_NAMES,
+        ["x-request-id", "X-Forwarded-User", "x-trace-id"]
+    );
+    assert_eq!(ConstNameHeaders::REQUIRED_HEADER_NAMES, ["x-request-id"]);
+}
+
+pub struct HeaderNames;
+
+impl HeaderNames {
+    pub const USER_ID: &str = "x-user-id";
+    pub const TENANT_ID: &str = "x-tenant-id";
+}
+
+#[derive(Headers)]
+pub struct PositionalConstHeaders {
+    #[header(HeaderNames::USER_ID)]
+    user_id: String,
+    #[header(HeaderNames::TENANT_ID, trim)]
+    tenant_id: Option<String>,
+    #[header(X_REQUEST_ID, default = "none")]
+    request_id: String,
+}
+
+async fn positional_const_handler(headers: PositionalConstHeaders) -> String {
+    format!(
+        "user: {}, tenant: {:?}, request: {}",
+        headers.user_id, headers.tenant_id, headers.request_id
+    )
+}
+
+#[tokio::test]
+async fn test_positional_const_header_name() {
+    let app = Router::new().route("/", get(positional_const_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "user123")
+        .header("x-tenant-id", " acme ")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "user: user123, tenant: Some(\"acme\"), request: none"
+    );
+    assert_eq!(
+        PositionalConstHeaders::HEADER_NAMES,
+        ["x-user-id", "x-tenant-id", "x-request-id"]
+    );
+}
+
+#[tokio::test]
+async fn test_const_header_name_missing() {
+    let app = Router::new().route("/", get(const_name_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["message"], "Missing required header: `x-request-id`");
+}
+
+// ============================================================================
+// NTH OCCURRENCE TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct HopHeaders {
+    #[header("x-hop", nth = 0)]
+    client: String,
+    #[header("x-hop", nth = 1)]
+    edge: Option<String>,
+    #[header("x-hop", nth = 2)]
+    origin: Option<u8>,
+}
+
+async fn hop_handler(headers: HopHeaders) -> String {
+    format!(
+        "client: {}, edge: {:?}, origin: {:?}",
+        headers.client, headers.edge, headers.origin
+    )
+}
+
+async fn send_hops(hops: &[&str]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(hop_handler));
+
+    let mut request = Request::builder().uri("/");
+    for hop in hops {
+        request = request.header("x-hop", *hop);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_nth_occurrences() {
+    let response = send_hops(&["client"]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "client: client, edge: None, origin: None"
+    );
+
+    let response = send_hops(&["client", "edge"]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "client: client, edge: Some(\"edge\"), origin: None"
+    );
+
+    let response = send_hops(&["client", "edge", "7"]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "client: client, edge: Some(\"edge\"), origin: Some(7)"
+    );
+}
+
+#[tokio::test]
+async fn test_nth_required_occurrence_missing() {
+    let response = send_hops(&[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["message"], "Missing required header: `x-hop`");
+}
+
+#[tokio::test]
+async fn test_nth_occurrence_parse_failure() {
+    let response = send_hops(&["client", "edge", "not-a-number"]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "client: client, edge: Some(\"edge\"), origin: None"
+    );
+}
+
+// ============================================================================
+// CSV TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct LanguageHeaders {
+    #[header("accept-language", csv)]
+    pub languages: Vec<String>,
+
+    #[header("x-weights", csv)]
+    pub weights: Option<Vec<u16>>,
+}
+
+async fn language_handler(headers: LanguageHeaders) -> String {
+    format!("{:?} {:?}", headers.languages, headers.weights)
+}
+
+async fn send_languages(pairs: &[(&str, &str)]) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(language_handler));
+
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_csv_elements() {
+    let response = send_languages(&[("accept-language", "en")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "[\"en\"] None");
+
+    let response =
+        send_languages(&[("accept-language", "en, fr ,de"), ("x-weights", "1,,2")]).await;
+    assert_eq!(
+        read_body_string(response).await,
+        "[\"en\", \"fr\", \"de\"] Some([1, 2])"
+    );
+}
+
+#[tokio::test]
+async fn test_csv_empty_value() {
+    let response = send_languages(&[("accept-language", ""), ("x-weights", " , ")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "[] Some([])");
+
+    let response = send_languages(&[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_csv_invalid_element_makes_option_none() {
+    let response = send_languages(&[("accept-language", "en"), ("x-weights", "1,x")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "[\"en\"] None");
+}
+
+// ============================================================================
+// FROM TYPE TESTS
+// ============================================================================
+
+#[derive(axum_required_headers::Header, Debug, PartialEq)]
+#[header("x-user-id", infallible)]
+pub struct UserId(String);
+
+#[derive(axum_required_headers::Header, Debug, PartialEq)]
+#[header("x-tag", infallible)]
+pub struct Tag(String);
+
+#[derive(Headers)]
+#[headers(rename_all = "kebab-case", prefix = "x-")]
+pub struct FromTypeHeaders {
+    #[header(from_type)]
+    user: UserId,
+    #[header(from_type)]
+    tags: Option<Vec<Tag>>,
+    region: Option<String>,
+}
+
+async fn from_type_handler(headers: FromTypeHeaders) -> String {
+    format!("{:?} {:?} {:?}", headers.user, headers.tags, headers.region)
+}
+
+#[tokio::test]
+async fn test_from_type_header_name() {
+    let app = Router::new().route("/", get(from_type_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .header("x-user-id", "u1")
+        .header("x-tag", "a")
+        .header("x-tag", "b")
+        .header("x-region", "eu")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "UserId(\"u1\") Some([Tag(\"a\"), Tag(\"b\")]) Some(\"eu\")"
+    );
+    assert_eq!(
+        FromTypeHeaders::HEADER_NAMES,
+        ["x-user-id", "x-tag", "x-region"]
+    );
+}
+
+#[tokio::test]
+async fn test_from_type_header_missing() {
+    let app = Router::new().route("/", get(from_type_handler));
+
+    let request = Request::builder()
+        .uri("/")
+        .body(axum::body::Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["message"], "Missing required header: `x-user-id`");
+}
+
+// ============================================================================
+// FROM HEADERS TESTS
+// ============================================================================
+
+fn header_map(pairs: &[(&'static str, &'static str)]) -> axum::http::HeaderMap {
+    pairs
+        .iter()
+        .map(|(name, value)| {
+            (
+                axum::http::HeaderName::from_static(name),
+                axum::http::HeaderValue::from_static(value),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_from_headers() {
+    let headers = TestHeaders::from_headers(&header_map(&[
+        ("x-user-id", "user123"),
+        ("x-optional", "on"),
+    ]))
+    .unwrap();
+    assert_eq!(headers.user_id, "user123");
+    assert_eq!(headers.optional_field.as_deref(), Some("on"));
+
+    assert_eq!(
+        TestHeaders::from_headers(&header_map(&[])).err(),
+        Some(axum_required_headers::HeaderError::Missing("x-user-id"))
+    );
+}
+
+#[test]
+fn test_from_headers_collect_errors() {
+    let error = CollectedHeaders::from_headers(&header_map(&[("x-count", "many")]))
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error,
+        axum_required_headers::HeaderError::Multiple(_)
+    ));
+}
+
+#[test]
+fn test_from_headers_infallible() {
+    let headers = InspectedHeaders::from_headers(&header_map(&[("x-count", "many")]));
+    assert_eq!(headers.count, 0);
+    assert_eq!(headers.limit, 50);
+    assert_eq!(
+        headers.problems,
+        [
+            axum_required_headers::HeaderError::Missing("x-user-id"),
+            axum_required_headers::HeaderError::Parse("x-count"),
+        ]
+    );
+}
+
+#[test]
+fn test_from_headers_with_state() {
+    let config = AppConfig {
+        api_keys: vec!["secret-1"],
+    };
+
+    let headers =
+        KeyedHeaders::from_headers(&header_map(&[("x-api-key", " secret-1 ")]), &config).unwrap();
+    assert_eq!(headers.api_key, "secret-1");
+
+    assert!(matches!(
+        KeyedHeaders::from_headers(&header_map(&[("x-api-key", "other")]), &config),
+        Err(axum_required_headers::HeaderError::Validation {
+            name: "x-api-key",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_from_headers_ignores_extensions() {
+    assert_eq!(
+        FallbackTenantHeaders::from_headers(&header_map(&[("x-tenant-id", "acme")]))
+            .unwrap()
+            .tenant_id,
+        TenantId("acme".to_owned())
+    );
+    assert!(FallbackTenantHeaders::from_headers(&header_map(&[])).is_err());
+}
+
+// ============================================================================
+// NON EMPTY TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct NonEmptyHeaders {
+    #[header("x-org-id", alias = "x-organization", non_empty)]
+    pub org_id: String,
+
+    #[header("x-team", non_empty)]
+    pub team: Option<String>,
+
+    #[header("x-region", non_empty, default = "us-east-1")]
+    pub region: String,
+
+    #[header("x-label")]
+    pub label: Option<String>,
+}
+
+#[test]
+fn test_non_empty_field_counts_empty_as_missing() {
+    assert_eq!(
+        NonEmptyHeaders::from_headers(&header_map(&[("x-org-id", "")])).err(),
+        Some(axum_required_headers::HeaderError::Missing("x-org-id"))
+    );
+}
+
+#[test]
+fn test_non_empty_fields() {
+    let headers = NonEmptyHeaders::from_headers(&header_map(&[
+        ("x-org-id", ""),
+        ("x-organization", "acme"),
+        ("x-team", ""),
+        ("x-region", ""),
+        ("x-label", ""),
+    ]))
+    .unwrap();
+    assert_eq!(headers.org_id, "acme");
+    assert_eq!(headers.team, None);
+    assert_eq!(headers.region, "us-east-1");
+    // without `non_empty`, empty values are kept
+    assert_eq!(headers.label.as_deref(), Some(""));
+}
+
+// ============================================================================
+// OR QUERY TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct QueryFallbackHeaders {
+    #[header("x-api-version", or_query = "api_version")]
+    pub api_version: u32,
+
+    #[header("x-locale", or_query = "locale")]
+    pub locale: Option<String>,
+}
+
+async fn query_fallback_handler(headers: QueryFallbackHeaders) -> String {
+    format!(
+        "version: {}, locale: {:?}",
+        headers.api_version, headers.locale
+    )
+}
+
+async fn send_query_fallback(
+    uri: &str,
+    pairs: &[(&str, &str)],
+) -> axum::http::Response<axum::body::Body> {
+    let app = Router::new().route("/", get(query_fallback_handler));
+
+    let mut request = Request::builder().uri(uri);
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    let request = request.body(axum::body::Body::empty()).unwrap();
+
+    app.oneshot(request).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_or_query_reads_query_when_header_absent() {
+    let response = send_query_fallback("/?api_version=2&locale=fr%2DCA+x", &[]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "version: 2, locale: Some(\"fr-CA x\")"
+    );
+}
+
+#[tokio::test]
+async fn test_or_query_header_wins() {
+    let response = send_query_fallback("/?api_version=2", &[("x-api-version", "3")]).await;
+    assert_eq!(read_body_string(response).await, "version: 3, locale: None");
+}
+
+#[tokio::test]
+async fn test_or_query_without_query_string_is_missing() {
+    let response = send_query_fallback("/", &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_or_query_parse_failures() {
+    for uri in ["/?api_version=two", "/?api_version=%FF", "/?api_version=%2"] {
+        let response = send_query_fallback(uri, &[]).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+        let body = read_body_json(response).await;
+        assert_eq!(
+            body["message"], "Failed to parse header value: `x-api-version`",
+            "{uri}"
+        );
+    }
+}
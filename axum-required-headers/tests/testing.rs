@@ -0,0 +1,228 @@
+#![cfg(feature = "testing")]
+
+use axum::body::Body;
+use axum::extract::FromRequestParts;
+use axum::http::{HeaderMap, Request, Response, StatusCode, request::Parts};
+use axum_required_headers::testing::{extract_response, extract_response_with_state, snapshot};
+use axum_required_headers::{Headers, Required};
+use http_body_util::BodyExt;
+
+fn parts(pairs: &[(&str, &[u8])]) -> Parts {
+    let mut request = Request::builder().uri("/");
+    for (name, value) in pairs {
+        request = request.header(*name, *value);
+    }
+    request.body(()).unwrap().into_parts().0
+}
+
+// ============================================================================
+// SNAPSHOT TESTS
+// ============================================================================
+
+#[test]
+fn test_snapshot_independent_of_insertion_order() {
+    let first = parts(&[
+        ("x-user-id", b"user123"),
+        ("Accept", b"text/html"),
+        ("x-tenant-id", b"tenant-1"),
+    ]);
+    let second = parts(&[
+        ("x-tenant-id", b"tenant-1"),
+        ("x-user-id", b"user123"),
+        ("accept", b"text/html"),
+    ]);
+
+    assert_eq!(snapshot(&first), snapshot(&second));
+    assert_eq!(
+        snapshot(&first).keys().collect::<Vec<_>>(),
+        ["accept", "x-tenant-id", "x-user-id"]
+    );
+}
+
+#[test]
+fn test_snapshot_joins_repeated_headers() {
+    let parts = parts(&[
+        ("x-forwarded-for", b"10.0.0.1"),
+        ("x-forwarded-for", b"10.0.0.2"),
+    ]);
+
+    assert_eq!(snapshot(&parts)["x-forwarded-for"], "10.0.0.1, 10.0.0.2");
+}
+
+#[test]
+fn test_snapshot_skips_non_ascii_values() {
+    let parts = parts(&[("x-name", "日本語".as_bytes()), ("x-id", b"1")]);
+
+    let snapshot = snapshot(&parts);
+    assert!(!snapshot.contains_key("x-name"));
+    assert_eq!(snapshot["x-id"], "1");
+}
+
+// ============================================================================
+// EXTRACT RESPONSE TESTS
+// ============================================================================
+
+#[derive(Headers, Debug)]
+#[headers(all_or_none("x-signature", "x-signature-timestamp"))]
+struct ApiHeaders {
+    #[header("x-user-id")]
+    user_id: String,
+
+    #[header("x-page", range(1..=100))]
+    page: u32,
+
+    #[header("x-signature")]
+    signature: Option<String>,
+
+    #[header("x-signature-timestamp")]
+    signature_timestamp: Option<u64>,
+}
+
+#[derive(Headers, Debug)]
+#[headers(collect_errors)]
+struct CollectedHeaders {
+    #[header("x-user-id")]
+    _user_id: String,
+
+    #[header("x-count")]
+    _count: u32,
+}
+
+type Pairs<'a> = &'a [(&'a str, &'a [u8])];
+
+fn headers(pairs: Pairs) -> HeaderMap {
+    parts(pairs).headers
+}
+
+fn rejection<T: std::fmt::Debug>(result: Result<T, Response<Body>>) -> Response<Body> {
+    result.expect_err("extraction should be rejected")
+}
+
+async fn body_json(response: Response<Body>) -> serde_json::Value {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_extract_response_success() {
+    let headers = headers(&[
+        ("x-user-id", b"user123"),
+        ("x-page", b"2"),
+        ("x-signature", b"abc"),
+        ("x-signature-timestamp", b"1700000000"),
+    ]);
+
+    let extracted = extract_response::<ApiHeaders>(&headers).await.unwrap();
+    assert_eq!(extracted.user_id, "user123");
+    assert_eq!(extracted.page, 2);
+    assert_eq!(extracted.signature.as_deref(), Some("abc"));
+    assert_eq!(extracted.signature_timestamp, Some(1_700_000_000));
+}
+
+#[tokio::test]
+async fn test_extract_response_error_kinds() {
+    let cases: [(Pairs, &str, &str); 5] = [
+        (&[], "missing_header", "x-user-id"),
+        (
+            &[("x-user-id", b"user123"), ("x-page", b"two")],
+            "header_parse_error",
+            "x-page",
+        ),
+        (
+            &[("x-user-id", "日本語".as_bytes()), ("x-page", b"1")],
+            "invalid_header_value",
+            "x-user-id",
+        ),
+        (
+            &[("x-user-id", b"user123"), ("x-page", b"101")],
+            "header_validation_error",
+            "x-page",
+        ),
+        (
+            &[
+                ("x-user-id", b"user123"),
+                ("x-page", b"1"),
+                ("x-signature", b"abc"),
+            ],
+            "incomplete_header_group",
+            "x-signature-timestamp",
+        ),
+    ];
+
+    for (pairs, code, header) in cases {
+        let response = rejection(extract_response::<ApiHeaders>(&headers(pairs)).await);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{code}");
+
+        let body = body_json(response).await;
+        assert_eq!(body["error"], code);
+        let message = body["message"].as_str().unwrap();
+        assert!(message.contains(header), "{message}");
+    }
+}
+
+#[tokio::test]
+async fn test_extract_response_multiple_errors() {
+    let response =
+        rejection(extract_response::<CollectedHeaders>(&headers(&[("x-count", b"many")])).await);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = body_json(response).await;
+    assert_eq!(body["error"], "multiple_header_errors");
+}
+
+#[tokio::test]
+async fn test_extract_response_plain_extractor() {
+    #[derive(Debug)]
+    struct UserId(String);
+
+    impl std::str::FromStr for UserId {
+        type Err = std::convert::Infallible;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Self(s.to_owned()))
+        }
+    }
+
+    axum_required_headers::impl_header!(UserId, "x-user-id");
+
+    let Required(user_id) =
+        extract_response_with_state::<Required<UserId>, _>(&headers(&[("x-user-id", b"u1")]), &())
+            .await
+            .unwrap();
+    assert_eq!(user_id.0, "u1");
+
+    let response = rejection(extract_response::<Required<UserId>>(&HeaderMap::new()).await);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_json(response).await["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_extract_response_awaiting_extractor() {
+    #[derive(Debug)]
+    struct Deferred(String);
+
+    impl<S: Send + Sync> FromRequestParts<S> for Deferred {
+        type Rejection = StatusCode;
+
+        async fn from_request_parts(
+            parts: &mut Parts,
+            _state: &S,
+        ) -> Result<Self, Self::Rejection> {
+            // pending once before completing, like an extractor awaiting I/O
+            tokio::task::yield_now().await;
+            let value = parts
+                .headers
+                .get("x-user-id")
+                .ok_or(StatusCode::UNAUTHORIZED)?;
+            Ok(Self(value.to_str().unwrap().to_owned()))
+        }
+    }
+
+    let extracted = extract_response::<Deferred>(&headers(&[("x-user-id", b"u1")]))
+        .await
+        .unwrap();
+    assert_eq!(extracted.0, "u1");
+
+    let response = rejection(extract_response::<Deferred>(&HeaderMap::new()).await);
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
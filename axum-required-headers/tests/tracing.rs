@@ -0,0 +1,256 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, response::IntoResponse, routing::get};
+use axum_required_headers::{Header, Headers};
+use common::{request, send};
+use std::sync::{Arc, Mutex};
+
+#[derive(Headers)]
+#[headers(debug_on_error)]
+pub struct DebugHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+#[derive(Headers)]
+pub struct QuietHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+async fn debug_handler(headers: DebugHeaders) -> impl IntoResponse {
+    headers.user_id
+}
+
+async fn quiet_handler(headers: QuietHeaders) -> impl IntoResponse {
+    headers.user_id
+}
+
+/// Collects formatted log output
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+impl Logs {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl std::io::Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+async fn send_logged(app: Router, user_header: &str) -> (StatusCode, String) {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let response = send(
+        app,
+        &[(user_header, "user123"), ("x-tenant-id", "tenant-1")],
+    )
+    .await;
+    (response.status(), logs.contents())
+}
+
+// ============================================================================
+// DEBUG ON ERROR TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_debug_on_error_logs_header_names() {
+    let app = Router::new().route("/", get(debug_handler));
+
+    let (status, logs) = send_logged(app, "x-userid").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(logs.contains("DEBUG"), "{logs}");
+    assert!(logs.contains("header extraction failed"), "{logs}");
+    assert!(logs.contains("x-userid"), "{logs}");
+    assert!(logs.contains("x-tenant-id"), "{logs}");
+}
+
+#[tokio::test]
+async fn test_debug_on_error_silent_on_success() {
+    let app = Router::new().route("/", get(debug_handler));
+
+    let (status, logs) = send_logged(app, "x-user-id").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(logs.is_empty(), "{logs}");
+}
+
+#[derive(Clone)]
+struct CanonicalHeaders(axum::http::HeaderMap);
+
+impl AsRef<axum::http::HeaderMap> for CanonicalHeaders {
+    fn as_ref(&self) -> &axum::http::HeaderMap {
+        &self.0
+    }
+}
+
+#[derive(Headers)]
+#[headers(debug_on_error, source_extension = "CanonicalHeaders")]
+pub struct CanonicalDebugHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+}
+
+#[tokio::test]
+async fn test_debug_on_error_logs_source_extension_names() {
+    async fn handler(headers: CanonicalDebugHeaders) -> impl IntoResponse {
+        headers.user_id
+    }
+    let mut canonical = axum::http::HeaderMap::new();
+    canonical.insert("x-user-name", "user123".parse().unwrap());
+    let app = Router::new()
+        .route("/", get(handler))
+        .layer(axum::Extension(CanonicalHeaders(canonical)));
+
+    let (status, logs) = send_logged(app, "x-user-id").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(logs.contains("x-user-name"), "{logs}");
+    assert!(!logs.contains("x-tenant-id"), "{logs}");
+}
+
+#[tokio::test]
+async fn test_no_logging_without_attribute() {
+    let app = Router::new().route("/", get(quiet_handler));
+
+    let (status, logs) = send_logged(app, "x-userid").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(logs.is_empty(), "{logs}");
+}
+
+// ============================================================================
+// WARN ON NORMALIZE TESTS
+// ============================================================================
+
+#[derive(Debug, PartialEq, Header)]
+#[header("x-forwarded-proto", warn_on_normalize)]
+enum Scheme {
+    #[header_value("http")]
+    Http,
+    #[header_value("https")]
+    Https,
+}
+
+fn parse_logged(value: &str) -> (Scheme, String) {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let scheme = tracing::subscriber::with_default(subscriber, || value.parse().unwrap());
+    (scheme, logs.contents())
+}
+
+#[test]
+fn test_warn_on_normalize_silent_on_exact_case() {
+    let (scheme, logs) = parse_logged("https");
+
+    assert_eq!(scheme, Scheme::Https);
+    assert!(logs.is_empty(), "{logs}");
+}
+
+#[test]
+fn test_warn_on_normalize_logs_case_mismatch() {
+    let (scheme, logs) = parse_logged("HTTPS");
+
+    assert_eq!(scheme, Scheme::Https);
+    assert!(logs.contains("WARN"), "{logs}");
+    assert!(
+        logs.contains("matched only after case normalization"),
+        "{logs}"
+    );
+    assert!(logs.contains("x-forwarded-proto"), "{logs}");
+    assert!(logs.contains("HTTPS"), "{logs}");
+}
+
+// ============================================================================
+// TRACE TESTS
+// ============================================================================
+
+#[derive(Headers)]
+pub struct TracedHeaders {
+    #[header("x-request-id", trace)]
+    pub request_id: String,
+    #[header("x-retries", trace)]
+    pub retries: Option<u32>,
+}
+
+async fn extract_logged(headers: &[(&str, &str)]) -> (bool, String) {
+    use axum::extract::FromRequestParts;
+    use tracing::Instrument;
+
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (mut parts, ()) = request(headers).body(()).unwrap().into_parts();
+
+    let span = tracing::info_span!(
+        "request",
+        "x-request-id" = tracing::field::Empty,
+        "x-retries" = tracing::field::Empty,
+    );
+    let extracted = async {
+        let result = TracedHeaders::from_request_parts(&mut parts, &()).await;
+        tracing::info!("extracted");
+        result.is_ok()
+    }
+    .instrument(span)
+    .await;
+    (extracted, logs.contents())
+}
+
+#[tokio::test]
+async fn test_trace_records_values_on_span() {
+    let (extracted, logs) = extract_logged(&[("x-request-id", "req-42"), ("x-retries", "3")]).await;
+
+    assert!(extracted);
+    assert!(logs.contains("x-request-id=req-42"), "{logs}");
+    assert!(logs.contains("x-retries=3"), "{logs}");
+    assert!(!logs.contains("WARN"), "{logs}");
+}
+
+#[tokio::test]
+async fn test_trace_skips_absent_optional_value() {
+    let (extracted, logs) = extract_logged(&[("x-request-id", "req-42")]).await;
+
+    assert!(extracted);
+    assert!(logs.contains("x-request-id=req-42"), "{logs}");
+    assert!(!logs.contains("x-retries="), "{logs}");
+}
+
+#[tokio::test]
+async fn test_trace_warns_on_malformed_value() {
+    let (extracted, logs) =
+        extract_logged(&[("x-request-id", "req-42"), ("x-retries", "many")]).await;
+
+    assert!(extracted);
+    assert!(logs.contains("WARN"), "{logs}");
+    assert!(logs.contains("x-retries"), "{logs}");
+    assert!(!logs.contains("x-retries=many"), "{logs}");
+}
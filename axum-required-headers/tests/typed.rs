@@ -0,0 +1,84 @@
+#![cfg(feature = "headers")]
+
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::Headers;
+use axum_required_headers::headers::{Authorization, ContentType, authorization::Bearer};
+use common::{read_body_json, read_body_string, send};
+
+#[derive(Headers)]
+pub struct TypedHeaders {
+    #[header("authorization", typed)]
+    pub authorization: Authorization<Bearer>,
+
+    #[header("content-type", typed)]
+    pub content_type: Option<ContentType>,
+}
+
+async fn handler(headers: TypedHeaders) -> String {
+    format!(
+        "token: {}, content type: {:?}",
+        headers.authorization.token(),
+        headers
+            .content_type
+            .map(|content_type| content_type.to_string())
+    )
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handler))
+}
+
+// ============================================================================
+// TYPED HEADER TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_typed_headers_decoded() {
+    let response = send(
+        app(),
+        &[
+            ("authorization", "Bearer secret-token"),
+            ("content-type", "application/json"),
+        ],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "token: secret-token, content type: Some(\"application/json\")"
+    );
+}
+
+#[tokio::test]
+async fn test_typed_optional_header_absent() {
+    let response = send(app(), &[("authorization", "Bearer secret-token")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "token: secret-token, content type: None"
+    );
+}
+
+#[tokio::test]
+async fn test_typed_header_missing() {
+    let response = send(app(), &[("content-type", "application/json")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+    assert!(body["message"].as_str().unwrap().contains("authorization"));
+}
+
+#[tokio::test]
+async fn test_typed_header_decode_failure() {
+    let response = send(app(), &[("authorization", "Basic dXNlcjpwYXNz")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+    assert!(body["message"].as_str().unwrap().contains("authorization"));
+}
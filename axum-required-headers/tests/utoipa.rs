@@ -0,0 +1,83 @@
+#![cfg(feature = "utoipa")]
+
+use axum_required_headers::Headers;
+use axum_required_headers::utoipa::IntoParams;
+use std::convert::Infallible;
+use std::str::FromStr;
+
+/// A header type without a schema of its own
+pub struct Region;
+
+impl FromStr for Region {
+    type Err = Infallible;
+
+    fn from_str(_: &str) -> Result<Self, Self::Err> {
+        Ok(Region)
+    }
+}
+
+#[derive(Headers)]
+pub struct DocumentedHeaders {
+    #[header("x-user-id")]
+    pub user_id: String,
+
+    #[header("x-page")]
+    pub page: Option<u32>,
+
+    #[header("x-region", default = "us-east-1")]
+    pub region: Region,
+
+    #[header("x-tag", list)]
+    pub tags: Vec<String>,
+
+    #[header(prefix = "x-meta-")]
+    pub meta: std::collections::HashMap<String, String>,
+}
+
+fn parameters() -> Vec<serde_json::Value> {
+    DocumentedHeaders::into_params(|| None)
+        .iter()
+        .map(|parameter| serde_json::to_value(parameter).unwrap())
+        .collect()
+}
+
+// ============================================================================
+// INTO PARAMS TESTS
+// ============================================================================
+
+#[test]
+fn test_into_params_lists_header_parameters() {
+    let names: Vec<_> = parameters()
+        .iter()
+        .map(|parameter| parameter["name"].as_str().unwrap().to_owned())
+        .collect();
+
+    assert_eq!(names, ["x-user-id", "x-page", "x-region", "x-tag"]);
+    assert!(
+        parameters()
+            .iter()
+            .all(|parameter| parameter["in"] == "header")
+    );
+}
+
+#[test]
+fn test_into_params_required_flags() {
+    let required: Vec<_> = parameters()
+        .iter()
+        .map(|parameter| parameter["required"].as_bool().unwrap())
+        .collect();
+
+    assert_eq!(required, [true, false, false, true]);
+}
+
+#[test]
+fn test_into_params_schemas() {
+    let parameters = parameters();
+
+    assert_eq!(parameters[0]["schema"]["type"], "string");
+    assert_eq!(parameters[1]["schema"]["type"], "integer");
+    // types without a schema of their own are documented as strings
+    assert_eq!(parameters[2]["schema"]["type"], "string");
+    assert_eq!(parameters[3]["schema"]["type"], "array");
+    assert_eq!(parameters[3]["schema"]["items"]["type"], "string");
+}
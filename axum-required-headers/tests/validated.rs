@@ -0,0 +1,105 @@
+mod common;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::{AsyncValidateHeader, Header, HeaderError, Validated};
+use common::{read_body_json, read_body_string, send};
+
+#[derive(Debug, Header)]
+#[header("x-api-key", infallible)]
+pub struct ApiKey(String);
+
+/// A mock key store, standing in for a database
+#[derive(Clone)]
+pub struct KeyStore(Arc<HashSet<&'static str>>);
+
+impl KeyStore {
+    async fn contains(&self, key: &str) -> bool {
+        tokio::task::yield_now().await;
+        self.0.contains(key)
+    }
+}
+
+impl AsyncValidateHeader<KeyStore> for ApiKey {
+    async fn validate(&self, store: &KeyStore) -> Result<(), HeaderError> {
+        if store.contains(&self.0).await {
+            Ok(())
+        } else {
+            Err(HeaderError::Validation {
+                name: "x-api-key",
+                reason: "unknown API key".to_owned(),
+            })
+        }
+    }
+}
+
+async fn handler(Validated(ApiKey(key)): Validated<ApiKey>) -> String {
+    format!("key: {key}")
+}
+
+async fn optional_handler(key: Option<Validated<ApiKey>>) -> String {
+    format!("key: {:?}", key.map(|key| key.into_inner().0))
+}
+
+fn app() -> Router {
+    Router::new()
+        .route("/", get(handler))
+        .with_state(key_store())
+}
+
+fn optional_app() -> Router {
+    Router::new()
+        .route("/", get(optional_handler))
+        .with_state(key_store())
+}
+
+fn key_store() -> KeyStore {
+    KeyStore(Arc::new(HashSet::from(["secret-1", "secret-2"])))
+}
+
+// ============================================================================
+// ASYNC VALIDATION TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_known_key_is_accepted() {
+    let response = send(app(), &[("x-api-key", "secret-2")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "key: secret-2");
+}
+
+#[tokio::test]
+async fn test_unknown_key_is_rejected() {
+    let response = send(app(), &[("x-api-key", "guess")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_validation_error");
+    assert!(
+        body["message"]
+            .as_str()
+            .unwrap()
+            .contains("unknown API key")
+    );
+}
+
+#[tokio::test]
+async fn test_missing_key_is_rejected_before_validation() {
+    let response = send(app(), &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(read_body_json(response).await["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_optional_validated() {
+    let response = send(optional_app(), &[]).await;
+    assert_eq!(read_body_string(response).await, "key: None");
+
+    let response = send(optional_app(), &[("x-api-key", "secret-1")]).await;
+    assert_eq!(read_body_string(response).await, "key: Some(\"secret-1\")");
+
+    let response = send(optional_app(), &[("x-api-key", "guess")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
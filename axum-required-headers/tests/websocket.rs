@@ -0,0 +1,106 @@
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::WebSocketHandshake;
+use common::{read_body_json, read_body_string, send};
+
+const VALID: &[(&str, &str)] = &[
+    ("connection", "keep-alive, Upgrade"),
+    ("upgrade", "websocket"),
+    ("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="),
+    ("sec-websocket-version", "13"),
+];
+
+async fn handshake_handler(handshake: WebSocketHandshake) -> String {
+    format!("{} {:?}", handshake.key, handshake.protocols)
+}
+
+fn app() -> Router {
+    Router::new().route("/", get(handshake_handler))
+}
+
+/// The valid handshake with `name` replaced by `value`, or removed if `value` is `None`
+fn with(name: &str, value: Option<&'static str>) -> Vec<(&'static str, &'static str)> {
+    VALID
+        .iter()
+        .filter_map(|(n, v)| {
+            if *n == name {
+                value.map(|value| (*n, value))
+            } else {
+                Some((*n, *v))
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// VALID HANDSHAKE TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_valid_handshake() {
+    let response = send(app(), VALID).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "dGhlIHNhbXBsZSBub25jZQ== []"
+    );
+}
+
+#[tokio::test]
+async fn test_valid_handshake_with_protocols() {
+    let mut headers = VALID.to_vec();
+    headers.push(("sec-websocket-protocol", "chat, superchat"));
+
+    let response = send(app(), &headers).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        read_body_string(response).await,
+        "dGhlIHNhbXBsZSBub25jZQ== [\"chat\", \"superchat\"]"
+    );
+}
+
+// ============================================================================
+// MALFORMED HANDSHAKE TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_missing_handshake_headers() {
+    for name in [
+        "connection",
+        "upgrade",
+        "sec-websocket-key",
+        "sec-websocket-version",
+    ] {
+        let response = send(app(), &with(name, None)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{name}");
+
+        let body = read_body_json(response).await;
+        assert_eq!(body["error"], "missing_header", "{name}");
+        assert!(body["message"].as_str().unwrap().contains(name));
+    }
+}
+
+#[tokio::test]
+async fn test_malformed_handshake_headers() {
+    for (name, value) in [
+        ("connection", "keep-alive"),
+        ("upgrade", "h2c"),
+        ("sec-websocket-key", "not a key"),
+        ("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ"),
+        ("sec-websocket-version", "8"),
+    ] {
+        let response = send(app(), &with(name, Some(value))).await;
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{name}: {value}"
+        );
+
+        let body = read_body_json(response).await;
+        assert_eq!(body["error"], "header_validation_error", "{name}: {value}");
+        assert!(body["message"].as_str().unwrap().contains(name));
+    }
+}
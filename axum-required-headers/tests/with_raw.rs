@@ -0,0 +1,81 @@
+mod common;
+
+use axum::{Router, http::StatusCode, routing::get};
+use axum_required_headers::{Header, WithRaw};
+use common::{read_body_json, read_body_string, send};
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+#[derive(Header)]
+#[header("x-page")]
+struct Page(u32);
+
+impl FromStr for Page {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Page)
+    }
+}
+
+async fn handler(page: WithRaw<Page>) -> String {
+    format!("page: {}, raw: {}", page.0, page.raw)
+}
+
+async fn optional_handler(page: Option<WithRaw<Page>>) -> String {
+    match page {
+        Some(page) => format!("page: {}, raw: {}", page.value.0, page.raw),
+        None => "no page".to_string(),
+    }
+}
+
+// ============================================================================
+// WITH RAW TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn test_with_raw_exposes_parsed_and_raw_values() {
+    let app = Router::new().route("/", get(handler));
+
+    let response = send(app, &[("x-page", "007")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(read_body_string(response).await, "page: 7, raw: 007");
+}
+
+#[tokio::test]
+async fn test_with_raw_missing() {
+    let app = Router::new().route("/", get(handler));
+
+    let response = send(app, &[]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "missing_header");
+}
+
+#[tokio::test]
+async fn test_with_raw_malformed() {
+    let app = Router::new().route("/", get(handler));
+
+    let response = send(app, &[("x-page", "seven")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body_json(response).await;
+    assert_eq!(body["error"], "header_parse_error");
+}
+
+#[tokio::test]
+async fn test_optional_with_raw() {
+    let app = Router::new().route("/", get(optional_handler));
+    let response = send(app, &[("x-page", "2")]).await;
+    assert_eq!(read_body_string(response).await, "page: 2, raw: 2");
+
+    let app = Router::new().route("/", get(optional_handler));
+    let response = send(app, &[]).await;
+    assert_eq!(read_body_string(response).await, "no page");
+
+    let app = Router::new().route("/", get(optional_handler));
+    let response = send(app, &[("x-page", "two")]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}